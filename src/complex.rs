@@ -0,0 +1,434 @@
+// src/complex.rs
+
+pub mod complex {
+    use crate::eigen::eigen::SYMMETRY_TOLERANCE;
+    use crate::linear_algebra::linear_algebra::{
+        scaled_norm2, unwrap_or_panic, LinAlgError, Matrix, Scalar, Vector,
+    };
+    use std::fmt;
+    use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+    // A complex number re + im i in double precision. It implements
+    // `Scalar`, so `Vector<Complex64>` and `Matrix<Complex64>` get all of
+    // the generic container arithmetic, including matrix multiplication.
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    pub struct Complex64 {
+        pub re: f64,
+        pub im: f64,
+    }
+
+    impl Complex64 {
+        pub fn new(re: f64, im: f64) -> Complex64 {
+            Complex64 { re, im }
+        }
+
+        pub fn i() -> Complex64 {
+            Complex64::new(0.0, 1.0)
+        }
+
+        pub fn from_polar(r: f64, theta: f64) -> Complex64 {
+            Complex64::new(r * theta.cos(), r * theta.sin())
+        }
+
+        // (modulus, argument) with the argument in (-pi, pi]
+        pub fn to_polar(self) -> (f64, f64) {
+            (self.norm(), self.arg())
+        }
+
+        pub fn conj(self) -> Complex64 {
+            Complex64::new(self.re, -self.im)
+        }
+
+        // The modulus, without overflow for large parts
+        pub fn norm(self) -> f64 {
+            self.re.hypot(self.im)
+        }
+
+        pub fn norm_sqr(self) -> f64 {
+            self.re * self.re + self.im * self.im
+        }
+
+        pub fn arg(self) -> f64 {
+            self.im.atan2(self.re)
+        }
+
+        pub fn recip(self) -> Complex64 {
+            Complex64::new(1.0, 0.0) / self
+        }
+
+        pub fn is_finite(self) -> bool {
+            self.re.is_finite() && self.im.is_finite()
+        }
+
+        pub fn exp(self) -> Complex64 {
+            Complex64::from_polar(self.re.exp(), self.im)
+        }
+
+        // Principal logarithm, with the branch cut along the negative real
+        // axis
+        pub fn ln(self) -> Complex64 {
+            Complex64::new(self.norm().ln(), self.arg())
+        }
+
+        // Principal square root, in the right half plane. Computed from the
+        // modulus so neither part cancels.
+        pub fn sqrt(self) -> Complex64 {
+            if self.re == 0.0 && self.im == 0.0 {
+                return Complex64::new(0.0, self.im);
+            }
+
+            let t = ((self.re.abs() + self.norm()) / 2.0).sqrt();
+
+            if self.re >= 0.0 {
+                return Complex64::new(t, self.im / (2.0 * t));
+            }
+
+            return Complex64::new(self.im.abs() / (2.0 * t), t.copysign(self.im));
+        }
+
+        // Integer powers by repeated squaring
+        pub fn powi(self, n: i32) -> Complex64 {
+            let mut base = if n < 0 { self.recip() } else { self };
+            let mut exponent = n.unsigned_abs();
+            let mut result = Complex64::new(1.0, 0.0);
+
+            while exponent > 0 {
+                if exponent & 1 == 1 {
+                    result *= base;
+                }
+
+                exponent >>= 1;
+
+                if exponent > 0 {
+                    base *= base;
+                }
+            }
+
+            return result;
+        }
+
+        // Principal powers z^p = exp(p log z)
+        pub fn powf(self, p: f64) -> Complex64 {
+            if p == 0.0 {
+                return Complex64::new(1.0, 0.0);
+            }
+
+            if self.re == 0.0 && self.im == 0.0 {
+                return Complex64::new(0.0, 0.0);
+            }
+
+            let (r, theta) = self.to_polar();
+
+            return Complex64::from_polar(r.powf(p), theta * p);
+        }
+
+        pub fn powc(self, w: Complex64) -> Complex64 {
+            if w.re == 0.0 && w.im == 0.0 {
+                return Complex64::new(1.0, 0.0);
+            }
+
+            if self.re == 0.0 && self.im == 0.0 {
+                return Complex64::new(0.0, 0.0);
+            }
+
+            return (w * self.ln()).exp();
+        }
+    }
+
+    impl Scalar for Complex64 {
+        fn zero() -> Complex64 {
+            Complex64::new(0.0, 0.0)
+        }
+
+        fn one() -> Complex64 {
+            Complex64::new(1.0, 0.0)
+        }
+
+        fn conj(self) -> Complex64 {
+            Complex64::conj(self)
+        }
+    }
+
+    impl From<f64> for Complex64 {
+        fn from(re: f64) -> Complex64 {
+            Complex64::new(re, 0.0)
+        }
+    }
+
+    impl fmt::Display for Complex64 {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            if self.im.is_sign_negative() {
+                write!(f, "{}-{}i", self.re, -self.im)
+            } else {
+                write!(f, "{}+{}i", self.re, self.im)
+            }
+        }
+    }
+
+    /* Operators */
+
+    impl Add for Complex64 {
+        type Output = Complex64;
+
+        fn add(self, rhs: Complex64) -> Complex64 {
+            Complex64::new(self.re + rhs.re, self.im + rhs.im)
+        }
+    }
+
+    impl Sub for Complex64 {
+        type Output = Complex64;
+
+        fn sub(self, rhs: Complex64) -> Complex64 {
+            Complex64::new(self.re - rhs.re, self.im - rhs.im)
+        }
+    }
+
+    impl Mul for Complex64 {
+        type Output = Complex64;
+
+        fn mul(self, rhs: Complex64) -> Complex64 {
+            Complex64::new(
+                self.re * rhs.re - self.im * rhs.im,
+                self.re * rhs.im + self.im * rhs.re,
+            )
+        }
+    }
+
+    // Smith's algorithm, which scales by the larger part of the divisor so
+    // |rhs|^2 is never formed and cannot overflow
+    impl Div for Complex64 {
+        type Output = Complex64;
+
+        fn div(self, rhs: Complex64) -> Complex64 {
+            if rhs.re.abs() >= rhs.im.abs() {
+                let ratio = rhs.im / rhs.re;
+                let denominator = rhs.re + rhs.im * ratio;
+
+                return Complex64::new(
+                    (self.re + self.im * ratio) / denominator,
+                    (self.im - self.re * ratio) / denominator,
+                );
+            }
+
+            let ratio = rhs.re / rhs.im;
+            let denominator = rhs.re * ratio + rhs.im;
+
+            return Complex64::new(
+                (self.re * ratio + self.im) / denominator,
+                (self.im * ratio - self.re) / denominator,
+            );
+        }
+    }
+
+    impl Neg for Complex64 {
+        type Output = Complex64;
+
+        fn neg(self) -> Complex64 {
+            Complex64::new(-self.re, -self.im)
+        }
+    }
+
+    // Mixed arithmetic with real numbers on either side
+    macro_rules! impl_real_ops {
+        ($($op:ident, $method:ident);*) => {
+            $(
+                impl $op<f64> for Complex64 {
+                    type Output = Complex64;
+
+                    fn $method(self, rhs: f64) -> Complex64 {
+                        self.$method(Complex64::from(rhs))
+                    }
+                }
+
+                impl $op<Complex64> for f64 {
+                    type Output = Complex64;
+
+                    fn $method(self, rhs: Complex64) -> Complex64 {
+                        Complex64::from(self).$method(rhs)
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_real_ops!(Add, add; Sub, sub; Mul, mul; Div, div);
+
+    macro_rules! impl_assign_ops {
+        ($($op:ident, $method:ident, $binary:ident);*) => {
+            $(
+                impl $op for Complex64 {
+                    fn $method(&mut self, rhs: Complex64) {
+                        *self = self.$binary(rhs);
+                    }
+                }
+
+                impl $op<f64> for Complex64 {
+                    fn $method(&mut self, rhs: f64) {
+                        *self = self.$binary(rhs);
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_assign_ops!(
+        AddAssign, add_assign, add;
+        SubAssign, sub_assign, sub;
+        MulAssign, mul_assign, mul;
+        DivAssign, div_assign, div
+    );
+
+    // Scaling complex containers by a complex number on the left, which the
+    // primitive-only impls in linear_algebra cannot cover
+    impl Mul<&Vector<Complex64>> for Complex64 {
+        type Output = Vector<Complex64>;
+
+        fn mul(self, rhs: &Vector<Complex64>) -> Vector<Complex64> {
+            rhs * self
+        }
+    }
+
+    impl Mul<&Matrix<Complex64>> for Complex64 {
+        type Output = Matrix<Complex64>;
+
+        fn mul(self, rhs: &Matrix<Complex64>) -> Matrix<Complex64> {
+            rhs * self
+        }
+    }
+
+    /* Complex containers */
+    impl Vector<Complex64> {
+        pub fn from_real(v: &Vector) -> Vector<Complex64> {
+            Vector {
+                size: v.size,
+                data: v.data.iter().map(|&x| Complex64::from(x)).collect(),
+            }
+        }
+
+        pub fn from_parts(re: &Vector, im: &Vector) -> Vector<Complex64> {
+            unwrap_or_panic(Vector::try_from_parts(re, im))
+        }
+
+        pub fn try_from_parts(re: &Vector, im: &Vector) -> Result<Vector<Complex64>, LinAlgError> {
+            if re.size != im.size {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![re.size],
+                    right: vec![im.size],
+                });
+            }
+
+            let data = re
+                .data
+                .iter()
+                .zip(im.data.iter())
+                .map(|(&x, &y)| Complex64::new(x, y))
+                .collect();
+
+            return Ok(Vector {
+                size: re.size,
+                data,
+            });
+        }
+
+        pub fn real(&self) -> Vector {
+            Vector {
+                size: self.size,
+                data: self.data.iter().map(|z| z.re).collect(),
+            }
+        }
+
+        pub fn imag(&self) -> Vector {
+            Vector {
+                size: self.size,
+                data: self.data.iter().map(|z| z.im).collect(),
+            }
+        }
+
+        // Euclidean norm sqrt(v^H v), which is the 2-norm of all the real
+        // and imaginary parts, scaled so that it does not overflow
+        pub fn norm(&self) -> f64 {
+            let parts: Vec<f64> = self.data.iter().flat_map(|z| [z.re, z.im]).collect();
+
+            return scaled_norm2(&parts);
+        }
+    }
+
+    impl Matrix<Complex64> {
+        pub fn from_real(m: &Matrix) -> Matrix<Complex64> {
+            Matrix::from_vec(
+                m.rows,
+                m.cols,
+                m.data.iter().map(|&x| Complex64::from(x)).collect(),
+            )
+        }
+
+        pub fn from_parts(re: &Matrix, im: &Matrix) -> Matrix<Complex64> {
+            unwrap_or_panic(Matrix::try_from_parts(re, im))
+        }
+
+        pub fn try_from_parts(re: &Matrix, im: &Matrix) -> Result<Matrix<Complex64>, LinAlgError> {
+            if re.rows != im.rows || re.cols != im.cols {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![re.rows, re.cols],
+                    right: vec![im.rows, im.cols],
+                });
+            }
+
+            let data = re
+                .data
+                .iter()
+                .zip(im.data.iter())
+                .map(|(&x, &y)| Complex64::new(x, y))
+                .collect();
+
+            return Ok(Matrix::from_vec(re.rows, re.cols, data));
+        }
+
+        pub fn real(&self) -> Matrix {
+            Matrix::from_vec(
+                self.rows,
+                self.cols,
+                self.data.iter().map(|z| z.re).collect(),
+            )
+        }
+
+        pub fn imag(&self) -> Matrix {
+            Matrix::from_vec(
+                self.rows,
+                self.cols,
+                self.data.iter().map(|z| z.im).collect(),
+            )
+        }
+
+        pub fn is_hermitian(&self) -> bool {
+            check_hermitian(self).is_ok()
+        }
+    }
+
+    // A = A^H up to rounding, relative to the largest entry, in the same
+    // way eigen::check_symmetric treats real matrices. The diagonal must be
+    // real.
+    pub fn check_hermitian(a: &Matrix<Complex64>) -> Result<(), LinAlgError> {
+        if a.rows != a.cols {
+            return Err(LinAlgError::NotSquare {
+                rows: a.rows,
+                cols: a.cols,
+            });
+        }
+
+        let n = a.rows;
+        let max = a.data.iter().fold(0.0f64, |acc, z| acc.max(z.norm()));
+
+        for i in 0..n {
+            for j in 0..=i {
+                let difference = a.data[i * n + j] - a.data[j * n + i].conj();
+
+                if difference.norm() > SYMMETRY_TOLERANCE * max {
+                    return Err(LinAlgError::NotHermitian { row: i, col: j });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,459 @@
+// src/convolution.rs
+
+pub mod convolution {
+    use crate::linear_algebra::linear_algebra::{
+        unwrap_or_panic, LinAlgError, Matrix, Real, Scalar, Tensor,
+    };
+    use std::cmp::Ordering;
+
+    // Layout: channels first, the N-D generalisation of NCHW.
+    //
+    //   input                 [N, C_in, d_1, ..., d_k]
+    //   convolution weight    [C_out, C_in, k_1, ..., k_k]
+    //   transposed weight     [C_in, C_out, k_1, ..., k_k]
+    //   output                [N, C_out, o_1, ..., o_k]
+    //
+    // N is the batch and there can be any number k >= 1 of spatial axes, so
+    // the same functions cover 1-D signals, 2-D images and 3-D volumes. As
+    // in deep learning libraries "convolution" is cross-correlation, the
+    // kernel is not flipped. There is no bias term, add one with
+    // `broadcast_add` and a [C_out, 1, ..., 1] tensor.
+
+    // Per spatial axis settings. An empty list takes the default, a single
+    // value applies to every spatial axis, otherwise there must be one
+    // value per spatial axis.
+    #[derive(Debug, Clone, Default)]
+    pub struct ConvOptions {
+        // Defaults to 1 for convolutions and to the kernel size for pooling
+        pub stride: Vec<usize>,
+        // Implicit zeros on both sides of every spatial axis, default 0
+        pub padding: Vec<usize>,
+        // Spacing between kernel taps, default 1
+        pub dilation: Vec<usize>,
+        // Extra size added to one side of a transposed convolution's output,
+        // to pick among the input sizes a strided convolution maps to the
+        // same output size. Default 0, and ignored everywhere else.
+        pub output_padding: Vec<usize>,
+    }
+
+    // Resolved settings for every spatial axis
+    struct Window {
+        kernel: Vec<usize>,
+        stride: Vec<usize>,
+        padding: Vec<usize>,
+        dilation: Vec<usize>,
+    }
+
+    /* Convolution */
+    pub fn conv<T: Scalar>(
+        input: &Tensor<T>,
+        weight: &Tensor<T>,
+        options: &ConvOptions,
+    ) -> Tensor<T> {
+        unwrap_or_panic(try_conv(input, weight, options))
+    }
+
+    // im2col: the receptive field of every output position becomes a column
+    // of a [C_in * K, O] matrix, K kernel taps and O output positions, and
+    // one matrix product with the [C_out, C_in * K] weights convolves the
+    // whole batch element
+    pub fn try_conv<T: Scalar>(
+        input: &Tensor<T>,
+        weight: &Tensor<T>,
+        options: &ConvOptions,
+    ) -> Result<Tensor<T>, LinAlgError> {
+        check_weight(input, weight, 1)?;
+
+        let window = resolve(options, &weight.shape[2..], &[1])?;
+        let spatial = &input.shape[2..];
+        let output = conv_output(spatial, &window)?;
+
+        let (batch, c_in, c_out) = (input.shape[0], input.shape[1], weight.shape[0]);
+        let (input_size, taps, positions) = (
+            spatial.iter().product::<usize>(),
+            window.kernel.iter().product::<usize>(),
+            output.iter().product::<usize>(),
+        );
+
+        let x = input.to_contiguous();
+        let w = Matrix::from_vec(c_out, c_in * taps, weight.to_contiguous().data);
+        let sources = tap_offsets(&output, spatial, &window);
+
+        let mut result = Vec::with_capacity(batch * c_out * positions);
+
+        for n in 0..batch {
+            let mut columns = Matrix::zeros(c_in * taps, positions);
+
+            for c in 0..c_in {
+                let channel = &x.data[(n * c_in + c) * input_size..][..input_size];
+
+                for (t, row) in sources.iter().enumerate() {
+                    let out = &mut columns.data[(c * taps + t) * positions..][..positions];
+
+                    for (o, source) in row.iter().enumerate() {
+                        if let Some(s) = source {
+                            out[o] = channel[*s];
+                        }
+                    }
+                }
+            }
+
+            result.extend(Matrix::matrix_mul(&w, &columns).data);
+        }
+
+        return Tensor::try_from_vec(&[&[batch, c_out][..], &output].concat(), result);
+    }
+
+    pub fn conv_transpose<T: Scalar>(
+        input: &Tensor<T>,
+        weight: &Tensor<T>,
+        options: &ConvOptions,
+    ) -> Tensor<T> {
+        unwrap_or_panic(try_conv_transpose(input, weight, options))
+    }
+
+    // The adjoint of `conv` with the same settings, which maps a conv output
+    // shape back to its input shape. Computed as col2im: one product with
+    // the transposed weights gives every tap's contribution, which is then
+    // scattered into the output.
+    pub fn try_conv_transpose<T: Scalar>(
+        input: &Tensor<T>,
+        weight: &Tensor<T>,
+        options: &ConvOptions,
+    ) -> Result<Tensor<T>, LinAlgError> {
+        check_weight(input, weight, 0)?;
+
+        let window = resolve(options, &weight.shape[2..], &[1])?;
+        let spatial = &input.shape[2..];
+        let k = spatial.len();
+        let extra = expand(&options.output_padding, &[0], k, "output padding")?;
+
+        let mut output = Vec::with_capacity(k);
+
+        for a in 0..k {
+            if extra[a] >= window.stride[a].max(window.dilation[a]) {
+                return Err(LinAlgError::InvalidParameter {
+                    name: "output padding",
+                    reason: "must be smaller than the stride or the dilation",
+                });
+            }
+
+            if spatial[a] == 0 {
+                return Err(LinAlgError::InvalidParameter {
+                    name: "input",
+                    reason: "has an empty spatial axis",
+                });
+            }
+
+            // Inverse of the conv_output formula
+            let size = (spatial[a] - 1) * window.stride[a]
+                + window.dilation[a] * (window.kernel[a] - 1)
+                + 1
+                + extra[a];
+
+            if size <= 2 * window.padding[a] {
+                return Err(LinAlgError::InvalidParameter {
+                    name: "padding",
+                    reason: "leaves an empty output",
+                });
+            }
+
+            output.push(size - 2 * window.padding[a]);
+        }
+
+        let (batch, c_in, c_out) = (input.shape[0], input.shape[1], weight.shape[1]);
+        let (input_size, taps, output_size) = (
+            spatial.iter().product::<usize>(),
+            window.kernel.iter().product::<usize>(),
+            output.iter().product::<usize>(),
+        );
+
+        let x = input.to_contiguous();
+        let w = Matrix::from_vec(c_in, c_out * taps, weight.to_contiguous().data);
+        let w_t = Matrix::matrix_transpose(&w);
+        let targets = tap_offsets(spatial, &output, &window);
+
+        let mut result = vec![T::zero(); batch * c_out * output_size];
+
+        for n in 0..batch {
+            let x_n = Matrix::from_vec(
+                c_in,
+                input_size,
+                x.data[n * c_in * input_size..(n + 1) * c_in * input_size].to_vec(),
+            );
+            let columns = Matrix::matrix_mul(&w_t, &x_n);
+
+            for c in 0..c_out {
+                let out = &mut result[(n * c_out + c) * output_size..][..output_size];
+
+                for (t, row) in targets.iter().enumerate() {
+                    let contributions = &columns.data[(c * taps + t) * input_size..][..input_size];
+
+                    for (i, target) in row.iter().enumerate() {
+                        if let Some(o) = target {
+                            out[*o] = out[*o] + contributions[i];
+                        }
+                    }
+                }
+            }
+        }
+
+        return Tensor::try_from_vec(&[&[batch, c_out][..], &output].concat(), result);
+    }
+
+    /* Pooling */
+
+    // Windows are per channel, so the output keeps the input's channels.
+    // Padded positions never take part: they are skipped by the max and
+    // left out of the average's count. A window lying entirely in the
+    // padding gives zero.
+    pub fn max_pool<T: Scalar + PartialOrd>(
+        input: &Tensor<T>,
+        kernel: &[usize],
+        options: &ConvOptions,
+    ) -> Tensor<T> {
+        unwrap_or_panic(try_max_pool(input, kernel, options))
+    }
+
+    // NaN propagates, as in `Tensor::max`
+    pub fn try_max_pool<T: Scalar + PartialOrd>(
+        input: &Tensor<T>,
+        kernel: &[usize],
+        options: &ConvOptions,
+    ) -> Result<Tensor<T>, LinAlgError> {
+        pool(input, kernel, options, |values| {
+            let mut best = *values.first()?;
+
+            for &v in values {
+                match v.partial_cmp(&best) {
+                    Some(Ordering::Greater) => best = v,
+                    None if v.partial_cmp(&v).is_none() => return Some(v),
+                    _ => {}
+                }
+            }
+
+            Some(best)
+        })
+    }
+
+    pub fn avg_pool<T: Real>(
+        input: &Tensor<T>,
+        kernel: &[usize],
+        options: &ConvOptions,
+    ) -> Tensor<T> {
+        unwrap_or_panic(try_avg_pool(input, kernel, options))
+    }
+
+    pub fn try_avg_pool<T: Real>(
+        input: &Tensor<T>,
+        kernel: &[usize],
+        options: &ConvOptions,
+    ) -> Result<Tensor<T>, LinAlgError> {
+        pool(input, kernel, options, |values| {
+            let sum = values.iter().fold(T::zero(), |acc, &v| acc + v);
+
+            Some(sum / T::from_f64(values.len() as f64))
+        })
+    }
+
+    fn pool<T: Scalar, F: Fn(&[T]) -> Option<T>>(
+        input: &Tensor<T>,
+        kernel: &[usize],
+        options: &ConvOptions,
+        reduce: F,
+    ) -> Result<Tensor<T>, LinAlgError> {
+        check_input(input)?;
+
+        let spatial = &input.shape[2..];
+
+        if kernel.len() != spatial.len() {
+            return Err(LinAlgError::DimensionMismatch {
+                left: input.shape.clone(),
+                right: kernel.to_vec(),
+            });
+        }
+
+        let window = resolve(options, kernel, kernel)?;
+        let output = conv_output(spatial, &window)?;
+
+        let planes = input.shape[0] * input.shape[1];
+        let (input_size, positions) = (
+            spatial.iter().product::<usize>(),
+            output.iter().product::<usize>(),
+        );
+
+        let x = input.to_contiguous();
+        let sources = tap_offsets(&output, spatial, &window);
+
+        let mut result = Vec::with_capacity(planes * positions);
+        let mut values = Vec::with_capacity(sources.len());
+
+        for p in 0..planes {
+            let plane = &x.data[p * input_size..][..input_size];
+
+            for o in 0..positions {
+                values.clear();
+                values.extend(sources.iter().filter_map(|row| row[o].map(|s| plane[s])));
+
+                result.push(reduce(&values).unwrap_or(T::zero()));
+            }
+        }
+
+        return Tensor::try_from_vec(&[&input.shape[..2], &output].concat(), result);
+    }
+
+    /* Shapes and windows */
+
+    fn check_input<T: Scalar>(input: &Tensor<T>) -> Result<(), LinAlgError> {
+        if input.ndim() < 3 {
+            return Err(LinAlgError::InvalidParameter {
+                name: "input",
+                reason: "needs batch, channel and at least one spatial axis",
+            });
+        }
+
+        Ok(())
+    }
+
+    // The weight's input channels sit on `channel_axis`, and both tensors
+    // need the same number of spatial axes
+    fn check_weight<T: Scalar>(
+        input: &Tensor<T>,
+        weight: &Tensor<T>,
+        channel_axis: usize,
+    ) -> Result<(), LinAlgError> {
+        check_input(input)?;
+
+        if weight.ndim() != input.ndim() || weight.shape[channel_axis] != input.shape[1] {
+            return Err(LinAlgError::DimensionMismatch {
+                left: input.shape.clone(),
+                right: weight.shape.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn resolve(
+        options: &ConvOptions,
+        kernel: &[usize],
+        default_stride: &[usize],
+    ) -> Result<Window, LinAlgError> {
+        let k = kernel.len();
+
+        let window = Window {
+            kernel: kernel.to_vec(),
+            stride: expand(&options.stride, default_stride, k, "stride")?,
+            padding: expand(&options.padding, &[0], k, "padding")?,
+            dilation: expand(&options.dilation, &[1], k, "dilation")?,
+        };
+
+        if window.stride.contains(&0) || window.dilation.contains(&0) {
+            return Err(LinAlgError::InvalidParameter {
+                name: "stride or dilation",
+                reason: "must be positive",
+            });
+        }
+
+        if kernel.contains(&0) {
+            return Err(LinAlgError::InvalidParameter {
+                name: "kernel",
+                reason: "must not be empty",
+            });
+        }
+
+        return Ok(window);
+    }
+
+    fn expand(
+        values: &[usize],
+        default: &[usize],
+        k: usize,
+        name: &'static str,
+    ) -> Result<Vec<usize>, LinAlgError> {
+        let values = if values.is_empty() { default } else { values };
+
+        if values.len() == 1 {
+            return Ok(vec![values[0]; k]);
+        }
+
+        if values.len() != k {
+            return Err(LinAlgError::InvalidParameter {
+                name,
+                reason: "needs one value or one per spatial axis",
+            });
+        }
+
+        return Ok(values.to_vec());
+    }
+
+    // o = (d + 2p - dilation (k - 1) - 1) / stride + 1 on every axis
+    fn conv_output(spatial: &[usize], window: &Window) -> Result<Vec<usize>, LinAlgError> {
+        let mut result = Vec::with_capacity(spatial.len());
+
+        for a in 0..spatial.len() {
+            let span = window.dilation[a] * (window.kernel[a] - 1) + 1;
+            let padded = spatial[a] + 2 * window.padding[a];
+
+            if padded < span {
+                return Err(LinAlgError::InvalidParameter {
+                    name: "kernel",
+                    reason: "is larger than the padded input",
+                });
+            }
+
+            result.push((padded - span) / window.stride[a] + 1);
+        }
+
+        return Ok(result);
+    }
+
+    // For every kernel tap t and every position o of the strided grid, the
+    // flat offset into the dense grid of tap t of the window at o, or None
+    // where that falls in the padding. Axis a of the window at o starts at
+    // o_a * stride_a - padding_a. Row-major over both grids.
+    fn tap_offsets(strided: &[usize], dense: &[usize], window: &Window) -> Vec<Vec<Option<usize>>> {
+        let k = strided.len();
+        let taps = window.kernel.iter().product::<usize>();
+        let positions = strided.iter().product::<usize>();
+
+        let mut tap = vec![0; k];
+        let mut position = vec![0; k];
+        let mut result = Vec::with_capacity(taps);
+
+        for t in 0..taps {
+            unravel(t, &window.kernel, &mut tap);
+
+            let row = (0..positions)
+                .map(|o| {
+                    unravel(o, strided, &mut position);
+
+                    let mut offset = 0;
+
+                    for a in 0..k {
+                        let coordinate = (position[a] * window.stride[a]
+                            + tap[a] * window.dilation[a])
+                            .checked_sub(window.padding[a])
+                            .filter(|&c| c < dense[a])?;
+
+                        offset = offset * dense[a] + coordinate;
+                    }
+
+                    Some(offset)
+                })
+                .collect();
+
+            result.push(row);
+        }
+
+        return result;
+    }
+
+    // Row-major multi-index of a flat position
+    fn unravel(mut flat: usize, shape: &[usize], index: &mut [usize]) {
+        for a in (0..shape.len()).rev() {
+            index[a] = flat % shape[a];
+            flat /= shape[a];
+        }
+    }
+}
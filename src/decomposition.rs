@@ -0,0 +1,1102 @@
+// src/decomposition.rs
+
+pub mod decomposition {
+    use crate::linear_algebra::linear_algebra::{LinAlgError, Matrix, Vector};
+
+    // LU factorization with partial pivoting, PA = LU. The unit lower
+    // triangular L (without its diagonal) and U are packed into one matrix.
+    #[derive(Debug, Clone)]
+    pub struct LU {
+        pub lu: Matrix,
+        // Row i of PA is row `pivots[i]` of A
+        pub pivots: Vec<usize>,
+        sign: f64,
+        norm: f64,
+        tolerance: f64,
+    }
+
+    pub(crate) fn check_square(a: &Matrix) -> Result<(), LinAlgError> {
+        if a.rows != a.cols {
+            return Err(LinAlgError::NotSquare {
+                rows: a.rows,
+                cols: a.cols,
+            });
+        }
+
+        Ok(())
+    }
+
+    // Maximum absolute column sum
+    pub(crate) fn norm_one(a: &Matrix) -> f64 {
+        (0..a.cols)
+            .map(|j| {
+                (0..a.rows)
+                    .map(|i| a.data[i * a.cols + j].abs())
+                    .sum::<f64>()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    impl LU {
+        pub fn new(a: &Matrix) -> Result<LU, LinAlgError> {
+            check_square(a)?;
+
+            let n = a.rows;
+            let mut lu = a.clone();
+            let mut pivots: Vec<usize> = (0..n).collect();
+            let mut sign = 1.0;
+
+            for k in 0..n {
+                // Pick the largest remaining entry in column `k` as the pivot
+                let mut p = k;
+
+                for i in k + 1..n {
+                    if lu.data[i * n + k].abs() > lu.data[p * n + k].abs() {
+                        p = i;
+                    }
+                }
+
+                if p != k {
+                    for j in 0..n {
+                        lu.data.swap(k * n + j, p * n + j);
+                    }
+
+                    pivots.swap(k, p);
+                    sign = -sign;
+                }
+
+                let pivot = lu.data[k * n + k];
+
+                // A zero column is left as is, `is_singular` reports it later
+                if pivot == 0.0 {
+                    continue;
+                }
+
+                for i in k + 1..n {
+                    let factor = lu.data[i * n + k] / pivot;
+                    lu.data[i * n + k] = factor;
+
+                    for j in k + 1..n {
+                        lu.data[i * n + j] -= factor * lu.data[k * n + j];
+                    }
+                }
+            }
+
+            let max = a.data.iter().fold(0.0f64, |acc, &x| acc.max(x.abs()));
+
+            Ok(LU {
+                lu,
+                pivots,
+                sign,
+                norm: norm_one(a),
+                tolerance: n as f64 * f64::EPSILON * max,
+            })
+        }
+
+        pub fn size(&self) -> usize {
+            self.lu.rows
+        }
+
+        pub fn l(&self) -> Matrix {
+            let n = self.size();
+            let mut result = Matrix::new(n, n);
+
+            for i in 0..n {
+                for j in 0..i {
+                    result.data[i * n + j] = self.lu.data[i * n + j];
+                }
+
+                result.data[i * n + i] = 1.0;
+            }
+
+            return result;
+        }
+
+        pub fn u(&self) -> Matrix {
+            let n = self.size();
+            let mut result = Matrix::new(n, n);
+
+            for i in 0..n {
+                for j in i..n {
+                    result.data[i * n + j] = self.lu.data[i * n + j];
+                }
+            }
+
+            return result;
+        }
+
+        // The permutation matrix P in PA = LU
+        pub fn p(&self) -> Matrix {
+            let n = self.size();
+            let mut result = Matrix::new(n, n);
+
+            for (i, &p) in self.pivots.iter().enumerate() {
+                result.data[i * n + p] = 1.0;
+            }
+
+            return result;
+        }
+
+        // A pivot at or below n * eps * max|a_ij| is treated as zero
+        pub fn is_singular(&self) -> bool {
+            let n = self.size();
+
+            (0..n).any(|i| self.lu.data[i * n + i].abs() <= self.tolerance)
+        }
+
+        pub fn determinant(&self) -> f64 {
+            let n = self.size();
+
+            (0..n).fold(self.sign, |acc, i| acc * self.lu.data[i * n + i])
+        }
+
+        // Estimate of the reciprocal condition number in the 1-norm. Values
+        // close to machine epsilon mean the matrix is numerically singular.
+        pub fn rcond(&self) -> f64 {
+            let n = self.size();
+
+            if n == 0 {
+                return 1.0;
+            }
+
+            if self.is_singular() || self.norm == 0.0 {
+                return 0.0;
+            }
+
+            // Hager's estimate of ||A^-1||_1
+            let mut x = vec![1.0 / n as f64; n];
+            let mut estimate = 0.0;
+
+            for iteration in 0..5 {
+                let y = self.solve_unchecked(&x);
+                estimate = y.iter().map(|v| v.abs()).sum::<f64>();
+
+                let signs: Vec<f64> = y
+                    .iter()
+                    .map(|&v| if v >= 0.0 { 1.0 } else { -1.0 })
+                    .collect();
+                let z = self.solve_transpose_unchecked(&signs);
+
+                let mut j = 0;
+
+                for i in 1..n {
+                    if z[i].abs() > z[j].abs() {
+                        j = i;
+                    }
+                }
+
+                let zx: f64 = z.iter().zip(x.iter()).map(|(&a, &b)| a * b).sum();
+
+                if iteration > 0 && z[j].abs() <= zx {
+                    break;
+                }
+
+                x = vec![0.0; n];
+                x[j] = 1.0;
+            }
+
+            return 1.0 / (self.norm * estimate);
+        }
+
+        pub fn solve(&self, b: &Vector) -> Result<Vector, LinAlgError> {
+            if b.size != self.size() {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![self.size(), self.size()],
+                    right: vec![b.size],
+                });
+            }
+
+            if self.is_singular() {
+                return Err(LinAlgError::SingularMatrix);
+            }
+
+            let mut result = Vector::new(b.size);
+            result.data = self.solve_unchecked(&b.data);
+
+            return Ok(result);
+        }
+
+        // Solves AX = B for every column of B at once
+        pub fn solve_matrix(&self, b: &Matrix) -> Result<Matrix, LinAlgError> {
+            let n = self.size();
+
+            if b.rows != n {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![n, n],
+                    right: vec![b.rows, b.cols],
+                });
+            }
+
+            if self.is_singular() {
+                return Err(LinAlgError::SingularMatrix);
+            }
+
+            let mut result = Matrix::new(n, b.cols);
+
+            for j in 0..b.cols {
+                let column: Vec<f64> = (0..n).map(|i| b.data[i * b.cols + j]).collect();
+                let x = self.solve_unchecked(&column);
+
+                for i in 0..n {
+                    result.data[i * b.cols + j] = x[i];
+                }
+            }
+
+            return Ok(result);
+        }
+
+        pub fn inverse(&self) -> Result<Matrix, LinAlgError> {
+            let n = self.size();
+            let mut identity = Matrix::new(n, n);
+
+            for i in 0..n {
+                identity.data[i * n + i] = 1.0;
+            }
+
+            return self.solve_matrix(&identity);
+        }
+
+        // Forward substitution with L, then back substitution with U
+        fn solve_unchecked(&self, b: &[f64]) -> Vec<f64> {
+            let n = self.size();
+            let lu = &self.lu.data;
+            let mut x: Vec<f64> = self.pivots.iter().map(|&p| b[p]).collect();
+
+            for i in 0..n {
+                for j in 0..i {
+                    x[i] -= lu[i * n + j] * x[j];
+                }
+            }
+
+            for i in (0..n).rev() {
+                for j in i + 1..n {
+                    x[i] -= lu[i * n + j] * x[j];
+                }
+
+                x[i] /= lu[i * n + i];
+            }
+
+            return x;
+        }
+
+        // Solves A^T x = b, i.e. U^T L^T P x = b
+        fn solve_transpose_unchecked(&self, b: &[f64]) -> Vec<f64> {
+            let n = self.size();
+            let lu = &self.lu.data;
+            let mut y = b.to_vec();
+
+            for i in 0..n {
+                for j in 0..i {
+                    y[i] -= lu[j * n + i] * y[j];
+                }
+
+                y[i] /= lu[i * n + i];
+            }
+
+            for i in (0..n).rev() {
+                for j in i + 1..n {
+                    y[i] -= lu[j * n + i] * y[j];
+                }
+            }
+
+            let mut x = vec![0.0; n];
+
+            for (i, &p) in self.pivots.iter().enumerate() {
+                x[p] = y[i];
+            }
+
+            return x;
+        }
+    }
+
+    // Householder QR factorization, A = QR or AP = QR with column pivoting.
+    // R sits in the upper triangle of `qr` and the Householder vectors, with
+    // an implicit leading 1, are stored below the diagonal.
+    #[derive(Debug, Clone)]
+    pub struct QR {
+        pub qr: Matrix,
+        pub tau: Vec<f64>,
+        // Column j of AP is column `permutation[j]` of A
+        pub permutation: Vec<usize>,
+    }
+
+    // Result of a least squares solve. `residuals` is b - Ax.
+    #[derive(Debug, Clone)]
+    pub struct LeastSquares {
+        pub solution: Vector,
+        pub residuals: Vector,
+        pub residual_norm: f64,
+        pub rank: usize,
+    }
+
+    impl QR {
+        pub fn new(a: &Matrix) -> QR {
+            return QR::factor(a, false);
+        }
+
+        // Greedily moves the remaining column with the largest norm to the
+        // front at every step, so |r_11| >= |r_22| >= ... and the rank can
+        // be read off the diagonal of R
+        pub fn new_pivoted(a: &Matrix) -> QR {
+            return QR::factor(a, true);
+        }
+
+        fn factor(a: &Matrix, pivoting: bool) -> QR {
+            let (m, n) = (a.rows, a.cols);
+            let k = m.min(n);
+            let mut qr = a.clone();
+            let mut tau = vec![0.0; k];
+            let mut permutation: Vec<usize> = (0..n).collect();
+
+            for step in 0..k {
+                if pivoting {
+                    // Norms of the trailing part of every remaining column
+                    let mut best = step;
+                    let mut best_norm = -1.0;
+
+                    for j in step..n {
+                        let norm: f64 = (step..m).map(|i| qr.data[i * n + j].powi(2)).sum();
+
+                        if norm > best_norm {
+                            best = j;
+                            best_norm = norm;
+                        }
+                    }
+
+                    if best != step {
+                        for i in 0..m {
+                            qr.data.swap(i * n + step, i * n + best);
+                        }
+
+                        permutation.swap(step, best);
+                    }
+                }
+
+                let alpha = qr.data[step * n + step];
+                let sigma: f64 = (step + 1..m).map(|i| qr.data[i * n + step].powi(2)).sum();
+
+                // Nothing to eliminate below the diagonal
+                if sigma == 0.0 {
+                    continue;
+                }
+
+                let norm = (alpha * alpha + sigma).sqrt();
+                let beta = if alpha >= 0.0 { -norm } else { norm };
+                let scale = 1.0 / (alpha - beta);
+
+                tau[step] = (beta - alpha) / beta;
+                qr.data[step * n + step] = beta;
+
+                for i in step + 1..m {
+                    qr.data[i * n + step] *= scale;
+                }
+
+                // Apply H = I - tau v v^T to the trailing columns
+                for j in step + 1..n {
+                    let mut w = qr.data[step * n + j];
+
+                    for i in step + 1..m {
+                        w += qr.data[i * n + step] * qr.data[i * n + j];
+                    }
+
+                    w *= tau[step];
+                    qr.data[step * n + j] -= w;
+
+                    for i in step + 1..m {
+                        qr.data[i * n + j] -= w * qr.data[i * n + step];
+                    }
+                }
+            }
+
+            QR {
+                qr,
+                tau,
+                permutation,
+            }
+        }
+
+        // Applies the reflector of `step` to `x` in place
+        fn reflect(&self, step: usize, x: &mut [f64]) {
+            let (m, n) = (self.qr.rows, self.qr.cols);
+
+            if self.tau[step] == 0.0 {
+                return;
+            }
+
+            let mut w = x[step];
+
+            for i in step + 1..m {
+                w += self.qr.data[i * n + step] * x[i];
+            }
+
+            w *= self.tau[step];
+            x[step] -= w;
+
+            for i in step + 1..m {
+                x[i] -= w * self.qr.data[i * n + step];
+            }
+        }
+
+        // Q^T b
+        pub fn qt_mul(&self, b: &Vector) -> Result<Vector, LinAlgError> {
+            if b.size != self.qr.rows {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![self.qr.rows, self.qr.cols],
+                    right: vec![b.size],
+                });
+            }
+
+            let mut result = b.clone();
+
+            for step in 0..self.tau.len() {
+                self.reflect(step, &mut result.data);
+            }
+
+            return Ok(result);
+        }
+
+        // The first `cols` columns of Q
+        fn q_columns(&self, cols: usize) -> Matrix {
+            let m = self.qr.rows;
+            let mut result = Matrix::new(m, cols);
+            let mut column = vec![0.0; m];
+
+            for j in 0..cols {
+                column.iter_mut().for_each(|x| *x = 0.0);
+                column[j] = 1.0;
+
+                // Q e_j = H_0 H_1 ... H_{k-1} e_j
+                for step in (0..self.tau.len()).rev() {
+                    self.reflect(step, &mut column);
+                }
+
+                for i in 0..m {
+                    result.data[i * cols + j] = column[i];
+                }
+            }
+
+            return result;
+        }
+
+        // m x min(m, n) with orthonormal columns
+        pub fn q_thin(&self) -> Matrix {
+            return self.q_columns(self.qr.rows.min(self.qr.cols));
+        }
+
+        // m x m orthogonal
+        pub fn q_full(&self) -> Matrix {
+            return self.q_columns(self.qr.rows);
+        }
+
+        // min(m, n) x n upper triangular, pairs with `q_thin`
+        pub fn r(&self) -> Matrix {
+            let (m, n) = (self.qr.rows, self.qr.cols);
+            let k = m.min(n);
+            let mut result = Matrix::new(k, n);
+
+            for i in 0..k {
+                for j in i..n {
+                    result.data[i * n + j] = self.qr.data[i * n + j];
+                }
+            }
+
+            return result;
+        }
+
+        // m x n upper triangular, pairs with `q_full`
+        pub fn r_full(&self) -> Matrix {
+            let (m, n) = (self.qr.rows, self.qr.cols);
+            let mut result = Matrix::new(m, n);
+
+            for i in 0..m.min(n) {
+                for j in i..n {
+                    result.data[i * n + j] = self.qr.data[i * n + j];
+                }
+            }
+
+            return result;
+        }
+
+        // The column permutation matrix P in AP = QR
+        pub fn p(&self) -> Matrix {
+            let n = self.qr.cols;
+            let mut result = Matrix::new(n, n);
+
+            for (j, &p) in self.permutation.iter().enumerate() {
+                result.data[p * n + j] = 1.0;
+            }
+
+            return result;
+        }
+
+        // Number of diagonal entries of R above `tolerance`, which defaults to
+        // max(m, n) * eps * |r_11|. Only meaningful for a pivoted factorization.
+        pub fn rank(&self, tolerance: Option<f64>) -> usize {
+            let (m, n) = (self.qr.rows, self.qr.cols);
+            let k = m.min(n);
+
+            if k == 0 {
+                return 0;
+            }
+
+            let tolerance =
+                tolerance.unwrap_or(m.max(n) as f64 * f64::EPSILON * self.qr.data[0].abs());
+
+            (0..k)
+                .take_while(|&i| self.qr.data[i * n + i].abs() > tolerance)
+                .count()
+        }
+
+        // Minimizes ||Ax - b||. Only the leading `rank` columns of R are used,
+        // so rank deficient problems get a basic solution with zeros in the
+        // dropped (permuted) positions.
+        pub fn solve_least_squares(
+            &self,
+            b: &Vector,
+            tolerance: Option<f64>,
+        ) -> Result<Vector, LinAlgError> {
+            let n = self.qr.cols;
+            let rank = self.rank(tolerance);
+            let y = self.qt_mul(b)?;
+            let mut z = vec![0.0; n];
+
+            for i in (0..rank).rev() {
+                let mut sum = y.data[i];
+
+                for j in i + 1..rank {
+                    sum -= self.qr.data[i * n + j] * z[j];
+                }
+
+                z[i] = sum / self.qr.data[i * n + i];
+            }
+
+            let mut result = Vector::new(n);
+
+            for (j, &p) in self.permutation.iter().enumerate() {
+                result.data[p] = z[j];
+            }
+
+            return Ok(result);
+        }
+    }
+
+    // Least squares solution of Ax = b through a column pivoted QR
+    pub fn lstsq(a: &Matrix, b: &Vector) -> Result<LeastSquares, LinAlgError> {
+        if a.rows != b.size {
+            return Err(LinAlgError::DimensionMismatch {
+                left: vec![a.rows, a.cols],
+                right: vec![b.size],
+            });
+        }
+
+        let qr = QR::new_pivoted(a);
+        let solution = qr.solve_least_squares(b, None)?;
+        let residuals = Vector::vector_sub(b, &(a * &solution));
+        let residual_norm = Vector::vector_norm(&residuals);
+
+        Ok(LeastSquares {
+            solution,
+            residuals,
+            residual_norm,
+            rank: qr.rank(None),
+        })
+    }
+
+    // Cholesky factorization A = LL^T of a symmetric positive definite
+    // matrix. Only the lower triangle of A is read.
+    #[derive(Debug, Clone)]
+    pub struct Cholesky {
+        pub l: Matrix,
+    }
+
+    impl Cholesky {
+        pub fn new(a: &Matrix) -> Result<Cholesky, LinAlgError> {
+            check_square(a)?;
+
+            let n = a.rows;
+            let mut l = Matrix::new(n, n);
+
+            for j in 0..n {
+                let mut d = a.data[j * n + j];
+
+                for k in 0..j {
+                    d -= l.data[j * n + k] * l.data[j * n + k];
+                }
+
+                // Also catches NaN
+                if d.partial_cmp(&0.0) != Some(std::cmp::Ordering::Greater) {
+                    return Err(LinAlgError::NotPositiveDefinite { pivot: j });
+                }
+
+                let diagonal = d.sqrt();
+                l.data[j * n + j] = diagonal;
+
+                for i in j + 1..n {
+                    let mut sum = a.data[i * n + j];
+
+                    for k in 0..j {
+                        sum -= l.data[i * n + k] * l.data[j * n + k];
+                    }
+
+                    l.data[i * n + j] = sum / diagonal;
+                }
+            }
+
+            Ok(Cholesky { l })
+        }
+
+        pub fn size(&self) -> usize {
+            self.l.rows
+        }
+
+        pub fn solve(&self, b: &Vector) -> Result<Vector, LinAlgError> {
+            let n = self.size();
+
+            if b.size != n {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![n, n],
+                    right: vec![b.size],
+                });
+            }
+
+            let l = &self.l.data;
+            let mut x = b.data.clone();
+
+            // L y = b
+            for i in 0..n {
+                for k in 0..i {
+                    x[i] -= l[i * n + k] * x[k];
+                }
+
+                x[i] /= l[i * n + i];
+            }
+
+            // L^T x = y
+            for i in (0..n).rev() {
+                for k in i + 1..n {
+                    x[i] -= l[k * n + i] * x[k];
+                }
+
+                x[i] /= l[i * n + i];
+            }
+
+            let mut result = Vector::new(n);
+            result.data = x;
+
+            return Ok(result);
+        }
+
+        pub fn solve_matrix(&self, b: &Matrix) -> Result<Matrix, LinAlgError> {
+            return solve_columns(b, self.size(), |column| self.solve(column));
+        }
+
+        pub fn inverse(&self) -> Result<Matrix, LinAlgError> {
+            return self.solve_matrix(&identity(self.size()));
+        }
+
+        pub fn determinant(&self) -> f64 {
+            return self.log_det().exp();
+        }
+
+        // log det A = 2 * sum(log l_ii), without overflowing like the
+        // determinant itself does for large covariance matrices
+        pub fn log_det(&self) -> f64 {
+            let n = self.size();
+
+            return 2.0 * (0..n).map(|i| self.l.data[i * n + i].ln()).sum::<f64>();
+        }
+
+        // Maps a vector of independent standard normal draws to a draw from
+        // a zero mean normal distribution with covariance A
+        pub fn sample(&self, z: &Vector) -> Result<Vector, LinAlgError> {
+            let n = self.size();
+
+            if z.size != n {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![n, n],
+                    right: vec![z.size],
+                });
+            }
+
+            let mut result = Vector::new(n);
+
+            for i in 0..n {
+                for k in 0..=i {
+                    result.data[i] += self.l.data[i * n + k] * z.data[k];
+                }
+            }
+
+            return Ok(result);
+        }
+    }
+
+    pub(crate) fn identity(n: usize) -> Matrix {
+        let mut result = Matrix::new(n, n);
+
+        for i in 0..n {
+            result.data[i * n + i] = 1.0;
+        }
+
+        return result;
+    }
+
+    // Runs a vector solver over every column of `b`
+    fn solve_columns<F>(b: &Matrix, n: usize, solve: F) -> Result<Matrix, LinAlgError>
+    where
+        F: Fn(&Vector) -> Result<Vector, LinAlgError>,
+    {
+        if b.rows != n {
+            return Err(LinAlgError::DimensionMismatch {
+                left: vec![n, n],
+                right: vec![b.rows, b.cols],
+            });
+        }
+
+        let mut result = Matrix::new(n, b.cols);
+
+        for j in 0..b.cols {
+            let mut column = Vector::new(n);
+            column.data = (0..n).map(|i| b.data[i * b.cols + j]).collect();
+
+            let x = solve(&column)?;
+
+            for i in 0..n {
+                result.data[i * b.cols + j] = x.data[i];
+            }
+        }
+
+        return Ok(result);
+    }
+
+    // Bunch-Kaufman factorization P A P^T = L D L^T of a symmetric, possibly
+    // indefinite matrix. L is unit lower triangular and D is block diagonal
+    // with 1x1 and 2x2 blocks. Only the lower triangle of A is read.
+    #[derive(Debug, Clone)]
+    pub struct LDLT {
+        pub l: Matrix,
+        // Diagonal of D, and the sub-diagonal entry of each 2x2 block (zero
+        // everywhere else)
+        pub d: Vec<f64>,
+        pub d_off: Vec<f64>,
+        // Row i of P A P^T is row `permutation[i]` of A
+        pub permutation: Vec<usize>,
+        // Size of the block starting at each row, 0 for the second row of a
+        // 2x2 block
+        pub block_sizes: Vec<usize>,
+        tolerance: f64,
+    }
+
+    impl LDLT {
+        pub fn new(a: &Matrix) -> Result<LDLT, LinAlgError> {
+            check_square(a)?;
+
+            let n = a.rows;
+            // Growth bound that balances stability and pivoting effort
+            let alpha = (1.0 + 17.0f64.sqrt()) / 8.0;
+
+            // Symmetric working copy built from the lower triangle
+            let mut work = Matrix::new(n, n);
+
+            for i in 0..n {
+                for j in 0..=i {
+                    work.data[i * n + j] = a.data[i * n + j];
+                    work.data[j * n + i] = a.data[i * n + j];
+                }
+            }
+
+            let mut l = identity(n);
+            let mut d = vec![0.0; n];
+            let mut d_off = vec![0.0; n];
+            let mut permutation: Vec<usize> = (0..n).collect();
+            let mut block_sizes = vec![0; n];
+
+            let mut k = 0;
+
+            while k < n {
+                let w = &work.data;
+                let absakk = w[k * n + k].abs();
+
+                let mut imax = k;
+                let mut colmax = 0.0;
+
+                for i in k + 1..n {
+                    if w[i * n + k].abs() > colmax {
+                        colmax = w[i * n + k].abs();
+                        imax = i;
+                    }
+                }
+
+                let (step, pivot) = if absakk.max(colmax) == 0.0 || absakk >= alpha * colmax {
+                    (1, k)
+                } else {
+                    let mut rowmax: f64 = 0.0;
+
+                    for j in k..n {
+                        if j != imax {
+                            rowmax = rowmax.max(w[imax * n + j].abs());
+                        }
+                    }
+
+                    if absakk >= alpha * colmax * (colmax / rowmax) {
+                        (1, k)
+                    } else if w[imax * n + imax].abs() >= alpha * rowmax {
+                        (1, imax)
+                    } else {
+                        (2, imax)
+                    }
+                };
+
+                // Bring the pivot to row/column k (1x1) or k + 1 (2x2)
+                let target = k + step - 1;
+
+                if pivot != target {
+                    for j in 0..n {
+                        work.data.swap(target * n + j, pivot * n + j);
+                    }
+
+                    for i in 0..n {
+                        work.data.swap(i * n + target, i * n + pivot);
+                    }
+
+                    for j in 0..k {
+                        l.data.swap(target * n + j, pivot * n + j);
+                    }
+
+                    permutation.swap(target, pivot);
+                }
+
+                let w = &mut work.data;
+
+                if step == 1 {
+                    let dk = w[k * n + k];
+                    d[k] = dk;
+                    block_sizes[k] = 1;
+
+                    // A zero pivot with a zero column needs no elimination
+                    if dk != 0.0 {
+                        for i in k + 1..n {
+                            l.data[i * n + k] = w[i * n + k] / dk;
+                        }
+
+                        for i in k + 1..n {
+                            for j in k + 1..=i {
+                                let update = l.data[i * n + k] * w[j * n + k];
+                                w[i * n + j] -= update;
+                                w[j * n + i] = w[i * n + j];
+                            }
+                        }
+                    }
+                } else {
+                    let (a11, a21, a22) =
+                        (w[k * n + k], w[(k + 1) * n + k], w[(k + 1) * n + k + 1]);
+                    let det = a11 * a22 - a21 * a21;
+
+                    d[k] = a11;
+                    d[k + 1] = a22;
+                    d_off[k] = a21;
+                    block_sizes[k] = 2;
+
+                    for i in k + 2..n {
+                        let (x, y) = (w[i * n + k], w[i * n + k + 1]);
+
+                        l.data[i * n + k] = (a22 * x - a21 * y) / det;
+                        l.data[i * n + k + 1] = (a11 * y - a21 * x) / det;
+                    }
+
+                    for i in k + 2..n {
+                        for j in k + 2..=i {
+                            let update = l.data[i * n + k] * w[j * n + k]
+                                + l.data[i * n + k + 1] * w[j * n + k + 1];
+                            w[i * n + j] -= update;
+                            w[j * n + i] = w[i * n + j];
+                        }
+                    }
+                }
+
+                k += step;
+            }
+
+            let max = (0..n)
+                .flat_map(|i| (0..=i).map(move |j| i * n + j))
+                .fold(0.0f64, |acc, k| acc.max(a.data[k].abs()));
+
+            Ok(LDLT {
+                l,
+                d,
+                d_off,
+                permutation,
+                block_sizes,
+                tolerance: n as f64 * f64::EPSILON * max,
+            })
+        }
+
+        pub fn size(&self) -> usize {
+            self.l.rows
+        }
+
+        // D as a dense matrix
+        pub fn d_matrix(&self) -> Matrix {
+            let n = self.size();
+            let mut result = Matrix::new(n, n);
+
+            for i in 0..n {
+                result.data[i * n + i] = self.d[i];
+
+                if self.block_sizes[i] == 2 {
+                    result.data[(i + 1) * n + i] = self.d_off[i];
+                    result.data[i * n + i + 1] = self.d_off[i];
+                }
+            }
+
+            return result;
+        }
+
+        // The permutation matrix P in P A P^T = L D L^T
+        pub fn p(&self) -> Matrix {
+            let n = self.size();
+            let mut result = Matrix::new(n, n);
+
+            for (i, &p) in self.permutation.iter().enumerate() {
+                result.data[i * n + p] = 1.0;
+            }
+
+            return result;
+        }
+
+        // Determinants of the 1x1 and 2x2 blocks of D
+        fn block_determinants(&self) -> Vec<f64> {
+            let n = self.size();
+
+            (0..n)
+                .filter(|&i| self.block_sizes[i] > 0)
+                .map(|i| match self.block_sizes[i] {
+                    1 => self.d[i],
+                    _ => self.d[i] * self.d[i + 1] - self.d_off[i] * self.d_off[i],
+                })
+                .collect()
+        }
+
+        // Like LU, a pivot at or below n * eps * max|a_ij| is treated as
+        // zero. A 2x2 block is measured by its smallest eigenvalue, roughly
+        // |det| / max|entry|.
+        pub fn is_singular(&self) -> bool {
+            let n = self.size();
+
+            (0..n).any(|i| match self.block_sizes[i] {
+                1 => self.d[i].abs() <= self.tolerance,
+                2 => {
+                    let det = self.d[i] * self.d[i + 1] - self.d_off[i] * self.d_off[i];
+                    let largest = self.d[i]
+                        .abs()
+                        .max(self.d[i + 1].abs())
+                        .max(self.d_off[i].abs());
+
+                    det.abs() <= self.tolerance * largest
+                }
+                _ => false,
+            })
+        }
+
+        // Numbers of positive, negative and zero eigenvalues of A. Every 2x2
+        // block has one eigenvalue of each sign.
+        pub fn inertia(&self) -> (usize, usize, usize) {
+            let n = self.size();
+            let (mut positive, mut negative, mut zero) = (0, 0, 0);
+
+            for i in 0..n {
+                match self.block_sizes[i] {
+                    1 if self.d[i] > 0.0 => positive += 1,
+                    1 if self.d[i] < 0.0 => negative += 1,
+                    1 => zero += 1,
+                    2 => {
+                        positive += 1;
+                        negative += 1;
+                    }
+                    _ => {}
+                }
+            }
+
+            return (positive, negative, zero);
+        }
+
+        pub fn determinant(&self) -> f64 {
+            return self.block_determinants().iter().product();
+        }
+
+        // Sign and logarithm of the absolute value of the determinant
+        pub fn log_det(&self) -> (f64, f64) {
+            if self.is_singular() {
+                return (0.0, f64::NEG_INFINITY);
+            }
+
+            let mut sign = 1.0;
+            let mut log = 0.0;
+
+            for det in self.block_determinants() {
+                sign *= det.signum();
+                log += det.abs().ln();
+            }
+
+            return (sign, log);
+        }
+
+        pub fn solve(&self, b: &Vector) -> Result<Vector, LinAlgError> {
+            let n = self.size();
+
+            if b.size != n {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![n, n],
+                    right: vec![b.size],
+                });
+            }
+
+            if self.is_singular() {
+                return Err(LinAlgError::SingularMatrix);
+            }
+
+            let l = &self.l.data;
+            let mut x: Vec<f64> = self.permutation.iter().map(|&p| b.data[p]).collect();
+
+            for i in 0..n {
+                for k in 0..i {
+                    x[i] -= l[i * n + k] * x[k];
+                }
+            }
+
+            let mut i = 0;
+
+            while i < n {
+                if self.block_sizes[i] == 1 {
+                    x[i] /= self.d[i];
+                    i += 1;
+                } else {
+                    let (a11, a21, a22) = (self.d[i], self.d_off[i], self.d[i + 1]);
+                    let det = a11 * a22 - a21 * a21;
+                    let (y1, y2) = (x[i], x[i + 1]);
+
+                    x[i] = (a22 * y1 - a21 * y2) / det;
+                    x[i + 1] = (a11 * y2 - a21 * y1) / det;
+                    i += 2;
+                }
+            }
+
+            for i in (0..n).rev() {
+                for k in i + 1..n {
+                    x[i] -= l[k * n + i] * x[k];
+                }
+            }
+
+            let mut result = Vector::new(n);
+
+            for (i, &p) in self.permutation.iter().enumerate() {
+                result.data[p] = x[i];
+            }
+
+            return Ok(result);
+        }
+
+        pub fn solve_matrix(&self, b: &Matrix) -> Result<Matrix, LinAlgError> {
+            return solve_columns(b, self.size(), |column| self.solve(column));
+        }
+    }
+}
@@ -0,0 +1,1144 @@
+// src/eigen.rs
+
+pub mod eigen {
+    use crate::complex::complex::Complex64;
+    use crate::decomposition::decomposition::check_square;
+    use crate::linear_algebra::linear_algebra::{LinAlgError, Matrix, Vector};
+
+    // Maximum number of QL sweeps spent on a single eigenvalue
+    const MAX_SWEEPS: usize = 30;
+
+    // Entries may differ by this much relative to the largest entry and still
+    // count as symmetric, to allow for rounding in products like A^T A
+    pub(crate) const SYMMETRY_TOLERANCE: f64 = 1e-10;
+
+    // Eigendecomposition A = V diag(eigenvalues) V^T of a symmetric matrix.
+    // Eigenvalues are sorted in ascending order and column j of
+    // `eigenvectors` is the unit eigenvector belonging to eigenvalue j.
+    #[derive(Debug, Clone)]
+    pub struct SymmetricEigen {
+        pub eigenvalues: Vector,
+        pub eigenvectors: Matrix,
+    }
+
+    pub fn check_symmetric(a: &Matrix) -> Result<(), LinAlgError> {
+        check_square(a)?;
+
+        let n = a.rows;
+        let max = a.data.iter().fold(0.0f64, |acc, x| acc.max(x.abs()));
+
+        for i in 0..n {
+            for j in 0..i {
+                if (a.data[i * n + j] - a.data[j * n + i]).abs() > SYMMETRY_TOLERANCE * max {
+                    return Err(LinAlgError::NotSymmetric { row: i, col: j });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    impl SymmetricEigen {
+        pub fn new(a: &Matrix) -> Result<SymmetricEigen, LinAlgError> {
+            let (d, v) = decompose(a, true)?;
+
+            let mut eigenvalues = Vector::new(d.len());
+            eigenvalues.data = d;
+
+            Ok(SymmetricEigen {
+                eigenvalues,
+                eigenvectors: v,
+            })
+        }
+
+        // Skips accumulating the eigenvectors, which dominates the cost
+        pub fn eigenvalues(a: &Matrix) -> Result<Vector, LinAlgError> {
+            let (d, _) = decompose(a, false)?;
+
+            let mut result = Vector::new(d.len());
+            result.data = d;
+
+            return Ok(result);
+        }
+
+        // V diag(eigenvalues) V^T
+        pub fn reconstruct(&self) -> Matrix {
+            let n = self.eigenvalues.size;
+            let v = &self.eigenvectors.data;
+            let mut result = Matrix::new(n, n);
+
+            for i in 0..n {
+                for j in 0..n {
+                    let mut sum = 0.0;
+
+                    for k in 0..n {
+                        sum += v[i * n + k] * self.eigenvalues.data[k] * v[j * n + k];
+                    }
+
+                    result.data[i * n + j] = sum;
+                }
+            }
+
+            return result;
+        }
+    }
+
+    fn decompose(a: &Matrix, vectors: bool) -> Result<(Vec<f64>, Matrix), LinAlgError> {
+        check_symmetric(a)?;
+
+        let n = a.rows;
+        let mut v = a.clone();
+        let mut d = vec![0.0; n];
+        let mut e = vec![0.0; n];
+
+        if n == 0 {
+            return Ok((d, v));
+        }
+
+        tridiagonalize(&mut v, &mut d, &mut e, vectors);
+        tridiagonal_ql(&mut v, &mut d, &mut e, vectors)?;
+
+        return Ok((d, v));
+    }
+
+    // Householder reduction to symmetric tridiagonal form (tred2 from
+    // EISPACK, by way of JAMA). On return `d` holds the diagonal, `e[1..]`
+    // the sub-diagonal and `v` the accumulated orthogonal transformation if
+    // `vectors` is set.
+    fn tridiagonalize(v: &mut Matrix, d: &mut [f64], e: &mut [f64], vectors: bool) {
+        let n = v.rows;
+        let v = &mut v.data;
+
+        for j in 0..n {
+            d[j] = v[(n - 1) * n + j];
+        }
+
+        for i in (1..n).rev() {
+            let mut scale = 0.0;
+            let mut h = 0.0;
+
+            for k in 0..i {
+                scale += d[k].abs();
+            }
+
+            if scale == 0.0 {
+                e[i] = d[i - 1];
+
+                for j in 0..i {
+                    d[j] = v[(i - 1) * n + j];
+                    v[i * n + j] = 0.0;
+                    v[j * n + i] = 0.0;
+                }
+            } else {
+                for k in 0..i {
+                    d[k] /= scale;
+                    h += d[k] * d[k];
+                }
+
+                let mut f = d[i - 1];
+                let mut g = h.sqrt();
+
+                if f > 0.0 {
+                    g = -g;
+                }
+
+                e[i] = scale * g;
+                h -= f * g;
+                d[i - 1] = f - g;
+
+                for j in 0..i {
+                    e[j] = 0.0;
+                }
+
+                for j in 0..i {
+                    f = d[j];
+                    v[j * n + i] = f;
+                    g = e[j] + v[j * n + j] * f;
+
+                    for k in j + 1..i {
+                        g += v[k * n + j] * d[k];
+                        e[k] += v[k * n + j] * f;
+                    }
+
+                    e[j] = g;
+                }
+
+                f = 0.0;
+
+                for j in 0..i {
+                    e[j] /= h;
+                    f += e[j] * d[j];
+                }
+
+                let hh = f / (h + h);
+
+                for j in 0..i {
+                    e[j] -= hh * d[j];
+                }
+
+                for j in 0..i {
+                    f = d[j];
+                    g = e[j];
+
+                    for k in j..i {
+                        v[k * n + j] -= f * e[k] + g * d[k];
+                    }
+
+                    d[j] = v[(i - 1) * n + j];
+                    v[i * n + j] = 0.0;
+                }
+            }
+
+            d[i] = h;
+        }
+
+        if !vectors {
+            for j in 0..n {
+                d[j] = v[j * n + j];
+            }
+
+            e[0] = 0.0;
+
+            return;
+        }
+
+        for i in 0..n - 1 {
+            v[(n - 1) * n + i] = v[i * n + i];
+            v[i * n + i] = 1.0;
+
+            let h = d[i + 1];
+
+            if h != 0.0 {
+                for k in 0..=i {
+                    d[k] = v[k * n + i + 1] / h;
+                }
+
+                for j in 0..=i {
+                    let mut g = 0.0;
+
+                    for k in 0..=i {
+                        g += v[k * n + i + 1] * v[k * n + j];
+                    }
+
+                    for k in 0..=i {
+                        v[k * n + j] -= g * d[k];
+                    }
+                }
+            }
+
+            for k in 0..=i {
+                v[k * n + i + 1] = 0.0;
+            }
+        }
+
+        for j in 0..n {
+            d[j] = v[(n - 1) * n + j];
+            v[(n - 1) * n + j] = 0.0;
+        }
+
+        v[(n - 1) * n + n - 1] = 1.0;
+        e[0] = 0.0;
+    }
+
+    // Implicit QL iteration on the symmetric tridiagonal matrix (tql2 from
+    // EISPACK, by way of JAMA). Eigenvalues end up sorted ascending in `d`,
+    // with the columns of `v` permuted to match.
+    fn tridiagonal_ql(
+        v: &mut Matrix,
+        d: &mut [f64],
+        e: &mut [f64],
+        vectors: bool,
+    ) -> Result<(), LinAlgError> {
+        let n = v.rows;
+        let v = &mut v.data;
+
+        for i in 1..n {
+            e[i - 1] = e[i];
+        }
+
+        e[n - 1] = 0.0;
+
+        let mut f = 0.0;
+        let mut tst1: f64 = 0.0;
+        let eps = f64::EPSILON;
+
+        for l in 0..n {
+            tst1 = tst1.max(d[l].abs() + e[l].abs());
+
+            // Find a small sub-diagonal element, e[n - 1] is always zero
+            let mut m = l;
+
+            while m < n - 1 && e[m].abs() > eps * tst1 {
+                m += 1;
+            }
+
+            let mut sweeps = 0;
+
+            while m > l && e[l].abs() > eps * tst1 {
+                if sweeps == MAX_SWEEPS {
+                    return Err(LinAlgError::NonConvergence { iterations: sweeps });
+                }
+
+                sweeps += 1;
+
+                // Implicit Wilkinson shift
+                let mut g = d[l];
+                let mut p = (d[l + 1] - g) / (2.0 * e[l]);
+                let mut r = p.hypot(1.0);
+
+                if p < 0.0 {
+                    r = -r;
+                }
+
+                d[l] = e[l] / (p + r);
+                d[l + 1] = e[l] * (p + r);
+
+                let dl1 = d[l + 1];
+                let mut h = g - d[l];
+
+                for i in l + 2..n {
+                    d[i] -= h;
+                }
+
+                f += h;
+
+                p = d[m];
+
+                let mut c = 1.0;
+                let mut c2 = c;
+                let mut c3 = c;
+                let el1 = e[l + 1];
+                let mut s = 0.0;
+                let mut s2 = 0.0;
+
+                for i in (l..m).rev() {
+                    c3 = c2;
+                    c2 = c;
+                    s2 = s;
+                    g = c * e[i];
+                    h = c * p;
+                    r = p.hypot(e[i]);
+                    e[i + 1] = s * r;
+                    s = e[i] / r;
+                    c = p / r;
+                    p = c * d[i] - s * g;
+                    d[i + 1] = h + s * (c * g + s * d[i]);
+
+                    if vectors {
+                        for k in 0..n {
+                            h = v[k * n + i + 1];
+                            v[k * n + i + 1] = s * v[k * n + i] + c * h;
+                            v[k * n + i] = c * v[k * n + i] - s * h;
+                        }
+                    }
+                }
+
+                p = -s * s2 * c3 * el1 * e[l] / dl1;
+                e[l] = s * p;
+                d[l] = c * p;
+
+                // The sub-diagonal may have split further down
+                m = l;
+
+                while m < n - 1 && e[m].abs() > eps * tst1 {
+                    m += 1;
+                }
+            }
+
+            d[l] += f;
+            e[l] = 0.0;
+        }
+
+        // Selection sort, which also keeps the number of column swaps low
+        for i in 0..n.saturating_sub(1) {
+            let mut k = i;
+
+            for j in i + 1..n {
+                if d[j] < d[k] {
+                    k = j;
+                }
+            }
+
+            if k != i {
+                d.swap(i, k);
+
+                if vectors {
+                    for j in 0..n {
+                        v.swap(j * n + i, j * n + k);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Eigenvalues of a general real matrix, with real and imaginary parts
+    // kept in separate vectors as LAPACK does. Complex eigenvalues come in
+    // conjugate pairs, positive imaginary part first. The order is the one
+    // the QR iteration finds them in, not sorted.
+    #[derive(Debug, Clone)]
+    pub struct GeneralEigen {
+        pub real: Vector,
+        pub imag: Vector,
+        // Column j is the eigenvector for eigenvalue j, scaled to unit norm
+        pub right: Option<EigenVectors>,
+        // Column j is y with y^H A = lambda_j y^H, scaled to unit norm
+        pub left: Option<EigenVectors>,
+    }
+
+    // Real and imaginary parts of a set of complex eigenvectors
+    #[derive(Debug, Clone)]
+    pub struct EigenVectors {
+        pub real: Matrix,
+        pub imag: Matrix,
+    }
+
+    // Maximum number of QR iterations per eigenvalue before giving up
+    const MAX_QR_ITERATIONS: usize = 30;
+
+    impl GeneralEigen {
+        // Eigenvalues and right eigenvectors
+        pub fn new(a: &Matrix) -> Result<GeneralEigen, LinAlgError> {
+            let (real, imag, v) = general_decompose(a, true)?;
+
+            Ok(GeneralEigen {
+                right: Some(complex_vectors(&v, &imag)),
+                real,
+                imag,
+                left: None,
+            })
+        }
+
+        // Also computes left eigenvectors, as conjugated right eigenvectors
+        // of A^T
+        pub fn new_with_left(a: &Matrix) -> Result<GeneralEigen, LinAlgError> {
+            let mut result = GeneralEigen::new(a)?;
+            let transposed = GeneralEigen::new(&a.view().transpose().to_matrix())?;
+            let vectors = transposed.right.unwrap();
+            let n = a.rows;
+
+            // Match every eigenvalue of A with the closest unused one of A^T
+            let mut used = vec![false; n];
+            let mut left = EigenVectors {
+                real: Matrix::new(n, n),
+                imag: Matrix::new(n, n),
+            };
+
+            for j in 0..n {
+                let distance = |k: usize| {
+                    (result.real[j] - transposed.real[k]).hypot(result.imag[j] - transposed.imag[k])
+                };
+
+                let k = (0..n)
+                    .filter(|&k| !used[k])
+                    .min_by(|&x, &y| distance(x).total_cmp(&distance(y)))
+                    .unwrap();
+
+                used[k] = true;
+
+                for i in 0..n {
+                    left.real.data[i * n + j] = vectors.real.data[i * n + k];
+                    left.imag.data[i * n + j] = -vectors.imag.data[i * n + k];
+                }
+            }
+
+            result.left = Some(left);
+
+            return Ok(result);
+        }
+
+        // Skips the eigenvector back substitution and accumulation
+        pub fn eigenvalues(a: &Matrix) -> Result<GeneralEigen, LinAlgError> {
+            let (real, imag, _) = general_decompose(a, false)?;
+
+            Ok(GeneralEigen {
+                real,
+                imag,
+                right: None,
+                left: None,
+            })
+        }
+
+        pub fn is_real(&self) -> bool {
+            self.imag.data.iter().all(|&x| x == 0.0)
+        }
+
+        // Largest eigenvalue modulus, which decides the stability of x <- Ax
+        pub fn spectral_radius(&self) -> f64 {
+            self.real
+                .data
+                .iter()
+                .zip(self.imag.data.iter())
+                .fold(0.0, |acc: f64, (re, im)| acc.max(re.hypot(*im)))
+        }
+
+        // The eigenvalues as one complex vector
+        pub fn complex_eigenvalues(&self) -> Vector<Complex64> {
+            Vector::from_parts(&self.real, &self.imag)
+        }
+    }
+
+    impl EigenVectors {
+        pub fn to_complex(&self) -> Matrix<Complex64> {
+            Matrix::from_parts(&self.real, &self.imag)
+        }
+    }
+
+    // Reduces A to upper Hessenberg form H = Q^T A Q by Householder
+    // reflections (orthes from EISPACK, by way of JAMA). Returns (H, Q).
+    pub fn hessenberg(a: &Matrix) -> Result<(Matrix, Matrix), LinAlgError> {
+        check_square(a)?;
+
+        let mut h = a.clone();
+        let mut v = Matrix::new(a.rows, a.rows);
+
+        orthes(&mut h, &mut v);
+
+        for i in 2..a.rows {
+            for j in 0..i - 1 {
+                h.data[i * a.rows + j] = 0.0;
+            }
+        }
+
+        return Ok((h, v));
+    }
+
+    fn general_decompose(
+        a: &Matrix,
+        vectors: bool,
+    ) -> Result<(Vector, Vector, Matrix), LinAlgError> {
+        check_square(a)?;
+
+        let n = a.rows;
+        let mut h = a.clone();
+        let mut v = Matrix::new(n, n);
+        let mut real = Vector::new(n);
+        let mut imag = Vector::new(n);
+
+        orthes(&mut h, &mut v);
+        hqr2(&mut h, &mut v, &mut real.data, &mut imag.data, vectors)?;
+
+        return Ok((real, imag, v));
+    }
+
+    fn orthes(h: &mut Matrix, v: &mut Matrix) {
+        let n = h.rows;
+        let h = &mut h.data;
+        let v = &mut v.data;
+        let mut ort = vec![0.0; n];
+
+        if n == 0 {
+            return;
+        }
+
+        let high = n - 1;
+
+        for m in 1..high {
+            let mut scale = 0.0;
+
+            for i in m..=high {
+                scale += h[i * n + m - 1].abs();
+            }
+
+            if scale != 0.0 {
+                let mut hh = 0.0;
+
+                for i in (m..=high).rev() {
+                    ort[i] = h[i * n + m - 1] / scale;
+                    hh += ort[i] * ort[i];
+                }
+
+                let mut g = hh.sqrt();
+
+                if ort[m] > 0.0 {
+                    g = -g;
+                }
+
+                hh -= ort[m] * g;
+                ort[m] -= g;
+
+                // H = (I - u u^T / h) H (I - u u^T / h)
+                for j in m..n {
+                    let mut f = 0.0;
+
+                    for i in (m..=high).rev() {
+                        f += ort[i] * h[i * n + j];
+                    }
+
+                    f /= hh;
+
+                    for i in m..=high {
+                        h[i * n + j] -= f * ort[i];
+                    }
+                }
+
+                for i in 0..=high {
+                    let mut f = 0.0;
+
+                    for j in (m..=high).rev() {
+                        f += ort[j] * h[i * n + j];
+                    }
+
+                    f /= hh;
+
+                    for j in m..=high {
+                        h[i * n + j] -= f * ort[j];
+                    }
+                }
+
+                ort[m] *= scale;
+                h[m * n + m - 1] = scale * g;
+            }
+        }
+
+        for i in 0..n {
+            for j in 0..n {
+                v[i * n + j] = if i == j { 1.0 } else { 0.0 };
+            }
+        }
+
+        for m in (1..high).rev() {
+            if h[m * n + m - 1] != 0.0 {
+                for i in m + 1..=high {
+                    ort[i] = h[i * n + m - 1];
+                }
+
+                for j in m..=high {
+                    let mut g = 0.0;
+
+                    for i in m..=high {
+                        g += ort[i] * v[i * n + j];
+                    }
+
+                    // Double division avoids possible underflow
+                    g = (g / ort[m]) / h[m * n + m - 1];
+
+                    for i in m..=high {
+                        v[i * n + j] += g * ort[i];
+                    }
+                }
+            }
+        }
+    }
+
+    // Complex scalar division (xr + i xi) / (yr + i yi)
+    fn cdiv(xr: f64, xi: f64, yr: f64, yi: f64) -> (f64, f64) {
+        if yr.abs() > yi.abs() {
+            let r = yi / yr;
+            let d = yr + r * yi;
+
+            return ((xr + r * xi) / d, (xi - r * xr) / d);
+        }
+
+        let r = yr / yi;
+        let d = yi + r * yr;
+
+        return ((r * xr + xi) / d, (r * xi - xr) / d);
+    }
+
+    // Francis double shift QR iteration on the Hessenberg matrix (hqr2 from
+    // EISPACK, by way of JAMA), followed by back substitution for the
+    // eigenvectors of the quasi-triangular Schur form when `vectors` is set.
+    fn hqr2(
+        hm: &mut Matrix,
+        vm: &mut Matrix,
+        d: &mut [f64],
+        e: &mut [f64],
+        vectors: bool,
+    ) -> Result<(), LinAlgError> {
+        let nn = hm.rows;
+        let h = &mut hm.data;
+        let v = &mut vm.data;
+
+        if nn == 0 {
+            return Ok(());
+        }
+
+        let idx = |i: usize, j: usize| i * nn + j;
+        let eps = f64::EPSILON;
+        let mut exshift = 0.0;
+        let (mut r, mut s, mut z) = (0.0, 0.0, 0.0);
+        let (mut p, mut q, mut w, mut x, mut y);
+
+        let mut norm = 0.0;
+
+        for i in 0..nn {
+            for j in i.saturating_sub(1)..nn {
+                norm += h[idx(i, j)].abs();
+            }
+        }
+
+        // `n` is one past the active block so it never underflows
+        let mut n = nn;
+        let mut iter = 0;
+
+        while n > 0 {
+            let last = n - 1;
+
+            // Look for a single small sub-diagonal element
+            let mut l = last;
+
+            while l > 0 {
+                s = h[idx(l - 1, l - 1)].abs() + h[idx(l, l)].abs();
+
+                if s == 0.0 {
+                    s = norm;
+                }
+
+                if h[idx(l, l - 1)].abs() < eps * s {
+                    break;
+                }
+
+                l -= 1;
+            }
+
+            if l == last {
+                // One root found
+                h[idx(last, last)] += exshift;
+                d[last] = h[idx(last, last)];
+                e[last] = 0.0;
+                n -= 1;
+                iter = 0;
+            } else if l + 1 == last {
+                // Two roots found
+                let k = last - 1;
+
+                w = h[idx(last, k)] * h[idx(k, last)];
+                p = (h[idx(k, k)] - h[idx(last, last)]) / 2.0;
+                q = p * p + w;
+                z = q.abs().sqrt();
+                h[idx(last, last)] += exshift;
+                h[idx(k, k)] += exshift;
+                x = h[idx(last, last)];
+
+                if q >= 0.0 {
+                    // Real pair
+                    z = if p >= 0.0 { p + z } else { p - z };
+                    d[k] = x + z;
+                    d[last] = d[k];
+
+                    if z != 0.0 {
+                        d[last] = x - w / z;
+                    }
+
+                    e[k] = 0.0;
+                    e[last] = 0.0;
+
+                    x = h[idx(last, k)];
+                    s = x.abs() + z.abs();
+                    p = x / s;
+                    q = z / s;
+                    r = (p * p + q * q).sqrt();
+                    p /= r;
+                    q /= r;
+
+                    // Row modification
+                    for j in k..nn {
+                        z = h[idx(k, j)];
+                        h[idx(k, j)] = q * z + p * h[idx(last, j)];
+                        h[idx(last, j)] = q * h[idx(last, j)] - p * z;
+                    }
+
+                    // Column modification
+                    for i in 0..=last {
+                        z = h[idx(i, k)];
+                        h[idx(i, k)] = q * z + p * h[idx(i, last)];
+                        h[idx(i, last)] = q * h[idx(i, last)] - p * z;
+                    }
+
+                    // Accumulate transformations
+                    for i in 0..nn {
+                        z = v[idx(i, k)];
+                        v[idx(i, k)] = q * z + p * v[idx(i, last)];
+                        v[idx(i, last)] = q * v[idx(i, last)] - p * z;
+                    }
+                } else {
+                    // Complex pair
+                    d[k] = x + p;
+                    d[last] = x + p;
+                    e[k] = z;
+                    e[last] = -z;
+                }
+
+                n -= 2;
+                iter = 0;
+            } else {
+                if iter == MAX_QR_ITERATIONS {
+                    return Err(LinAlgError::NonConvergence { iterations: iter });
+                }
+
+                // No convergence yet, form the shift
+                x = h[idx(last, last)];
+                y = h[idx(last - 1, last - 1)];
+                w = h[idx(last, last - 1)] * h[idx(last - 1, last)];
+
+                // Wilkinson's original ad hoc shift
+                if iter == 10 {
+                    exshift += x;
+
+                    for i in 0..=last {
+                        h[idx(i, i)] -= x;
+                    }
+
+                    s = h[idx(last, last - 1)].abs() + h[idx(last - 1, last - 2)].abs();
+                    x = 0.75 * s;
+                    y = x;
+                    w = -0.4375 * s * s;
+                }
+
+                // MATLAB's ad hoc shift
+                if iter == 20 {
+                    s = (y - x) / 2.0;
+                    s = s * s + w;
+
+                    if s > 0.0 {
+                        s = s.sqrt();
+
+                        if y < x {
+                            s = -s;
+                        }
+
+                        s = x - w / ((y - x) / 2.0 + s);
+
+                        for i in 0..=last {
+                            h[idx(i, i)] -= s;
+                        }
+
+                        exshift += s;
+                        x = 0.964;
+                        y = x;
+                        w = x;
+                    }
+                }
+
+                iter += 1;
+
+                // Look for two consecutive small sub-diagonal elements
+                let mut m = last - 2;
+
+                loop {
+                    z = h[idx(m, m)];
+                    r = x - z;
+                    s = y - z;
+                    p = (r * s - w) / h[idx(m + 1, m)] + h[idx(m, m + 1)];
+                    q = h[idx(m + 1, m + 1)] - z - r - s;
+                    r = h[idx(m + 2, m + 1)];
+                    s = p.abs() + q.abs() + r.abs();
+                    p /= s;
+                    q /= s;
+                    r /= s;
+
+                    if m == l {
+                        break;
+                    }
+
+                    if h[idx(m, m - 1)].abs() * (q.abs() + r.abs())
+                        < eps
+                            * (p.abs()
+                                * (h[idx(m - 1, m - 1)].abs()
+                                    + z.abs()
+                                    + h[idx(m + 1, m + 1)].abs()))
+                    {
+                        break;
+                    }
+
+                    m -= 1;
+                }
+
+                for i in m + 2..=last {
+                    h[idx(i, i - 2)] = 0.0;
+
+                    if i > m + 2 {
+                        h[idx(i, i - 3)] = 0.0;
+                    }
+                }
+
+                // Double QR step involving rows l..=last and columns m..=last
+                for k in m..last {
+                    let notlast = k != last - 1;
+
+                    if k != m {
+                        p = h[idx(k, k - 1)];
+                        q = h[idx(k + 1, k - 1)];
+                        r = if notlast { h[idx(k + 2, k - 1)] } else { 0.0 };
+                        x = p.abs() + q.abs() + r.abs();
+
+                        if x == 0.0 {
+                            continue;
+                        }
+
+                        p /= x;
+                        q /= x;
+                        r /= x;
+                    }
+
+                    s = (p * p + q * q + r * r).sqrt();
+
+                    if p < 0.0 {
+                        s = -s;
+                    }
+
+                    if s != 0.0 {
+                        if k != m {
+                            h[idx(k, k - 1)] = -s * x;
+                        } else if l != m {
+                            h[idx(k, k - 1)] = -h[idx(k, k - 1)];
+                        }
+
+                        p += s;
+                        x = p / s;
+                        y = q / s;
+                        z = r / s;
+                        q /= p;
+                        r /= p;
+
+                        // Row modification
+                        for j in k..nn {
+                            p = h[idx(k, j)] + q * h[idx(k + 1, j)];
+
+                            if notlast {
+                                p += r * h[idx(k + 2, j)];
+                                h[idx(k + 2, j)] -= p * z;
+                            }
+
+                            h[idx(k, j)] -= p * x;
+                            h[idx(k + 1, j)] -= p * y;
+                        }
+
+                        // Column modification
+                        for i in 0..=last.min(k + 3) {
+                            p = x * h[idx(i, k)] + y * h[idx(i, k + 1)];
+
+                            if notlast {
+                                p += z * h[idx(i, k + 2)];
+                                h[idx(i, k + 2)] -= p * r;
+                            }
+
+                            h[idx(i, k)] -= p;
+                            h[idx(i, k + 1)] -= p * q;
+                        }
+
+                        // Accumulate transformations
+                        for i in 0..nn {
+                            p = x * v[idx(i, k)] + y * v[idx(i, k + 1)];
+
+                            if notlast {
+                                p += z * v[idx(i, k + 2)];
+                                v[idx(i, k + 2)] -= p * r;
+                            }
+
+                            v[idx(i, k)] -= p;
+                            v[idx(i, k + 1)] -= p * q;
+                        }
+                    }
+                }
+            }
+        }
+
+        if !vectors || norm == 0.0 {
+            return Ok(());
+        }
+
+        // Back substitute to find the vectors of the upper triangular form
+        for n in (0..nn).rev() {
+            p = d[n];
+            q = e[n];
+
+            if q == 0.0 {
+                // Real vector
+                let mut l = n;
+                h[idx(n, n)] = 1.0;
+
+                for i in (0..n).rev() {
+                    w = h[idx(i, i)] - p;
+                    r = 0.0;
+
+                    for j in l..=n {
+                        r += h[idx(i, j)] * h[idx(j, n)];
+                    }
+
+                    if e[i] < 0.0 {
+                        z = w;
+                        s = r;
+                    } else {
+                        l = i;
+
+                        if e[i] == 0.0 {
+                            h[idx(i, n)] = if w != 0.0 { -r / w } else { -r / (eps * norm) };
+                        } else {
+                            // Solve the real 2x2 system
+                            x = h[idx(i, i + 1)];
+                            y = h[idx(i + 1, i)];
+                            q = (d[i] - p) * (d[i] - p) + e[i] * e[i];
+
+                            let t = (x * s - z * r) / q;
+                            h[idx(i, n)] = t;
+                            h[idx(i + 1, n)] = if x.abs() > z.abs() {
+                                (-r - w * t) / x
+                            } else {
+                                (-s - y * t) / z
+                            };
+                        }
+
+                        // Overflow control
+                        let t = h[idx(i, n)].abs();
+
+                        if (eps * t) * t > 1.0 {
+                            for j in i..=n {
+                                h[idx(j, n)] /= t;
+                            }
+                        }
+                    }
+                }
+            } else if q < 0.0 {
+                // Complex vector, real part in column n - 1 and imaginary part
+                // in column n
+                let mut l = n - 1;
+
+                // The last vector component is imaginary, so the matrix is
+                // triangular
+                if h[idx(n, n - 1)].abs() > h[idx(n - 1, n)].abs() {
+                    h[idx(n - 1, n - 1)] = q / h[idx(n, n - 1)];
+                    h[idx(n - 1, n)] = -(h[idx(n, n)] - p) / h[idx(n, n - 1)];
+                } else {
+                    let (cr, ci) = cdiv(0.0, -h[idx(n - 1, n)], h[idx(n - 1, n - 1)] - p, q);
+                    h[idx(n - 1, n - 1)] = cr;
+                    h[idx(n - 1, n)] = ci;
+                }
+
+                h[idx(n, n - 1)] = 0.0;
+                h[idx(n, n)] = 1.0;
+
+                for i in (0..n - 1).rev() {
+                    let mut ra = 0.0;
+                    let mut sa = 0.0;
+
+                    for j in l..=n {
+                        ra += h[idx(i, j)] * h[idx(j, n - 1)];
+                        sa += h[idx(i, j)] * h[idx(j, n)];
+                    }
+
+                    w = h[idx(i, i)] - p;
+
+                    if e[i] < 0.0 {
+                        z = w;
+                        r = ra;
+                        s = sa;
+                    } else {
+                        l = i;
+
+                        if e[i] == 0.0 {
+                            let (cr, ci) = cdiv(-ra, -sa, w, q);
+                            h[idx(i, n - 1)] = cr;
+                            h[idx(i, n)] = ci;
+                        } else {
+                            // Solve the complex 2x2 system
+                            x = h[idx(i, i + 1)];
+                            y = h[idx(i + 1, i)];
+
+                            let mut vr = (d[i] - p) * (d[i] - p) + e[i] * e[i] - q * q;
+                            let vi = (d[i] - p) * 2.0 * q;
+
+                            if vr == 0.0 && vi == 0.0 {
+                                vr = eps * norm * (w.abs() + q.abs() + x.abs() + y.abs() + z.abs());
+                            }
+
+                            let (cr, ci) =
+                                cdiv(x * r - z * ra + q * sa, x * s - z * sa - q * ra, vr, vi);
+                            h[idx(i, n - 1)] = cr;
+                            h[idx(i, n)] = ci;
+
+                            if x.abs() > z.abs() + q.abs() {
+                                h[idx(i + 1, n - 1)] =
+                                    (-ra - w * h[idx(i, n - 1)] + q * h[idx(i, n)]) / x;
+                                h[idx(i + 1, n)] =
+                                    (-sa - w * h[idx(i, n)] - q * h[idx(i, n - 1)]) / x;
+                            } else {
+                                let (cr, ci) =
+                                    cdiv(-r - y * h[idx(i, n - 1)], -s - y * h[idx(i, n)], z, q);
+                                h[idx(i + 1, n - 1)] = cr;
+                                h[idx(i + 1, n)] = ci;
+                            }
+                        }
+
+                        // Overflow control
+                        let t = h[idx(i, n - 1)].abs().max(h[idx(i, n)].abs());
+
+                        if (eps * t) * t > 1.0 {
+                            for j in i..=n {
+                                h[idx(j, n - 1)] /= t;
+                                h[idx(j, n)] /= t;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Back transformation to get the eigenvectors of the original matrix
+        for j in (0..nn).rev() {
+            for i in 0..nn {
+                z = 0.0;
+
+                for k in 0..=j {
+                    z += v[idx(i, k)] * h[idx(k, j)];
+                }
+
+                v[idx(i, j)] = z;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Unpacks the real Schur vectors, where a conjugate pair at columns j and
+    // j + 1 is stored as real part and imaginary part, into complex
+    // eigenvectors of unit norm
+    fn complex_vectors(v: &Matrix, imag: &Vector) -> EigenVectors {
+        let n = v.rows;
+        let mut result = EigenVectors {
+            real: Matrix::new(n, n),
+            imag: Matrix::new(n, n),
+        };
+
+        let mut j = 0;
+
+        while j < n {
+            if imag.data[j] == 0.0 {
+                for i in 0..n {
+                    result.real.data[i * n + j] = v.data[i * n + j];
+                }
+
+                j += 1;
+            } else {
+                for i in 0..n {
+                    let (re, im) = (v.data[i * n + j], v.data[i * n + j + 1]);
+
+                    result.real.data[i * n + j] = re;
+                    result.imag.data[i * n + j] = im;
+                    result.real.data[i * n + j + 1] = re;
+                    result.imag.data[i * n + j + 1] = -im;
+                }
+
+                j += 2;
+            }
+        }
+
+        for j in 0..n {
+            let norm = (0..n)
+                .map(|i| result.real.data[i * n + j].powi(2) + result.imag.data[i * n + j].powi(2))
+                .sum::<f64>()
+                .sqrt();
+
+            if norm > 0.0 {
+                for i in 0..n {
+                    result.real.data[i * n + j] /= norm;
+                    result.imag.data[i * n + j] /= norm;
+                }
+            }
+        }
+
+        return result;
+    }
+}
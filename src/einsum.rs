@@ -0,0 +1,477 @@
+// src/einsum.rs
+
+pub mod einsum {
+    use crate::linear_algebra::linear_algebra::{
+        unwrap_or_panic, LinAlgError, Matrix, Scalar, Tensor,
+    };
+    use std::collections::HashMap;
+
+    // A tensor whose axes are named by labels. Einsum labels are letters,
+    // tensordot makes up its own.
+    struct Labelled<T: Scalar> {
+        labels: Vec<usize>,
+        tensor: Tensor<T>,
+    }
+
+    pub fn einsum<T: Scalar>(subscripts: &str, operands: &[&Tensor<T>]) -> Tensor<T> {
+        unwrap_or_panic(try_einsum(subscripts, operands))
+    }
+
+    // Sums products of the operands over the labels missing from the output,
+    // as in NumPy: "bij,bjk->bik" is a batched product, "ii->" a trace,
+    // "i,j->ij" an outer product and "ij->ji" a transpose. Without "->" the
+    // output holds the labels used exactly once, in alphabetical order.
+    //
+    // With more than two operands the pair to contract next is chosen
+    // greedily, preferring the pair whose result is smallest relative to
+    // its inputs.
+    pub fn try_einsum<T: Scalar>(
+        subscripts: &str,
+        operands: &[&Tensor<T>],
+    ) -> Result<Tensor<T>, LinAlgError> {
+        let (inputs, output) = parse(subscripts, operands)?;
+
+        let mut sizes: HashMap<usize, usize> = HashMap::new();
+        let mut owners: HashMap<usize, usize> = HashMap::new();
+
+        for (k, (labels, operand)) in inputs.iter().zip(operands).enumerate() {
+            for (&label, &size) in labels.iter().zip(&operand.shape) {
+                match sizes.get(&label) {
+                    Some(&known) if known != size => {
+                        return Err(LinAlgError::DimensionMismatch {
+                            left: operands[owners[&label]].shape.clone(),
+                            right: operand.shape.clone(),
+                        });
+                    }
+                    _ => {
+                        sizes.insert(label, size);
+                        owners.entry(label).or_insert(k);
+                    }
+                }
+            }
+        }
+
+        let mut terms: Vec<Labelled<T>> = inputs
+            .into_iter()
+            .zip(operands)
+            .map(|(labels, operand)| diagonal(labels, operand))
+            .collect();
+
+        // Labels only one operand uses are summed out before any contraction
+        for k in 0..terms.len() {
+            let needed = needed_labels(&terms, &[k], &output);
+            let term = std::mem::replace(&mut terms[k], placeholder());
+
+            terms[k] = sum_out(term, &needed);
+        }
+
+        while terms.len() > 1 {
+            let (i, j) = choose_pair(&terms, &output, &sizes);
+            let needed = needed_labels(&terms, &[i, j], &output);
+
+            let b = terms.remove(j);
+            let a = terms.remove(i);
+
+            terms.push(contract(a, b, &needed));
+        }
+
+        let result = sum_out(terms.remove(0), &output);
+
+        let axes: Vec<usize> = output
+            .iter()
+            .map(|label| result.labels.iter().position(|l| l == label).unwrap())
+            .collect();
+
+        return Ok(result.tensor.permute(&axes).to_contiguous());
+    }
+
+    pub fn tensordot<T: Scalar>(
+        a: &Tensor<T>,
+        b: &Tensor<T>,
+        axes_a: &[usize],
+        axes_b: &[usize],
+    ) -> Tensor<T> {
+        unwrap_or_panic(try_tensordot(a, b, axes_a, axes_b))
+    }
+
+    // Contracts axes_a[k] of `a` with axes_b[k] of `b`. The result has the
+    // remaining axes of `a` followed by the remaining axes of `b`, so
+    // tensordot(a, b, &[1], &[0]) is the matrix product.
+    pub fn try_tensordot<T: Scalar>(
+        a: &Tensor<T>,
+        b: &Tensor<T>,
+        axes_a: &[usize],
+        axes_b: &[usize],
+    ) -> Result<Tensor<T>, LinAlgError> {
+        if axes_a.len() != axes_b.len() {
+            return Err(LinAlgError::DimensionMismatch {
+                left: axes_a.to_vec(),
+                right: axes_b.to_vec(),
+            });
+        }
+
+        check_axes(a, axes_a)?;
+        check_axes(b, axes_b)?;
+
+        for (&i, &j) in axes_a.iter().zip(axes_b) {
+            if a.shape[i] != b.shape[j] {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: a.shape.clone(),
+                    right: b.shape.clone(),
+                });
+            }
+        }
+
+        // Axes of `a` are labelled 0..n, contracted axes of `b` share the
+        // label of their partner and the others continue from n
+        let labels_a: Vec<usize> = (0..a.ndim()).collect();
+        let mut next = a.ndim();
+
+        let labels_b: Vec<usize> = (0..b.ndim())
+            .map(|k| match axes_b.iter().position(|&axis| axis == k) {
+                Some(p) => axes_a[p],
+                None => {
+                    next += 1;
+                    next - 1
+                }
+            })
+            .collect();
+
+        let keep: Vec<usize> = labels_a
+            .iter()
+            .filter(|label| !axes_a.contains(label))
+            .chain(labels_b.iter().filter(|label| **label >= a.ndim()))
+            .copied()
+            .collect();
+
+        let a = Labelled {
+            labels: labels_a,
+            tensor: a.clone(),
+        };
+        let b = Labelled {
+            labels: labels_b,
+            tensor: b.clone(),
+        };
+
+        return Ok(contract(a, b, &keep).tensor.to_contiguous());
+    }
+
+    fn check_axes<T: Scalar>(t: &Tensor<T>, axes: &[usize]) -> Result<(), LinAlgError> {
+        for (k, &axis) in axes.iter().enumerate() {
+            if axis >= t.ndim() || axes[..k].contains(&axis) {
+                return Err(LinAlgError::IndexOutOfBounds {
+                    index: axes.to_vec(),
+                    shape: t.shape.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn invalid(subscripts: &str, reason: &'static str) -> LinAlgError {
+        LinAlgError::InvalidSubscripts {
+            subscripts: subscripts.to_string(),
+            reason,
+        }
+    }
+
+    // Splits "ij,jk->ik" into per-operand labels and output labels
+    fn parse<T: Scalar>(
+        subscripts: &str,
+        operands: &[&Tensor<T>],
+    ) -> Result<(Vec<Vec<usize>>, Vec<usize>), LinAlgError> {
+        let spec: String = subscripts.chars().filter(|c| !c.is_whitespace()).collect();
+
+        let (lhs, rhs) = match spec.split_once("->") {
+            Some((lhs, rhs)) => (lhs, Some(rhs)),
+            None => (spec.as_str(), None),
+        };
+
+        let labels = |term: &str| -> Result<Vec<usize>, LinAlgError> {
+            if !term.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(invalid(subscripts, "labels must be letters"));
+            }
+
+            Ok(term.chars().map(|c| c as usize).collect())
+        };
+
+        let inputs = lhs.split(',').map(labels).collect::<Result<Vec<_>, _>>()?;
+
+        if operands.is_empty() || inputs.len() != operands.len() {
+            return Err(invalid(subscripts, "expected one term per operand"));
+        }
+
+        for (term, operand) in inputs.iter().zip(operands) {
+            if term.len() != operand.ndim() {
+                return Err(invalid(
+                    subscripts,
+                    "a term does not match its operand's dimensions",
+                ));
+            }
+        }
+
+        let output = match rhs {
+            Some(rhs) => {
+                let output = labels(rhs)?;
+
+                for (k, label) in output.iter().enumerate() {
+                    if output[..k].contains(label) {
+                        return Err(invalid(subscripts, "output labels must be unique"));
+                    }
+
+                    if !inputs.iter().any(|term| term.contains(label)) {
+                        return Err(invalid(subscripts, "output label missing from the inputs"));
+                    }
+                }
+
+                output
+            }
+            None => {
+                let mut output: Vec<usize> = inputs
+                    .iter()
+                    .flatten()
+                    .copied()
+                    .filter(|label| inputs.iter().flatten().filter(|l| *l == label).count() == 1)
+                    .collect();
+
+                output.sort();
+                output
+            }
+        };
+
+        return Ok((inputs, output));
+    }
+
+    fn placeholder<T: Scalar>() -> Labelled<T> {
+        Labelled {
+            labels: vec![],
+            tensor: Tensor::zeros(&[]),
+        }
+    }
+
+    // Takes the diagonal over repeated labels, so "ii" becomes "i". Walking
+    // the diagonal of two axes means stepping along both at once, which is
+    // a single axis whose stride is the sum of theirs.
+    fn diagonal<T: Scalar>(labels: Vec<usize>, t: &Tensor<T>) -> Labelled<T> {
+        let mut unique: Vec<usize> = Vec::new();
+        let mut shape = Vec::new();
+        let mut strides: Vec<usize> = Vec::new();
+
+        for (k, &label) in labels.iter().enumerate() {
+            match unique.iter().position(|&l| l == label) {
+                Some(p) => strides[p] += t.strides[k],
+                None => {
+                    unique.push(label);
+                    shape.push(t.shape[k]);
+                    strides.push(t.strides[k]);
+                }
+            }
+        }
+
+        if unique.len() == labels.len() {
+            return Labelled {
+                labels,
+                tensor: t.clone(),
+            };
+        }
+
+        let view = Tensor {
+            shape: shape.clone(),
+            strides,
+            data: t.data.clone(),
+        };
+
+        Labelled {
+            labels: unique,
+            tensor: Tensor::from_vec(&shape, view.iter().collect()),
+        }
+    }
+
+    // Labels of the terms other than `skip` plus the output labels, which
+    // are the ones a contraction of the `skip` terms must keep
+    fn needed_labels<T: Scalar>(
+        terms: &[Labelled<T>],
+        skip: &[usize],
+        output: &[usize],
+    ) -> Vec<usize> {
+        let mut needed = output.to_vec();
+
+        for (k, term) in terms.iter().enumerate() {
+            if !skip.contains(&k) {
+                needed.extend(&term.labels);
+            }
+        }
+
+        return needed;
+    }
+
+    // Greedy ordering: the pair minimizing size(result) - size(a) - size(b)
+    fn choose_pair<T: Scalar>(
+        terms: &[Labelled<T>],
+        output: &[usize],
+        sizes: &HashMap<usize, usize>,
+    ) -> (usize, usize) {
+        let size = |labels: &mut dyn Iterator<Item = &usize>| -> i128 {
+            labels.map(|label| sizes[label] as i128).product()
+        };
+
+        let mut best = (0, 1);
+        let mut best_cost = i128::MAX;
+
+        for i in 0..terms.len() {
+            for j in (i + 1)..terms.len() {
+                let needed = needed_labels(terms, &[i, j], output);
+
+                let mut kept: Vec<usize> = terms[i].labels.clone();
+                kept.extend(
+                    terms[j]
+                        .labels
+                        .iter()
+                        .filter(|l| !terms[i].labels.contains(l)),
+                );
+                kept.retain(|label| needed.contains(label));
+
+                let cost = size(&mut kept.iter())
+                    - size(&mut terms[i].labels.iter())
+                    - size(&mut terms[j].labels.iter());
+
+                if cost < best_cost {
+                    best = (i, j);
+                    best_cost = cost;
+                }
+            }
+        }
+
+        return best;
+    }
+
+    // Sums over every axis whose label is not in `keep`
+    fn sum_out<T: Scalar>(term: Labelled<T>, keep: &[usize]) -> Labelled<T> {
+        let (kept, summed): (Vec<usize>, Vec<usize>) =
+            (0..term.labels.len()).partition(|&k| keep.contains(&term.labels[k]));
+
+        if summed.is_empty() {
+            return term;
+        }
+
+        let shape: Vec<usize> = kept.iter().map(|&k| term.tensor.shape[k]).collect();
+        let chunk: usize = summed.iter().map(|&k| term.tensor.shape[k]).product();
+
+        let axes: Vec<usize> = kept.iter().chain(&summed).copied().collect();
+        let permuted = term.tensor.permute(&axes).to_contiguous();
+
+        let data = if chunk == 0 {
+            vec![T::zero(); shape.iter().product()]
+        } else {
+            permuted
+                .data
+                .chunks(chunk)
+                .map(|values| values.iter().fold(T::zero(), |acc, &x| acc + x))
+                .collect()
+        };
+
+        Labelled {
+            labels: kept.iter().map(|&k| term.labels[k]).collect(),
+            tensor: Tensor::from_vec(&shape, data),
+        }
+    }
+
+    // Contracts two terms, keeping the labels in `keep`. Shared kept labels
+    // become batch axes, so the work is a batch of matrix products
+    // [batch, m, k] x [batch, k, n] on the blocked GEMM kernel.
+    fn contract<T: Scalar>(a: Labelled<T>, b: Labelled<T>, keep: &[usize]) -> Labelled<T> {
+        let a_keep: Vec<usize> = keep.iter().chain(&b.labels).copied().collect();
+        let b_keep: Vec<usize> = keep.iter().chain(&a.labels).copied().collect();
+
+        let a = sum_out(a, &a_keep);
+        let b = sum_out(b, &b_keep);
+
+        let shared = |label: &usize| a.labels.contains(label) && b.labels.contains(label);
+
+        let batch: Vec<usize> = a
+            .labels
+            .iter()
+            .copied()
+            .filter(|l| shared(l) && keep.contains(l))
+            .collect();
+        let summed: Vec<usize> = a
+            .labels
+            .iter()
+            .copied()
+            .filter(|l| shared(l) && !keep.contains(l))
+            .collect();
+        let free_a: Vec<usize> = a.labels.iter().copied().filter(|l| !shared(l)).collect();
+        let free_b: Vec<usize> = b.labels.iter().copied().filter(|l| !shared(l)).collect();
+
+        let size_of = |term: &Labelled<T>, labels: &[usize]| -> Vec<usize> {
+            labels
+                .iter()
+                .map(|label| {
+                    term.tensor.shape[term.labels.iter().position(|l| l == label).unwrap()]
+                })
+                .collect()
+        };
+
+        let batch_shape = size_of(&a, &batch);
+        let m_shape = size_of(&a, &free_a);
+        let n_shape = size_of(&b, &free_b);
+
+        let count: usize = batch_shape.iter().product();
+        let m: usize = m_shape.iter().product();
+        let k: usize = size_of(&a, &summed).iter().product();
+        let n: usize = n_shape.iter().product();
+
+        let a_order: Vec<usize> = batch
+            .iter()
+            .chain(&free_a)
+            .chain(&summed)
+            .copied()
+            .collect();
+        let b_order: Vec<usize> = batch
+            .iter()
+            .chain(&summed)
+            .chain(&free_b)
+            .copied()
+            .collect();
+
+        let a_data = arrange(a, &a_order);
+        let b_data = arrange(b, &b_order);
+
+        let mut data = Vec::with_capacity(count * m * n);
+
+        for p in 0..count {
+            let left = Matrix::from_vec(m, k, a_data[p * m * k..(p + 1) * m * k].to_vec());
+            let right = Matrix::from_vec(k, n, b_data[p * k * n..(p + 1) * k * n].to_vec());
+
+            data.extend(Matrix::matrix_mul(&left, &right).data);
+        }
+
+        let shape: Vec<usize> = batch_shape
+            .iter()
+            .chain(&m_shape)
+            .chain(&n_shape)
+            .copied()
+            .collect();
+
+        Labelled {
+            labels: batch
+                .iter()
+                .chain(&free_a)
+                .chain(&free_b)
+                .copied()
+                .collect(),
+            tensor: Tensor::from_vec(&shape, data),
+        }
+    }
+
+    // The elements of a term, row-major with its axes in `order`
+    fn arrange<T: Scalar>(term: Labelled<T>, order: &[usize]) -> Vec<T> {
+        let axes: Vec<usize> = order
+            .iter()
+            .map(|label| term.labels.iter().position(|l| l == label).unwrap())
+            .collect();
+
+        return term.tensor.permute(&axes).to_contiguous().data;
+    }
+}
@@ -0,0 +1,450 @@
+// src/fft.rs
+
+pub mod fft {
+    use crate::complex::complex::Complex64;
+    use crate::linear_algebra::linear_algebra::{
+        unwrap_or_panic, LinAlgError, Matrix, Scalar, Vector,
+    };
+    use std::f64::consts::PI;
+
+    // Conventions follow NumPy: the forward transform is
+    // X_k = sum_j x_j e^(-2 pi i jk/n), unnormalized, and the inverse
+    // divides by n. Every length is supported. Powers of two use radix-2
+    // passes, lengths whose prime factors are all at most MAX_RADIX use
+    // mixed-radix passes, and anything else goes through Bluestein's
+    // algorithm, so the cost is O(n log n) throughout.
+
+    // Largest prime factor handled by a mixed-radix pass, which costs
+    // O(n p) for a factor p
+    const MAX_RADIX: usize = 31;
+
+    // Convolutions with a shorter input than this are summed directly,
+    // which is both faster and exact at these sizes
+    const DIRECT_THRESHOLD: usize = 32;
+
+    /* Complex transforms */
+    pub fn fft(x: &Vector<Complex64>) -> Vector<Complex64> {
+        Vector {
+            size: x.size,
+            data: transform(&x.data, -1.0),
+        }
+    }
+
+    pub fn ifft(x: &Vector<Complex64>) -> Vector<Complex64> {
+        let scale = 1.0 / x.size as f64;
+
+        Vector {
+            size: x.size,
+            data: transform(&x.data, 1.0)
+                .into_iter()
+                .map(|z| z * scale)
+                .collect(),
+        }
+    }
+
+    // The 2-D transform, along every row and then every column
+    pub fn fft2(m: &Matrix<Complex64>) -> Matrix<Complex64> {
+        transform_2d(m, -1.0)
+    }
+
+    pub fn ifft2(m: &Matrix<Complex64>) -> Matrix<Complex64> {
+        let scale = 1.0 / (m.rows * m.cols) as f64;
+
+        return &transform_2d(m, 1.0) * Complex64::from(scale);
+    }
+
+    /* Real transforms */
+
+    // The first n/2 + 1 coefficients of the transform of a real signal, the
+    // rest being their conjugates
+    pub fn rfft(x: &Vector) -> Vector<Complex64> {
+        let data = real_spectrum(&x.data, x.size);
+
+        Vector {
+            size: data.len(),
+            data,
+        }
+    }
+
+    // Inverse of `rfft` for a signal of length n, which needs
+    // n/2 + 1 coefficients
+    pub fn irfft(x: &Vector<Complex64>, n: usize) -> Vector {
+        unwrap_or_panic(try_irfft(x, n))
+    }
+
+    pub fn try_irfft(x: &Vector<Complex64>, n: usize) -> Result<Vector, LinAlgError> {
+        if x.size != n / 2 + 1 {
+            return Err(LinAlgError::DimensionMismatch {
+                left: vec![x.size],
+                right: vec![n / 2 + 1],
+            });
+        }
+
+        return Ok(Vector {
+            size: n,
+            data: inverse_real_spectrum(&x.data, n),
+        });
+    }
+
+    /* Convolution and correlation */
+
+    // Linear convolution, of length a.size + b.size - 1
+    pub fn convolve(a: &Vector, b: &Vector) -> Vector {
+        if a.size == 0 || b.size == 0 {
+            return Vector::new(0);
+        }
+
+        let size = a.size + b.size - 1;
+
+        if a.size.min(b.size) < DIRECT_THRESHOLD {
+            let mut result = Vector::new(size);
+
+            for i in 0..a.size {
+                for j in 0..b.size {
+                    result.data[i + j] += a.data[i] * b.data[j];
+                }
+            }
+
+            return result;
+        }
+
+        // Padding to a power of two is always at least as fast as the
+        // exact length
+        let n = size.next_power_of_two();
+        let product = pointwise(&a.data, &b.data, n, false);
+        let mut data = inverse_real_spectrum(&product, n);
+        data.truncate(size);
+
+        return Vector { size, data };
+    }
+
+    // Linear cross-correlation sum_j a[j + lag] b[j] for every lag with any
+    // overlap. Element k holds lag k - (b.size - 1), so the zero lag sits at
+    // b.size - 1.
+    pub fn correlate(a: &Vector, b: &Vector) -> Vector {
+        let mut reversed = b.clone();
+        reversed.data.reverse();
+
+        return convolve(a, &reversed);
+    }
+
+    // Circular convolution sum_j a[j] b[(k - j) mod n] of equal length
+    // signals
+    pub fn circular_convolve(a: &Vector, b: &Vector) -> Vector {
+        unwrap_or_panic(try_circular_convolve(a, b))
+    }
+
+    pub fn try_circular_convolve(a: &Vector, b: &Vector) -> Result<Vector, LinAlgError> {
+        check_same_size(a, b)?;
+
+        let product = pointwise(&a.data, &b.data, a.size, false);
+
+        return Ok(Vector {
+            size: a.size,
+            data: inverse_real_spectrum(&product, a.size),
+        });
+    }
+
+    // Circular cross-correlation sum_j a[(j + k) mod n] b[j]
+    pub fn circular_correlate(a: &Vector, b: &Vector) -> Vector {
+        unwrap_or_panic(try_circular_correlate(a, b))
+    }
+
+    pub fn try_circular_correlate(a: &Vector, b: &Vector) -> Result<Vector, LinAlgError> {
+        check_same_size(a, b)?;
+
+        let product = pointwise(&a.data, &b.data, a.size, true);
+
+        return Ok(Vector {
+            size: a.size,
+            data: inverse_real_spectrum(&product, a.size),
+        });
+    }
+
+    fn check_same_size(a: &Vector, b: &Vector) -> Result<(), LinAlgError> {
+        if a.size != b.size {
+            return Err(LinAlgError::DimensionMismatch {
+                left: vec![a.size],
+                right: vec![b.size],
+            });
+        }
+
+        Ok(())
+    }
+
+    // The half spectrum of a zero padded to n times that of b, or of b's
+    // conjugate for correlations
+    fn pointwise(a: &[f64], b: &[f64], n: usize, conjugate: bool) -> Vec<Complex64> {
+        let fa = real_spectrum(a, n);
+        let fb = real_spectrum(b, n);
+
+        return fa
+            .iter()
+            .zip(fb.iter())
+            .map(|(&x, &y)| if conjugate { x * y.conj() } else { x * y })
+            .collect();
+    }
+
+    /* Kernels */
+
+    // Unnormalized DFT with e^(sign 2 pi i jk/n) as the kernel
+    fn transform(x: &[Complex64], sign: f64) -> Vec<Complex64> {
+        if x.len() <= 1 {
+            return x.to_vec();
+        }
+
+        if largest_prime_factor(x.len()) > MAX_RADIX {
+            return bluestein(x, sign);
+        }
+
+        return smooth_transform(x, sign);
+    }
+
+    // Lengths with only small prime factors. Decimation in time splits off
+    // the smallest factor p, transforms the p interleaved subsequences and
+    // combines them with a length p DFT per output column.
+    fn smooth_transform(x: &[Complex64], sign: f64) -> Vec<Complex64> {
+        let n = x.len();
+
+        if n.is_power_of_two() {
+            let mut result = x.to_vec();
+            radix2(&mut result, sign);
+
+            return result;
+        }
+
+        let p = smallest_prime_factor(n);
+        let m = n / p;
+
+        let subsequences: Vec<Vec<Complex64>> = (0..p)
+            .map(|r| {
+                let sub: Vec<Complex64> = x[r..].iter().step_by(p).copied().collect();
+                smooth_transform(&sub, sign)
+            })
+            .collect();
+
+        let roots = twiddles(n, n, sign);
+        let mut result = vec![Complex64::zero(); n];
+        let mut column = vec![Complex64::zero(); p];
+
+        for k in 0..m {
+            for r in 0..p {
+                column[r] = roots[r * k] * subsequences[r][k];
+            }
+
+            // e^(sign 2 pi i rq/p) is root r q m of n
+            for q in 0..p {
+                let mut sum = Complex64::zero();
+
+                for r in 0..p {
+                    sum += column[r] * roots[(r * q * m) % n];
+                }
+
+                result[k + m * q] = sum;
+            }
+        }
+
+        return result;
+    }
+
+    // In place iterative radix-2 transform of a power of two length
+    fn radix2(data: &mut [Complex64], sign: f64) {
+        let n = data.len();
+
+        if n <= 1 {
+            return;
+        }
+
+        // Bit reversal permutation
+        let mut j = 0;
+
+        for i in 1..n {
+            let mut bit = n >> 1;
+
+            while j & bit != 0 {
+                j ^= bit;
+                bit >>= 1;
+            }
+
+            j |= bit;
+
+            if i < j {
+                data.swap(i, j);
+            }
+        }
+
+        let roots = twiddles(n, n / 2, sign);
+        let mut len = 2;
+
+        while len <= n {
+            let half = len / 2;
+            let step = n / len;
+
+            for start in (0..n).step_by(len) {
+                for k in 0..half {
+                    let u = data[start + k];
+                    let v = data[start + k + half] * roots[k * step];
+
+                    data[start + k] = u + v;
+                    data[start + k + half] = u - v;
+                }
+            }
+
+            len <<= 1;
+        }
+    }
+
+    // Bluestein's algorithm rewrites the DFT as a convolution with the
+    // chirp w_k = e^(sign pi i k^2/n), using jk = (j^2 + k^2 - (k - j)^2)/2,
+    // and evaluates that convolution with power of two transforms
+    fn bluestein(x: &[Complex64], sign: f64) -> Vec<Complex64> {
+        let n = x.len();
+        let size = (2 * n - 1).next_power_of_two();
+
+        // k^2 is reduced mod 2n first, so the angle stays small and exact
+        let chirp: Vec<Complex64> = (0..n)
+            .map(|k| {
+                let k2 = (k as u128 * k as u128 % (2 * n) as u128) as f64;
+                Complex64::from_polar(1.0, sign * PI * k2 / n as f64)
+            })
+            .collect();
+
+        let mut a = vec![Complex64::zero(); size];
+        let mut b = vec![Complex64::zero(); size];
+
+        for k in 0..n {
+            a[k] = x[k] * chirp[k];
+        }
+
+        b[0] = chirp[0].conj();
+
+        for k in 1..n {
+            b[k] = chirp[k].conj();
+            b[size - k] = chirp[k].conj();
+        }
+
+        radix2(&mut a, -1.0);
+        radix2(&mut b, -1.0);
+
+        for i in 0..size {
+            a[i] *= b[i];
+        }
+
+        radix2(&mut a, 1.0);
+
+        let scale = 1.0 / size as f64;
+
+        return (0..n).map(|k| chirp[k] * a[k] * scale).collect();
+    }
+
+    // The first `count` powers of e^(sign 2 pi i/n), each computed directly
+    // rather than by recurrence so rounding does not accumulate
+    fn twiddles(n: usize, count: usize, sign: f64) -> Vec<Complex64> {
+        (0..count)
+            .map(|k| Complex64::from_polar(1.0, sign * 2.0 * PI * k as f64 / n as f64))
+            .collect()
+    }
+
+    // Half spectrum of x zero padded (or truncated) to length n. Even
+    // lengths pack the signal into a complex one of half the length:
+    // z_j = x_2j + i x_2j+1 transforms to Z, from which the even and odd
+    // sample spectra are recovered by conjugate symmetry.
+    fn real_spectrum(x: &[f64], n: usize) -> Vec<Complex64> {
+        let sample = |j: usize| if j < x.len() { x[j] } else { 0.0 };
+
+        if n % 2 == 1 || n < 2 {
+            let signal: Vec<Complex64> = (0..n).map(|j| Complex64::from(sample(j))).collect();
+            let mut result = transform(&signal, -1.0);
+            result.truncate(n / 2 + 1);
+
+            return result;
+        }
+
+        let h = n / 2;
+        let packed: Vec<Complex64> = (0..h)
+            .map(|j| Complex64::new(sample(2 * j), sample(2 * j + 1)))
+            .collect();
+
+        let z = transform(&packed, -1.0);
+        let roots = twiddles(n, h + 1, -1.0);
+
+        return (0..=h)
+            .map(|k| {
+                let a = z[k % h];
+                let b = z[(h - k) % h].conj();
+
+                let even = (a + b) * 0.5;
+                let odd = (a - b) * Complex64::new(0.0, -0.5);
+
+                even + roots[k] * odd
+            })
+            .collect();
+    }
+
+    // Real signal of length n with the given half spectrum, rebuilding the
+    // upper half from conjugate symmetry
+    fn inverse_real_spectrum(x: &[Complex64], n: usize) -> Vec<f64> {
+        let mut full = vec![Complex64::zero(); n];
+
+        for k in 0..n {
+            full[k] = if k <= n / 2 { x[k] } else { x[n - k].conj() };
+        }
+
+        let scale = 1.0 / n as f64;
+
+        return transform(&full, 1.0)
+            .into_iter()
+            .map(|z| z.re * scale)
+            .collect();
+    }
+
+    fn transform_2d(m: &Matrix<Complex64>, sign: f64) -> Matrix<Complex64> {
+        let (rows, cols) = (m.rows, m.cols);
+        let mut result = m.clone();
+
+        for i in 0..rows {
+            let row = transform(&result.data[i * cols..(i + 1) * cols], sign);
+            result.data[i * cols..(i + 1) * cols].copy_from_slice(&row);
+        }
+
+        for j in 0..cols {
+            let column: Vec<Complex64> = (0..rows).map(|i| result.data[i * cols + j]).collect();
+
+            for (i, z) in transform(&column, sign).into_iter().enumerate() {
+                result.data[i * cols + j] = z;
+            }
+        }
+
+        return result;
+    }
+
+    fn smallest_prime_factor(n: usize) -> usize {
+        let mut p = 2;
+
+        while p * p <= n {
+            if n.is_multiple_of(p) {
+                return p;
+            }
+
+            p += 1;
+        }
+
+        return n;
+    }
+
+    fn largest_prime_factor(mut n: usize) -> usize {
+        let mut largest = 1;
+
+        while n > 1 {
+            let p = smallest_prime_factor(n);
+
+            while n.is_multiple_of(p) {
+                n /= p;
+            }
+
+            largest = p;
+        }
+
+        return largest;
+    }
+}
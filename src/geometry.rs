@@ -0,0 +1,601 @@
+// src/geometry.rs
+
+pub mod geometry {
+    use crate::linear_algebra::linear_algebra::{unwrap_or_panic, LinAlgError, Matrix, Vector};
+    use std::cmp::Ordering;
+    use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+    // Conventions: right-handed coordinates, column vectors transformed as
+    // p' = M p, and angles in radians. Transforms compose right to left, so
+    // `&translation(t) * &rotation(q)` rotates first.
+
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    pub struct Vec3 {
+        pub x: f64,
+        pub y: f64,
+        pub z: f64,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    pub struct Vec4 {
+        pub x: f64,
+        pub y: f64,
+        pub z: f64,
+        pub w: f64,
+    }
+
+    impl Vec3 {
+        pub fn new(x: f64, y: f64, z: f64) -> Vec3 {
+            Vec3 { x, y, z }
+        }
+
+        pub fn unit_x() -> Vec3 {
+            Vec3::new(1.0, 0.0, 0.0)
+        }
+
+        pub fn unit_y() -> Vec3 {
+            Vec3::new(0.0, 1.0, 0.0)
+        }
+
+        pub fn unit_z() -> Vec3 {
+            Vec3::new(0.0, 0.0, 1.0)
+        }
+
+        pub fn cross(self, other: Vec3) -> Vec3 {
+            Vec3::new(
+                self.y * other.z - self.z * other.y,
+                self.z * other.x - self.x * other.z,
+                self.x * other.y - self.y * other.x,
+            )
+        }
+
+        // Homogeneous coordinates: w = 1 for points, w = 0 for directions
+        pub fn extend(self, w: f64) -> Vec4 {
+            Vec4::new(self.x, self.y, self.z, w)
+        }
+
+        pub fn to_vector(self) -> Vector {
+            let mut result = Vector::new(3);
+            result.data = vec![self.x, self.y, self.z];
+
+            return result;
+        }
+
+        pub fn from_vector(v: &Vector) -> Vec3 {
+            unwrap_or_panic(Vec3::try_from_vector(v))
+        }
+
+        pub fn try_from_vector(v: &Vector) -> Result<Vec3, LinAlgError> {
+            if v.size != 3 {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![v.size],
+                    right: vec![3],
+                });
+            }
+
+            Ok(Vec3::new(v.data[0], v.data[1], v.data[2]))
+        }
+    }
+
+    impl Vec4 {
+        pub fn new(x: f64, y: f64, z: f64, w: f64) -> Vec4 {
+            Vec4 { x, y, z, w }
+        }
+
+        // Drops w without dividing by it
+        pub fn truncate(self) -> Vec3 {
+            Vec3::new(self.x, self.y, self.z)
+        }
+
+        pub fn to_vector(self) -> Vector {
+            let mut result = Vector::new(4);
+            result.data = vec![self.x, self.y, self.z, self.w];
+
+            return result;
+        }
+
+        pub fn from_vector(v: &Vector) -> Vec4 {
+            unwrap_or_panic(Vec4::try_from_vector(v))
+        }
+
+        pub fn try_from_vector(v: &Vector) -> Result<Vec4, LinAlgError> {
+            if v.size != 4 {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![v.size],
+                    right: vec![4],
+                });
+            }
+
+            Ok(Vec4::new(v.data[0], v.data[1], v.data[2], v.data[3]))
+        }
+    }
+
+    // Componentwise arithmetic and the Euclidean helpers shared by both
+    // vector types
+    macro_rules! impl_geometric_vector {
+        ($t:ident, $($field:ident),*) => {
+            impl $t {
+                pub fn zero() -> $t {
+                    $t { $($field: 0.0),* }
+                }
+
+                pub fn dot(self, other: $t) -> f64 {
+                    0.0 $(+ self.$field * other.$field)*
+                }
+
+                pub fn length(self) -> f64 {
+                    self.dot(self).sqrt()
+                }
+
+                pub fn normalize(self) -> $t {
+                    self / self.length()
+                }
+
+                pub fn distance(self, other: $t) -> f64 {
+                    (self - other).length()
+                }
+
+                // Straight line interpolation, self at t = 0 and other at t = 1
+                pub fn lerp(self, other: $t, t: f64) -> $t {
+                    self + (other - self) * t
+                }
+            }
+
+            impl Add for $t {
+                type Output = $t;
+
+                fn add(self, rhs: $t) -> $t {
+                    $t { $($field: self.$field + rhs.$field),* }
+                }
+            }
+
+            impl Sub for $t {
+                type Output = $t;
+
+                fn sub(self, rhs: $t) -> $t {
+                    $t { $($field: self.$field - rhs.$field),* }
+                }
+            }
+
+            impl Neg for $t {
+                type Output = $t;
+
+                fn neg(self) -> $t {
+                    $t { $($field: -self.$field),* }
+                }
+            }
+
+            impl Mul<f64> for $t {
+                type Output = $t;
+
+                fn mul(self, rhs: f64) -> $t {
+                    $t { $($field: self.$field * rhs),* }
+                }
+            }
+
+            impl Mul<$t> for f64 {
+                type Output = $t;
+
+                fn mul(self, rhs: $t) -> $t {
+                    rhs * self
+                }
+            }
+
+            impl Div<f64> for $t {
+                type Output = $t;
+
+                fn div(self, rhs: f64) -> $t {
+                    $t { $($field: self.$field / rhs),* }
+                }
+            }
+
+            impl AddAssign for $t {
+                fn add_assign(&mut self, rhs: $t) {
+                    *self = *self + rhs;
+                }
+            }
+
+            impl SubAssign for $t {
+                fn sub_assign(&mut self, rhs: $t) {
+                    *self = *self - rhs;
+                }
+            }
+
+            impl MulAssign<f64> for $t {
+                fn mul_assign(&mut self, rhs: f64) {
+                    *self = *self * rhs;
+                }
+            }
+        };
+    }
+
+    impl_geometric_vector!(Vec3, x, y, z);
+    impl_geometric_vector!(Vec4, x, y, z, w);
+
+    /* Quaternions */
+
+    // q = w + xi + yj + zk. Rotations use unit quaternions, where q and -q
+    // describe the same rotation.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Quaternion {
+        pub w: f64,
+        pub x: f64,
+        pub y: f64,
+        pub z: f64,
+    }
+
+    impl Quaternion {
+        pub fn new(w: f64, x: f64, y: f64, z: f64) -> Quaternion {
+            Quaternion { w, x, y, z }
+        }
+
+        pub fn identity() -> Quaternion {
+            Quaternion::new(1.0, 0.0, 0.0, 0.0)
+        }
+
+        fn vector_part(self) -> Vec3 {
+            Vec3::new(self.x, self.y, self.z)
+        }
+
+        fn from_parts(w: f64, v: Vec3) -> Quaternion {
+            Quaternion::new(w, v.x, v.y, v.z)
+        }
+
+        // Rotation by `angle` around `axis`, counterclockwise when looking
+        // down the axis towards the origin. The axis need not be unit length.
+        pub fn from_axis_angle(axis: Vec3, angle: f64) -> Quaternion {
+            let (sin, cos) = (angle / 2.0).sin_cos();
+
+            Quaternion::from_parts(cos, axis.normalize() * sin)
+        }
+
+        // Unit axis and angle in [0, pi]. The identity has angle 0 and
+        // reports the x axis.
+        pub fn to_axis_angle(self) -> (Vec3, f64) {
+            let q = if self.w < 0.0 { -self } else { self }.normalize();
+            let sin = q.vector_part().length();
+
+            if sin < 1e-12 {
+                return (Vec3::unit_x(), 0.0);
+            }
+
+            return (q.vector_part() / sin, 2.0 * sin.atan2(q.w));
+        }
+
+        // Roll about x, then pitch about y, then yaw about z, so the rotation
+        // matrix is Rz(yaw) Ry(pitch) Rx(roll)
+        pub fn from_euler(roll: f64, pitch: f64, yaw: f64) -> Quaternion {
+            let (sr, cr) = (roll / 2.0).sin_cos();
+            let (sp, cp) = (pitch / 2.0).sin_cos();
+            let (sy, cy) = (yaw / 2.0).sin_cos();
+
+            Quaternion::new(
+                cr * cp * cy + sr * sp * sy,
+                sr * cp * cy - cr * sp * sy,
+                cr * sp * cy + sr * cp * sy,
+                cr * cp * sy - sr * sp * cy,
+            )
+        }
+
+        // (roll, pitch, yaw) as in `from_euler`, with pitch in [-pi/2, pi/2].
+        // At pitch = +-pi/2 (gimbal lock) roll and yaw are not unique.
+        pub fn to_euler(self) -> (f64, f64, f64) {
+            let q = self.normalize();
+
+            let roll = (2.0 * (q.w * q.x + q.y * q.z)).atan2(1.0 - 2.0 * (q.x * q.x + q.y * q.y));
+            let pitch = (2.0 * (q.w * q.y - q.z * q.x)).clamp(-1.0, 1.0).asin();
+            let yaw = (2.0 * (q.w * q.z + q.x * q.y)).atan2(1.0 - 2.0 * (q.y * q.y + q.z * q.z));
+
+            return (roll, pitch, yaw);
+        }
+
+        // 3 x 3 rotation matrix of a unit quaternion
+        pub fn to_rotation_matrix(self) -> Matrix {
+            let Quaternion { w, x, y, z } = self;
+
+            Matrix::from_vec(
+                3,
+                3,
+                vec![
+                    1.0 - 2.0 * (y * y + z * z),
+                    2.0 * (x * y - w * z),
+                    2.0 * (x * z + w * y),
+                    2.0 * (x * y + w * z),
+                    1.0 - 2.0 * (x * x + z * z),
+                    2.0 * (y * z - w * x),
+                    2.0 * (x * z - w * y),
+                    2.0 * (y * z + w * x),
+                    1.0 - 2.0 * (x * x + y * y),
+                ],
+            )
+        }
+
+        pub fn from_rotation_matrix(m: &Matrix) -> Quaternion {
+            unwrap_or_panic(Quaternion::try_from_rotation_matrix(m))
+        }
+
+        // Accepts a 3 x 3 rotation or the rotation block of a 4 x 4
+        // transform. The matrix is assumed orthogonal; the largest of w, x,
+        // y and z is recovered first (Shepperd's method) to stay accurate
+        // near 180 degree rotations.
+        pub fn try_from_rotation_matrix(m: &Matrix) -> Result<Quaternion, LinAlgError> {
+            if !(m.rows == m.cols && (m.rows == 3 || m.rows == 4)) {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![m.rows, m.cols],
+                    right: vec![3, 3],
+                });
+            }
+
+            let r = |i: usize, j: usize| m[(i, j)];
+            let trace = r(0, 0) + r(1, 1) + r(2, 2);
+
+            let q = if trace > 0.0 {
+                let s = 2.0 * (trace + 1.0).sqrt();
+                Quaternion::new(
+                    s / 4.0,
+                    (r(2, 1) - r(1, 2)) / s,
+                    (r(0, 2) - r(2, 0)) / s,
+                    (r(1, 0) - r(0, 1)) / s,
+                )
+            } else if r(0, 0) > r(1, 1) && r(0, 0) > r(2, 2) {
+                let s = 2.0 * (1.0 + r(0, 0) - r(1, 1) - r(2, 2)).sqrt();
+                Quaternion::new(
+                    (r(2, 1) - r(1, 2)) / s,
+                    s / 4.0,
+                    (r(0, 1) + r(1, 0)) / s,
+                    (r(0, 2) + r(2, 0)) / s,
+                )
+            } else if r(1, 1) > r(2, 2) {
+                let s = 2.0 * (1.0 + r(1, 1) - r(0, 0) - r(2, 2)).sqrt();
+                Quaternion::new(
+                    (r(0, 2) - r(2, 0)) / s,
+                    (r(0, 1) + r(1, 0)) / s,
+                    s / 4.0,
+                    (r(1, 2) + r(2, 1)) / s,
+                )
+            } else {
+                let s = 2.0 * (1.0 + r(2, 2) - r(0, 0) - r(1, 1)).sqrt();
+                Quaternion::new(
+                    (r(1, 0) - r(0, 1)) / s,
+                    (r(0, 2) + r(2, 0)) / s,
+                    (r(1, 2) + r(2, 1)) / s,
+                    s / 4.0,
+                )
+            };
+
+            return Ok(q.normalize());
+        }
+
+        pub fn conjugate(self) -> Quaternion {
+            Quaternion::new(self.w, -self.x, -self.y, -self.z)
+        }
+
+        pub fn dot(self, other: Quaternion) -> f64 {
+            self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+        }
+
+        pub fn norm(self) -> f64 {
+            Vec4::new(self.w, self.x, self.y, self.z).length()
+        }
+
+        pub fn normalize(self) -> Quaternion {
+            self * (1.0 / self.norm())
+        }
+
+        pub fn inverse(self) -> Quaternion {
+            self.conjugate() * (1.0 / self.dot(self))
+        }
+
+        // Rotates v by a unit quaternion, q v q^-1 without forming the
+        // products
+        pub fn rotate(self, v: Vec3) -> Vec3 {
+            let u = self.vector_part();
+            let t = u.cross(v) * 2.0;
+
+            v + t * self.w + u.cross(t)
+        }
+
+        // Spherical linear interpolation between unit quaternions, at
+        // constant angular speed along the shorter arc. Nearly equal
+        // rotations fall back to normalized linear interpolation, where the
+        // slerp weights lose precision.
+        pub fn slerp(self, other: Quaternion, t: f64) -> Quaternion {
+            let mut cos = self.dot(other);
+            let mut end = other;
+
+            if cos < 0.0 {
+                cos = -cos;
+                end = -other;
+            }
+
+            if cos > 0.9995 {
+                return (self * (1.0 - t) + end * t).normalize();
+            }
+
+            let theta = cos.acos();
+            let sin = theta.sin();
+
+            return self * (((1.0 - t) * theta).sin() / sin) + end * ((t * theta).sin() / sin);
+        }
+    }
+
+    // Hamilton product: applying `rhs` first, then `self`
+    impl Mul for Quaternion {
+        type Output = Quaternion;
+
+        fn mul(self, rhs: Quaternion) -> Quaternion {
+            let (u, v) = (self.vector_part(), rhs.vector_part());
+
+            Quaternion::from_parts(
+                self.w * rhs.w - u.dot(v),
+                v * self.w + u * rhs.w + u.cross(v),
+            )
+        }
+    }
+
+    impl Mul<f64> for Quaternion {
+        type Output = Quaternion;
+
+        fn mul(self, rhs: f64) -> Quaternion {
+            Quaternion::new(self.w * rhs, self.x * rhs, self.y * rhs, self.z * rhs)
+        }
+    }
+
+    impl Add for Quaternion {
+        type Output = Quaternion;
+
+        fn add(self, rhs: Quaternion) -> Quaternion {
+            Quaternion::new(
+                self.w + rhs.w,
+                self.x + rhs.x,
+                self.y + rhs.y,
+                self.z + rhs.z,
+            )
+        }
+    }
+
+    impl Neg for Quaternion {
+        type Output = Quaternion;
+
+        fn neg(self) -> Quaternion {
+            self * -1.0
+        }
+    }
+
+    /* Homogeneous transforms */
+
+    fn identity4() -> Matrix {
+        let mut result = Matrix::new(4, 4);
+
+        for i in 0..4 {
+            result[(i, i)] = 1.0;
+        }
+
+        return result;
+    }
+
+    pub fn translation(offset: Vec3) -> Matrix {
+        let mut result = identity4();
+
+        result[(0, 3)] = offset.x;
+        result[(1, 3)] = offset.y;
+        result[(2, 3)] = offset.z;
+
+        return result;
+    }
+
+    pub fn scaling(factors: Vec3) -> Matrix {
+        let mut result = identity4();
+
+        result[(0, 0)] = factors.x;
+        result[(1, 1)] = factors.y;
+        result[(2, 2)] = factors.z;
+
+        return result;
+    }
+
+    pub fn rotation(q: Quaternion) -> Matrix {
+        let mut result = identity4();
+
+        result
+            .slice_mut(0..3, 0..3)
+            .copy_from(&q.to_rotation_matrix().view());
+
+        return result;
+    }
+
+    pub fn look_at(eye: Vec3, target: Vec3, up: Vec3) -> Matrix {
+        unwrap_or_panic(try_look_at(eye, target, up))
+    }
+
+    // View matrix of a camera at `eye` facing `target`, mapping the camera
+    // to the origin looking down -z with `up` towards +y (as gluLookAt).
+    // Fails with SingularMatrix when `up` is parallel to the view direction.
+    pub fn try_look_at(eye: Vec3, target: Vec3, up: Vec3) -> Result<Matrix, LinAlgError> {
+        let forward = (target - eye).normalize();
+        let side = forward.cross(up);
+
+        // Also rejects eye == target, where the direction is NaN
+        if side.length().partial_cmp(&(1e-12 * up.length())) != Some(Ordering::Greater) {
+            return Err(LinAlgError::SingularMatrix);
+        }
+
+        let side = side.normalize();
+        let up = side.cross(forward);
+
+        return Ok(Matrix::from_vec(
+            4,
+            4,
+            vec![
+                side.x,
+                side.y,
+                side.z,
+                -side.dot(eye),
+                up.x,
+                up.y,
+                up.z,
+                -up.dot(eye),
+                -forward.x,
+                -forward.y,
+                -forward.z,
+                forward.dot(eye),
+                0.0,
+                0.0,
+                0.0,
+                1.0,
+            ],
+        ));
+    }
+
+    // OpenGL-style projection with a vertical field of view `fovy`, mapping
+    // the frustum between the near and far planes to the cube [-1, 1]^3
+    pub fn perspective(fovy: f64, aspect: f64, near: f64, far: f64) -> Matrix {
+        let f = 1.0 / (fovy / 2.0).tan();
+        let mut result = Matrix::new(4, 4);
+
+        result[(0, 0)] = f / aspect;
+        result[(1, 1)] = f;
+        result[(2, 2)] = (far + near) / (near - far);
+        result[(2, 3)] = 2.0 * far * near / (near - far);
+        result[(3, 2)] = -1.0;
+
+        return result;
+    }
+
+    pub fn transform(m: &Matrix, v: Vec4) -> Vec4 {
+        unwrap_or_panic(try_transform(m, v))
+    }
+
+    pub fn try_transform(m: &Matrix, v: Vec4) -> Result<Vec4, LinAlgError> {
+        if m.rows != 4 || m.cols != 4 {
+            return Err(LinAlgError::DimensionMismatch {
+                left: vec![m.rows, m.cols],
+                right: vec![4, 4],
+            });
+        }
+
+        let row = |i: usize| Vec4::new(m[(i, 0)], m[(i, 1)], m[(i, 2)], m[(i, 3)]).dot(v);
+
+        return Ok(Vec4::new(row(0), row(1), row(2), row(3)));
+    }
+
+    // Applies a transform to a point (w = 1), with the perspective divide
+    pub fn transform_point(m: &Matrix, p: Vec3) -> Vec3 {
+        unwrap_or_panic(try_transform_point(m, p))
+    }
+
+    pub fn try_transform_point(m: &Matrix, p: Vec3) -> Result<Vec3, LinAlgError> {
+        let result = try_transform(m, p.extend(1.0))?;
+
+        return Ok(result.truncate() / result.w);
+    }
+
+    // Applies a transform to a direction (w = 0), which ignores translation
+    pub fn transform_direction(m: &Matrix, d: Vec3) -> Vec3 {
+        unwrap_or_panic(try_transform_direction(m, d))
+    }
+
+    pub fn try_transform_direction(m: &Matrix, d: Vec3) -> Result<Vec3, LinAlgError> {
+        return Ok(try_transform(m, d.extend(0.0))?.truncate());
+    }
+}
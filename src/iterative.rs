@@ -0,0 +1,808 @@
+// src/iterative.rs
+
+pub mod iterative {
+    use crate::linear_algebra::linear_algebra::{LinAlgError, Matrix, Vector};
+    use crate::sparse::sparse::{CscMatrix, CsrMatrix};
+
+    // Anything that can compute y = Ax. The solvers only ever touch the
+    // matrix through this, so dense, sparse and matrix-free operators all
+    // work.
+    pub trait LinearOperator {
+        fn shape(&self) -> (usize, usize);
+        fn apply(&self, x: &[f64], y: &mut [f64]);
+    }
+
+    impl LinearOperator for Matrix {
+        fn shape(&self) -> (usize, usize) {
+            (self.rows, self.cols)
+        }
+
+        fn apply(&self, x: &[f64], y: &mut [f64]) {
+            for i in 0..self.rows {
+                y[i] = dot(&self.data[i * self.cols..(i + 1) * self.cols], x);
+            }
+        }
+    }
+
+    impl LinearOperator for CsrMatrix {
+        fn shape(&self) -> (usize, usize) {
+            (self.rows, self.cols)
+        }
+
+        fn apply(&self, x: &[f64], y: &mut [f64]) {
+            for i in 0..self.rows {
+                y[i] = (self.indptr[i]..self.indptr[i + 1])
+                    .map(|k| self.values[k] * x[self.indices[k]])
+                    .sum();
+            }
+        }
+    }
+
+    impl LinearOperator for CscMatrix {
+        fn shape(&self) -> (usize, usize) {
+            (self.rows, self.cols)
+        }
+
+        fn apply(&self, x: &[f64], y: &mut [f64]) {
+            y.fill(0.0);
+
+            for j in 0..self.cols {
+                for k in self.indptr[j]..self.indptr[j + 1] {
+                    y[self.indices[k]] += self.values[k] * x[j];
+                }
+            }
+        }
+    }
+
+    // A square matrix-free operator given by a closure writing Ax into its
+    // second argument
+    pub struct FnOperator<F: Fn(&[f64], &mut [f64])> {
+        pub size: usize,
+        pub f: F,
+    }
+
+    impl<F: Fn(&[f64], &mut [f64])> FnOperator<F> {
+        pub fn new(size: usize, f: F) -> FnOperator<F> {
+            FnOperator { size, f }
+        }
+    }
+
+    impl<F: Fn(&[f64], &mut [f64])> LinearOperator for FnOperator<F> {
+        fn shape(&self) -> (usize, usize) {
+            (self.size, self.size)
+        }
+
+        fn apply(&self, x: &[f64], y: &mut [f64]) {
+            (self.f)(x, y)
+        }
+    }
+
+    // Computes z = M^-1 r for a preconditioner M approximating A
+    pub trait Preconditioner {
+        fn apply(&self, r: &[f64], z: &mut [f64]);
+    }
+
+    // No preconditioning, M = I
+    pub struct Identity;
+
+    impl Preconditioner for Identity {
+        fn apply(&self, r: &[f64], z: &mut [f64]) {
+            z.copy_from_slice(r);
+        }
+    }
+
+    // M = diag(A)
+    pub struct Jacobi {
+        pub inverse_diagonal: Vec<f64>,
+    }
+
+    impl Jacobi {
+        pub fn new(a: &CsrMatrix) -> Result<Jacobi, LinAlgError> {
+            check_square(a.rows, a.cols)?;
+
+            let mut inverse_diagonal = vec![0.0; a.rows];
+
+            for i in 0..a.rows {
+                let diagonal = a.get(i, i);
+
+                if diagonal == 0.0 {
+                    return Err(LinAlgError::SingularMatrix);
+                }
+
+                inverse_diagonal[i] = 1.0 / diagonal;
+            }
+
+            Ok(Jacobi { inverse_diagonal })
+        }
+    }
+
+    impl Preconditioner for Jacobi {
+        fn apply(&self, r: &[f64], z: &mut [f64]) {
+            for i in 0..r.len() {
+                z[i] = r[i] * self.inverse_diagonal[i];
+            }
+        }
+    }
+
+    // Zero fill-in incomplete Cholesky, M = LL^T where L keeps the sparsity
+    // pattern of the lower triangle of A. Only the lower triangle is read.
+    pub struct IncompleteCholesky {
+        pub l: CsrMatrix,
+    }
+
+    impl IncompleteCholesky {
+        pub fn new(a: &CsrMatrix) -> Result<IncompleteCholesky, LinAlgError> {
+            check_square(a.rows, a.cols)?;
+
+            let n = a.rows;
+            let mut l = CsrMatrix::new(n, n);
+
+            for i in 0..n {
+                let start = l.indices.len();
+
+                for k in a.indptr[i]..a.indptr[i + 1] {
+                    let j = a.indices[k];
+
+                    if j > i {
+                        break;
+                    }
+
+                    // Row i of L so far against row j, both sorted by column
+                    let (mut p, mut q) = (start, l.indptr[j]);
+                    let q_end = if j == i {
+                        l.indices.len()
+                    } else {
+                        l.indptr[j + 1]
+                    };
+                    let mut sum = a.values[k];
+
+                    while p < l.indices.len() && q < q_end {
+                        let (x, y) = (l.indices[p], l.indices[q]);
+
+                        if x >= j || y >= j {
+                            break;
+                        }
+
+                        if x == y {
+                            sum -= l.values[p] * l.values[q];
+                            p += 1;
+                            q += 1;
+                        } else if x < y {
+                            p += 1;
+                        } else {
+                            q += 1;
+                        }
+                    }
+
+                    if j == i {
+                        // Also catches NaN
+                        if sum.partial_cmp(&0.0) != Some(std::cmp::Ordering::Greater) {
+                            return Err(LinAlgError::NotPositiveDefinite { pivot: i });
+                        }
+
+                        l.indices.push(i);
+                        l.values.push(sum.sqrt());
+                    } else {
+                        // Row j is complete, so its diagonal is its last entry
+                        l.indices.push(j);
+                        l.values.push(sum / l.values[l.indptr[j + 1] - 1]);
+                    }
+                }
+
+                if l.indices.last() != Some(&i) || l.indices.len() == start {
+                    return Err(LinAlgError::NotPositiveDefinite { pivot: i });
+                }
+
+                l.indptr[i + 1] = l.indices.len();
+            }
+
+            Ok(IncompleteCholesky { l })
+        }
+    }
+
+    impl Preconditioner for IncompleteCholesky {
+        fn apply(&self, r: &[f64], z: &mut [f64]) {
+            let l = &self.l;
+            let n = l.rows;
+
+            // L y = r, the diagonal is the last entry of each row
+            for i in 0..n {
+                let (start, end) = (l.indptr[i], l.indptr[i + 1] - 1);
+                let mut sum = r[i];
+
+                for k in start..end {
+                    sum -= l.values[k] * z[l.indices[k]];
+                }
+
+                z[i] = sum / l.values[end];
+            }
+
+            // L^T z = y, column by column from the back
+            for i in (0..n).rev() {
+                let (start, end) = (l.indptr[i], l.indptr[i + 1] - 1);
+                z[i] /= l.values[end];
+
+                for k in start..end {
+                    z[l.indices[k]] -= l.values[k] * z[i];
+                }
+            }
+        }
+    }
+
+    // Zero fill-in incomplete LU, M = LU where L (unit lower) and U share the
+    // sparsity pattern of A
+    pub struct IncompleteLU {
+        pub lu: CsrMatrix,
+        // Position of the diagonal entry of each row in `lu.values`
+        diagonal: Vec<usize>,
+    }
+
+    impl IncompleteLU {
+        pub fn new(a: &CsrMatrix) -> Result<IncompleteLU, LinAlgError> {
+            check_square(a.rows, a.cols)?;
+
+            let n = a.rows;
+            let mut lu = a.clone();
+            let mut diagonal = vec![0; n];
+            // Position of each column of the current row, or usize::MAX
+            let mut position = vec![usize::MAX; n];
+
+            for i in 0..n {
+                let (start, end) = (lu.indptr[i], lu.indptr[i + 1]);
+
+                for k in start..end {
+                    position[lu.indices[k]] = k;
+                }
+
+                for k in start..end {
+                    let column = lu.indices[k];
+
+                    if column >= i {
+                        break;
+                    }
+
+                    let multiplier = lu.values[k] / lu.values[diagonal[column]];
+                    lu.values[k] = multiplier;
+
+                    // Update the entries of row i that exist in row `column`
+                    // of U
+                    for p in diagonal[column] + 1..lu.indptr[column + 1] {
+                        let target = position[lu.indices[p]];
+
+                        if target != usize::MAX {
+                            lu.values[target] -= multiplier * lu.values[p];
+                        }
+                    }
+                }
+
+                if position[i] == usize::MAX || lu.values[position[i]] == 0.0 {
+                    return Err(LinAlgError::SingularMatrix);
+                }
+
+                diagonal[i] = position[i];
+
+                for k in start..end {
+                    position[lu.indices[k]] = usize::MAX;
+                }
+            }
+
+            Ok(IncompleteLU { lu, diagonal })
+        }
+    }
+
+    impl Preconditioner for IncompleteLU {
+        fn apply(&self, r: &[f64], z: &mut [f64]) {
+            let lu = &self.lu;
+            let n = lu.rows;
+
+            for i in 0..n {
+                let mut sum = r[i];
+
+                for k in lu.indptr[i]..self.diagonal[i] {
+                    sum -= lu.values[k] * z[lu.indices[k]];
+                }
+
+                z[i] = sum;
+            }
+
+            for i in (0..n).rev() {
+                let mut sum = z[i];
+
+                for k in self.diagonal[i] + 1..lu.indptr[i + 1] {
+                    sum -= lu.values[k] * z[lu.indices[k]];
+                }
+
+                z[i] = sum / lu.values[self.diagonal[i]];
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct SolverOptions {
+        // Stop once ||b - Ax|| <= tolerance * ||b||
+        pub tolerance: f64,
+        pub max_iterations: usize,
+        // Krylov subspace size between GMRES restarts
+        pub restart: usize,
+    }
+
+    impl Default for SolverOptions {
+        fn default() -> SolverOptions {
+            SolverOptions {
+                tolerance: 1e-10,
+                max_iterations: 1000,
+                restart: 30,
+            }
+        }
+    }
+
+    // Running out of iterations is not an error, `converged` is false and
+    // the last iterate is returned
+    #[derive(Debug, Clone)]
+    pub struct SolverResult {
+        pub solution: Vector,
+        pub iterations: usize,
+        pub converged: bool,
+        // Residual norm before the first iteration and after every iteration
+        pub residual_history: Vec<f64>,
+    }
+
+    fn check_square(rows: usize, cols: usize) -> Result<(), LinAlgError> {
+        if rows != cols {
+            return Err(LinAlgError::NotSquare { rows, cols });
+        }
+
+        Ok(())
+    }
+
+    // Checks the shapes and returns the starting guess
+    fn initial_guess<A: LinearOperator + ?Sized>(
+        a: &A,
+        b: &Vector,
+        x0: Option<&Vector>,
+    ) -> Result<Vec<f64>, LinAlgError> {
+        let (rows, cols) = a.shape();
+        check_square(rows, cols)?;
+
+        for v in std::iter::once(b).chain(x0) {
+            if v.size != rows {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![rows, cols],
+                    right: vec![v.size],
+                });
+            }
+        }
+
+        Ok(x0.map_or(vec![0.0; rows], |x| x.data.clone()))
+    }
+
+    fn dot(x: &[f64], y: &[f64]) -> f64 {
+        x.iter().zip(y).map(|(a, b)| a * b).sum()
+    }
+
+    fn norm(x: &[f64]) -> f64 {
+        dot(x, x).sqrt()
+    }
+
+    // y += alpha x
+    fn axpy(alpha: f64, x: &[f64], y: &mut [f64]) {
+        for (y, x) in y.iter_mut().zip(x) {
+            *y += alpha * x;
+        }
+    }
+
+    fn residual<A: LinearOperator + ?Sized>(a: &A, b: &Vector, x: &[f64]) -> Vec<f64> {
+        let mut r = vec![0.0; b.size];
+        a.apply(x, &mut r);
+
+        for i in 0..b.size {
+            r[i] = b.data[i] - r[i];
+        }
+
+        return r;
+    }
+
+    fn finish(x: Vec<f64>, iterations: usize, converged: bool, history: Vec<f64>) -> SolverResult {
+        let mut solution = Vector::new(x.len());
+        solution.data = x;
+
+        SolverResult {
+            solution,
+            iterations,
+            converged,
+            residual_history: history,
+        }
+    }
+
+    // Preconditioned conjugate gradients, for symmetric positive definite A
+    // and M. A search direction with p^T A p <= 0 shows that A is not
+    // positive definite and fails with NotPositiveDefinite.
+    pub fn cg<A, M>(
+        a: &A,
+        b: &Vector,
+        x0: Option<&Vector>,
+        preconditioner: &M,
+        options: &SolverOptions,
+    ) -> Result<SolverResult, LinAlgError>
+    where
+        A: LinearOperator + ?Sized,
+        M: Preconditioner + ?Sized,
+    {
+        let mut x = initial_guess(a, b, x0)?;
+        let n = b.size;
+        let target = options.tolerance * norm(&b.data);
+
+        let mut r = residual(a, b, &x);
+        let mut z = vec![0.0; n];
+        let mut q = vec![0.0; n];
+        let mut history = vec![norm(&r)];
+
+        if history[0] <= target {
+            return Ok(finish(x, 0, true, history));
+        }
+
+        preconditioner.apply(&r, &mut z);
+
+        let mut p = z.clone();
+        let mut rz = dot(&r, &z);
+
+        for iteration in 1..=options.max_iterations {
+            a.apply(&p, &mut q);
+
+            let curvature = dot(&p, &q);
+
+            if curvature.is_nan() || curvature <= 0.0 {
+                return Err(LinAlgError::NotPositiveDefinite { pivot: iteration });
+            }
+
+            let alpha = rz / curvature;
+
+            axpy(alpha, &p, &mut x);
+            axpy(-alpha, &q, &mut r);
+
+            let residual_norm = norm(&r);
+            history.push(residual_norm);
+
+            if residual_norm <= target {
+                return Ok(finish(x, iteration, true, history));
+            }
+
+            preconditioner.apply(&r, &mut z);
+
+            let rz_next = dot(&r, &z);
+            let beta = rz_next / rz;
+            rz = rz_next;
+
+            for i in 0..n {
+                p[i] = z[i] + beta * p[i];
+            }
+        }
+
+        Ok(finish(x, options.max_iterations, false, history))
+    }
+
+    // Preconditioned MINRES (Paige and Saunders), for symmetric, possibly
+    // indefinite A with a symmetric positive definite M. The residual
+    // history is the recurrence estimate of ||b - Ax|| in the M^-1 norm,
+    // which is the plain 2-norm without a preconditioner.
+    pub fn minres<A, M>(
+        a: &A,
+        b: &Vector,
+        x0: Option<&Vector>,
+        preconditioner: &M,
+        options: &SolverOptions,
+    ) -> Result<SolverResult, LinAlgError>
+    where
+        A: LinearOperator + ?Sized,
+        M: Preconditioner + ?Sized,
+    {
+        let mut x = initial_guess(a, b, x0)?;
+        let n = b.size;
+
+        let mut y = vec![0.0; n];
+        preconditioner.apply(&b.data, &mut y);
+        let target = options.tolerance * dot(&b.data, &y).max(0.0).sqrt();
+
+        let mut r1 = residual(a, b, &x);
+        preconditioner.apply(&r1, &mut y);
+
+        let beta1 = dot(&r1, &y);
+
+        if beta1 < 0.0 {
+            return Err(LinAlgError::NotPositiveDefinite { pivot: 0 });
+        }
+
+        let beta1 = beta1.sqrt();
+        let mut history = vec![beta1];
+
+        if beta1 <= target {
+            return Ok(finish(x, 0, true, history));
+        }
+
+        let mut r2 = r1.clone();
+        let (mut oldb, mut beta) = (0.0, beta1);
+        let (mut dbar, mut epsln, mut phibar) = (0.0, 0.0, beta1);
+        let (mut cs, mut sn) = (-1.0, 0.0);
+        let mut w = vec![0.0; n];
+        let mut w2 = vec![0.0; n];
+        let mut v = vec![0.0; n];
+
+        for iteration in 1..=options.max_iterations {
+            for i in 0..n {
+                v[i] = y[i] / beta;
+            }
+
+            a.apply(&v, &mut y);
+
+            if iteration >= 2 {
+                axpy(-beta / oldb, &r1, &mut y);
+            }
+
+            let alpha = dot(&v, &y);
+            axpy(-alpha / beta, &r2, &mut y);
+
+            std::mem::swap(&mut r1, &mut r2);
+            r2.copy_from_slice(&y);
+            preconditioner.apply(&r2, &mut y);
+
+            oldb = beta;
+            beta = dot(&r2, &y);
+
+            if beta < 0.0 {
+                return Err(LinAlgError::NotPositiveDefinite { pivot: iteration });
+            }
+
+            beta = beta.sqrt();
+
+            // Apply the previous rotation, then eliminate beta with a new one
+            let oldeps = epsln;
+            let delta = cs * dbar + sn * alpha;
+            let gbar = sn * dbar - cs * alpha;
+            epsln = sn * beta;
+            dbar = -cs * beta;
+
+            let gamma = gbar.hypot(beta).max(f64::EPSILON);
+            cs = gbar / gamma;
+            sn = beta / gamma;
+
+            let phi = cs * phibar;
+            phibar *= sn;
+
+            for i in 0..n {
+                let w1 = w2[i];
+                w2[i] = w[i];
+                w[i] = (v[i] - oldeps * w1 - delta * w2[i]) / gamma;
+            }
+
+            axpy(phi, &w, &mut x);
+            history.push(phibar);
+
+            if phibar <= target || beta == 0.0 {
+                return Ok(finish(x, iteration, true, history));
+            }
+        }
+
+        Ok(finish(x, options.max_iterations, false, history))
+    }
+
+    // Restarted GMRES(m) with right preconditioning, so the residual history
+    // is the true residual norm. Works for any nonsingular A.
+    pub fn gmres<A, M>(
+        a: &A,
+        b: &Vector,
+        x0: Option<&Vector>,
+        preconditioner: &M,
+        options: &SolverOptions,
+    ) -> Result<SolverResult, LinAlgError>
+    where
+        A: LinearOperator + ?Sized,
+        M: Preconditioner + ?Sized,
+    {
+        let mut x = initial_guess(a, b, x0)?;
+        let n = b.size;
+        let m = options.restart.max(1);
+        let target = options.tolerance * norm(&b.data);
+
+        let mut history = Vec::new();
+        let mut iterations = 0;
+        let mut z = vec![0.0; n];
+
+        loop {
+            let r = residual(a, b, &x);
+            let beta = norm(&r);
+
+            if history.is_empty() {
+                history.push(beta);
+            }
+
+            if beta <= target {
+                return Ok(finish(x, iterations, true, history));
+            }
+
+            if iterations >= options.max_iterations {
+                return Ok(finish(x, iterations, false, history));
+            }
+
+            // Arnoldi basis, Hessenberg matrix (column by column), Givens
+            // rotations and the rotated right hand side
+            let mut basis: Vec<Vec<f64>> = vec![r.iter().map(|x| x / beta).collect()];
+            let mut h: Vec<Vec<f64>> = Vec::new();
+            let mut rotations: Vec<(f64, f64)> = Vec::new();
+            let mut g = vec![beta];
+
+            while h.len() < m && iterations < options.max_iterations {
+                let j = h.len();
+                let mut w = vec![0.0; n];
+
+                preconditioner.apply(&basis[j], &mut z);
+                a.apply(&z, &mut w);
+
+                // Modified Gram-Schmidt
+                let mut column = vec![0.0; j + 2];
+
+                for i in 0..=j {
+                    column[i] = dot(&w, &basis[i]);
+                    axpy(-column[i], &basis[i], &mut w);
+                }
+
+                column[j + 1] = norm(&w);
+
+                let breakdown = column[j + 1] == 0.0;
+
+                if !breakdown {
+                    basis.push(w.iter().map(|x| x / column[j + 1]).collect());
+                }
+
+                for (i, &(c, s)) in rotations.iter().enumerate() {
+                    let (x, y) = (column[i], column[i + 1]);
+                    column[i] = c * x + s * y;
+                    column[i + 1] = -s * x + c * y;
+                }
+
+                let r = column[j].hypot(column[j + 1]);
+                let (c, s) = (column[j] / r, column[j + 1] / r);
+
+                column[j] = r;
+                column[j + 1] = 0.0;
+                g.push(-s * g[j]);
+                g[j] *= c;
+
+                rotations.push((c, s));
+                h.push(column);
+                iterations += 1;
+                history.push(g[j + 1].abs());
+
+                if g[j + 1].abs() <= target || breakdown {
+                    break;
+                }
+            }
+
+            // Solve the triangular system for the update coefficients
+            let k = h.len();
+            let mut coefficients = vec![0.0; k];
+
+            for i in (0..k).rev() {
+                let mut sum = g[i];
+
+                for j in i + 1..k {
+                    sum -= h[j][i] * coefficients[j];
+                }
+
+                coefficients[i] = sum / h[i][i];
+            }
+
+            let mut update = vec![0.0; n];
+
+            for j in 0..k {
+                axpy(coefficients[j], &basis[j], &mut update);
+            }
+
+            preconditioner.apply(&update, &mut z);
+            axpy(1.0, &z, &mut x);
+
+            if *history.last().unwrap() <= target {
+                return Ok(finish(x, iterations, true, history));
+            }
+        }
+    }
+
+    // BiCGSTAB (van der Vorst) with right preconditioning, for general
+    // nonsymmetric A. Stops without converging on a breakdown.
+    pub fn bicgstab<A, M>(
+        a: &A,
+        b: &Vector,
+        x0: Option<&Vector>,
+        preconditioner: &M,
+        options: &SolverOptions,
+    ) -> Result<SolverResult, LinAlgError>
+    where
+        A: LinearOperator + ?Sized,
+        M: Preconditioner + ?Sized,
+    {
+        let mut x = initial_guess(a, b, x0)?;
+        let n = b.size;
+        let target = options.tolerance * norm(&b.data);
+
+        let mut r = residual(a, b, &x);
+        let r_hat = r.clone();
+        let mut history = vec![norm(&r)];
+
+        if history[0] <= target {
+            return Ok(finish(x, 0, true, history));
+        }
+
+        let (mut rho, mut alpha, mut omega) = (1.0, 1.0, 1.0);
+        let mut p = vec![0.0; n];
+        let mut v = vec![0.0; n];
+        let mut p_hat = vec![0.0; n];
+        let mut s_hat = vec![0.0; n];
+        let mut s = vec![0.0; n];
+        let mut t = vec![0.0; n];
+
+        for iteration in 1..=options.max_iterations {
+            let rho_next = dot(&r_hat, &r);
+
+            if rho_next == 0.0 || omega == 0.0 {
+                return Ok(finish(x, iteration - 1, false, history));
+            }
+
+            let beta = (rho_next / rho) * (alpha / omega);
+            rho = rho_next;
+
+            for i in 0..n {
+                p[i] = r[i] + beta * (p[i] - omega * v[i]);
+            }
+
+            preconditioner.apply(&p, &mut p_hat);
+            a.apply(&p_hat, &mut v);
+
+            let r_hat_v = dot(&r_hat, &v);
+
+            if r_hat_v == 0.0 {
+                return Ok(finish(x, iteration - 1, false, history));
+            }
+
+            alpha = rho / r_hat_v;
+
+            for i in 0..n {
+                s[i] = r[i] - alpha * v[i];
+            }
+
+            if norm(&s) <= target {
+                axpy(alpha, &p_hat, &mut x);
+                history.push(norm(&s));
+
+                return Ok(finish(x, iteration, true, history));
+            }
+
+            preconditioner.apply(&s, &mut s_hat);
+            a.apply(&s_hat, &mut t);
+
+            let tt = dot(&t, &t);
+            axpy(alpha, &p_hat, &mut x);
+
+            // Omega is undefined, keep the half step and stop
+            if tt == 0.0 {
+                history.push(norm(&s));
+
+                return Ok(finish(x, iteration, false, history));
+            }
+
+            omega = dot(&t, &s) / tt;
+            axpy(omega, &s_hat, &mut x);
+
+            for i in 0..n {
+                r[i] = s[i] - omega * t[i];
+            }
+
+            let residual_norm = norm(&r);
+            history.push(residual_norm);
+
+            if residual_norm <= target {
+                return Ok(finish(x, iteration, true, history));
+            }
+        }
+
+        Ok(finish(x, options.max_iterations, false, history))
+    }
+}
@@ -1,2 +1,26 @@
+#![allow(
+    non_snake_case,
+    clippy::module_inception,
+    clippy::needless_range_loop,
+    clippy::needless_return
+)]
+
+pub mod complex;
+pub mod convolution;
+pub mod decomposition;
+pub mod eigen;
+pub mod einsum;
+pub mod fft;
+pub mod geometry;
+pub mod iterative;
 pub mod linear_algebra;
+pub mod matrix_functions;
+pub mod sparse;
+pub mod static_matrix;
+#[allow(
+    unused_comparisons,
+    clippy::absurd_extreme_comparisons,
+    clippy::manual_range_contains
+)]
 pub mod stats;
+pub mod svd;
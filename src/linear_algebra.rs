@@ -1,52 +1,310 @@
 // src/linear_algebra.rs
 
 pub mod linear_algebra {
+    use crate::svd::svd::SVD;
+    use std::cmp::Ordering;
+    use std::fmt;
+    use std::ops::{
+        Add, AddAssign, Bound, Div, Index, IndexMut, Mul, MulAssign, Neg, RangeBounds, Sub,
+        SubAssign,
+    };
+
+    // Shapes are reported as [size] for vectors, [rows, cols] for matrices
+    // and the full shape for tensors
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum LinAlgError {
+        DimensionMismatch {
+            left: Vec<usize>,
+            right: Vec<usize>,
+        },
+        NotSquare {
+            rows: usize,
+            cols: usize,
+        },
+        SingularMatrix,
+        NotPositiveDefinite {
+            pivot: usize,
+        },
+        NotSymmetric {
+            row: usize,
+            col: usize,
+        },
+        NotHermitian {
+            row: usize,
+            col: usize,
+        },
+        IndexOutOfBounds {
+            index: Vec<usize>,
+            shape: Vec<usize>,
+        },
+        NonConvergence {
+            iterations: usize,
+        },
+        // `axis` counts from the front of the broadcast result
+        BroadcastMismatch {
+            left: Vec<usize>,
+            right: Vec<usize>,
+            axis: usize,
+        },
+        InvalidSubscripts {
+            subscripts: String,
+            reason: &'static str,
+        },
+        EmptyReduction {
+            shape: Vec<usize>,
+        },
+        EigenvalueOnNegativeAxis {
+            eigenvalue: f64,
+        },
+        InvalidParameter {
+            name: &'static str,
+            reason: &'static str,
+        },
+        Overflow,
+    }
 
-    #[derive(Debug, Clone)]
-    pub struct Vector {
+    impl fmt::Display for LinAlgError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                LinAlgError::DimensionMismatch { left, right } => {
+                    write!(f, "Dimension mismatch: {:?} and {:?}!", left, right)
+                }
+                LinAlgError::NotSquare { rows, cols } => {
+                    write!(f, "Matrix must be square, got {} x {}!", rows, cols)
+                }
+                LinAlgError::SingularMatrix => write!(f, "Matrix is singular!"),
+                LinAlgError::NotSymmetric { row, col } => {
+                    write!(f, "Matrix is not symmetric at ({}, {})!", row, col)
+                }
+                LinAlgError::NotHermitian { row, col } => {
+                    write!(f, "Matrix is not Hermitian at ({}, {})!", row, col)
+                }
+                LinAlgError::NotPositiveDefinite { pivot } => write!(
+                    f,
+                    "Matrix is not positive definite (failed at pivot {})!",
+                    pivot
+                ),
+                LinAlgError::IndexOutOfBounds { index, shape } => {
+                    write!(f, "Index {:?} out of bounds for shape {:?}!", index, shape)
+                }
+                LinAlgError::NonConvergence { iterations } => {
+                    write!(f, "Failed to converge after {} iterations!", iterations)
+                }
+                LinAlgError::BroadcastMismatch { left, right, axis } => write!(
+                    f,
+                    "Shapes {:?} and {:?} cannot be broadcast together at axis {}!",
+                    left, right, axis
+                ),
+                LinAlgError::InvalidSubscripts { subscripts, reason } => {
+                    write!(f, "Invalid subscripts \"{}\": {}!", subscripts, reason)
+                }
+                LinAlgError::EmptyReduction { shape } => {
+                    write!(f, "Cannot reduce an empty lane of shape {:?}!", shape)
+                }
+                LinAlgError::EigenvalueOnNegativeAxis { eigenvalue } => write!(
+                    f,
+                    "Eigenvalue {} lies on the closed negative real axis!",
+                    eigenvalue
+                ),
+                LinAlgError::InvalidParameter { name, reason } => {
+                    write!(f, "Invalid {}: {}!", name, reason)
+                }
+                LinAlgError::Overflow => write!(f, "Result is too large to represent!"),
+            }
+        }
+    }
+
+    impl std::error::Error for LinAlgError {}
+
+    // The panicking API is a thin wrapper over the `try_` functions
+    #[track_caller]
+    pub(crate) fn unwrap_or_panic<T>(result: Result<T, LinAlgError>) -> T {
+        match result {
+            Ok(value) => value,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    // The element type of vectors, matrices and tensors. Anything that
+    // supports the four arithmetic operations can be stored, and types with
+    // no imaginary part are their own conjugate. Scalars are shared between
+    // threads by the parallel kernels.
+    pub trait Scalar:
+        Copy
+        + Send
+        + Sync
+        + fmt::Debug
+        + PartialEq
+        + Add<Output = Self>
+        + Sub<Output = Self>
+        + Mul<Output = Self>
+        + Div<Output = Self>
+    {
+        fn zero() -> Self;
+        fn one() -> Self;
+        fn conj(self) -> Self;
+    }
+
+    // Real floating point scalars, for operations that need square roots
+    // and magnitudes such as norms
+    pub trait Real: Scalar + PartialOrd + Neg<Output = Self> {
+        fn sqrt(self) -> Self;
+        fn abs(self) -> Self;
+        fn powf(self, p: Self) -> Self;
+        fn min_positive() -> Self;
+        fn from_f64(x: f64) -> Self;
+        fn to_f64(self) -> f64;
+    }
+
+    macro_rules! impl_scalar {
+        ($($t:ty),*) => {
+            $(
+                impl Scalar for $t {
+                    fn zero() -> $t {
+                        0 as $t
+                    }
+
+                    fn one() -> $t {
+                        1 as $t
+                    }
+
+                    fn conj(self) -> $t {
+                        self
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_scalar!(f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+    macro_rules! impl_real {
+        ($($t:ty),*) => {
+            $(
+                impl Real for $t {
+                    fn sqrt(self) -> $t {
+                        <$t>::sqrt(self)
+                    }
+
+                    fn abs(self) -> $t {
+                        <$t>::abs(self)
+                    }
+
+                    fn powf(self, p: $t) -> $t {
+                        <$t>::powf(self, p)
+                    }
+
+                    fn min_positive() -> $t {
+                        <$t>::MIN_POSITIVE
+                    }
+
+                    fn from_f64(x: f64) -> $t {
+                        x as $t
+                    }
+
+                    fn to_f64(self) -> f64 {
+                        self as f64
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_real!(f32, f64);
+
+    // Containers default to `f64` elements, so `Vector`, `Matrix` and
+    // `Tensor` on their own mean the `f64` versions
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Vector<T = f64> {
         pub size: usize,
-        pub data: Vec<f64>,
+        pub data: Vec<T>,
     }
 
-    #[derive(Debug, Clone)]
-    pub struct Matrix {
+    // Matrices are stored as one contiguous row-major buffer, element (i, j)
+    // lives at `data[i * cols + j]`.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Matrix<T = f64> {
         pub rows: usize,
         pub cols: usize,
-        pub data: Vec<Vec<f64>>,
+        pub data: Vec<T>,
     }
 
+    // A borrowed, strided window into a matrix buffer. Element (i, j) lives at
+    // `data[i * row_stride + j * col_stride]`, so transposes and submatrices
+    // only change the strides and the start of the slice.
+    #[derive(Debug, Clone, Copy)]
+    pub struct MatrixView<'a, T = f64> {
+        rows: usize,
+        cols: usize,
+        row_stride: usize,
+        col_stride: usize,
+        data: &'a [T],
+    }
+
+    #[derive(Debug)]
+    pub struct MatrixViewMut<'a, T = f64> {
+        rows: usize,
+        cols: usize,
+        row_stride: usize,
+        col_stride: usize,
+        data: &'a mut [T],
+    }
+
+    // An N-dimensional array over a flat buffer. Element [i_0, ..., i_n-1]
+    // lives at `data[i_0 * strides[0] + ... + i_n-1 * strides[n-1]]`. New
+    // tensors are row-major (the last axis is contiguous), but permuting axes
+    // only rearranges the strides. A tensor with an empty shape is a scalar.
+    // Outside the crate the strides and buffer are read through `strides()`
+    // and `as_slice()`, so they cannot be set out of step with each other.
     #[derive(Debug, Clone)]
-    pub struct Tensor {
-        pub rank: usize,
-        pub rows: usize,
-        pub cols: usize,
-        pub data: Vec<Matrix>,
+    pub struct Tensor<T = f64> {
+        pub shape: Vec<usize>,
+        pub(crate) strides: Vec<usize>,
+        pub(crate) data: Vec<T>,
     }
 
     /* Vector functions */
-    impl Vector {
+    impl Vector<f64> {
         pub fn new(size: usize) -> Vector {
+            Vector::zeros(size)
+        }
+    }
+
+    impl<T: Scalar> Vector<T> {
+        pub fn zeros(size: usize) -> Vector<T> {
             Vector {
                 size,
-                data: vec![0.0; size],
+                data: vec![T::zero(); size],
             }
         }
 
-        pub fn vector_set(&mut self, list: Vec<f64>) {
-            assert_eq!(
-                self.size,
-                list.len(),
-                "Vector size must be equal to list size!"
-            );
+        pub fn vector_set(&mut self, list: Vec<T>) {
+            unwrap_or_panic(self.try_vector_set(list))
+        }
+
+        pub fn try_vector_set(&mut self, list: Vec<T>) -> Result<(), LinAlgError> {
+            if self.size != list.len() {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![self.size],
+                    right: vec![list.len()],
+                });
+            }
+
             self.data = list;
+
+            Ok(())
+        }
+
+        pub fn vector_add(v: &Vector<T>, u: &Vector<T>) -> Vector<T> {
+            unwrap_or_panic(Vector::try_vector_add(v, u))
         }
 
-        pub fn vector_add(v: &Vector, u: &Vector) -> Vector {
+        pub fn try_vector_add(v: &Vector<T>, u: &Vector<T>) -> Result<Vector<T>, LinAlgError> {
             // Check so that the vectors are of the same size
             // If not, return an error
-            assert_eq!(v.size, u.size, "Vectors must be of the same size!");
+            Vector::check_same_size(v, u)?;
 
-            let mut result = Vector::new(v.size);
+            let mut result = Vector::zeros(v.size);
 
             result.data = v
                 .data
@@ -55,15 +313,19 @@ pub mod linear_algebra {
                 .map(|(&x, &y)| x + y)
                 .collect();
 
-            return result;
+            return Ok(result);
+        }
+
+        pub fn vector_sub(v: &Vector<T>, u: &Vector<T>) -> Vector<T> {
+            unwrap_or_panic(Vector::try_vector_sub(v, u))
         }
 
-        pub fn vector_sub(v: &Vector, u: &Vector) -> Vector {
+        pub fn try_vector_sub(v: &Vector<T>, u: &Vector<T>) -> Result<Vector<T>, LinAlgError> {
             // Check so that the vectors are of the same size
             // If not, return an error
-            assert_eq!(v.size, u.size, "Vectors must be of the same size!");
+            Vector::check_same_size(v, u)?;
 
-            let mut result = Vector::new(v.size);
+            let mut result = Vector::zeros(v.size);
 
             result.data = v
                 .data
@@ -72,277 +334,2621 @@ pub mod linear_algebra {
                 .map(|(&x, &y)| x - y)
                 .collect();
 
-            return result;
+            return Ok(result);
         }
 
-        pub fn vector_scale(v: &Vector, scalar: f64) -> Vector {
-            let mut result = Vector::new(v.size);
+        pub fn vector_scale(v: &Vector<T>, scalar: T) -> Vector<T> {
+            let mut result = Vector::zeros(v.size);
 
             result.data = v.data.iter().map(|&x| x * scalar).collect();
 
             return result;
         }
 
-        pub fn vector_dot(v1: &Vector, v2: &Vector) -> f64 {
+        pub fn vector_dot(v1: &Vector<T>, v2: &Vector<T>) -> T {
+            unwrap_or_panic(Vector::try_vector_dot(v1, v2))
+        }
+
+        pub fn try_vector_dot(v1: &Vector<T>, v2: &Vector<T>) -> Result<T, LinAlgError> {
             // Check for same size
             // If not, return an error
-            assert_eq!(v1.size, v2.size, "Vectors must be of the same size!");
-
-            let result: f64;
+            Vector::check_same_size(v1, v2)?;
 
-            result = v1
+            let result = v1
                 .data
                 .iter()
                 .zip(v2.data.iter())
-                .map(|(&x, &y)| x * y)
-                .sum();
+                .fold(T::zero(), |acc, (&x, &y)| acc + x * y);
 
-            return result;
+            return Ok(result);
         }
 
-        pub fn vector_norm(v: &Vector) -> f64 {
-            let result: f64;
-
-            result = v.data.iter().map(|&x| x * x).sum();
-
-            return result.sqrt();
+        // The inner product v1^H v2, conjugating the first argument
+        pub fn vector_conj_dot(v1: &Vector<T>, v2: &Vector<T>) -> T {
+            unwrap_or_panic(Vector::try_vector_conj_dot(v1, v2))
         }
 
-        pub fn vector_normalize(v: &Vector) -> Vector {
-            let mut result = Vector::new(v.size);
+        pub fn try_vector_conj_dot(v1: &Vector<T>, v2: &Vector<T>) -> Result<T, LinAlgError> {
+            Vector::check_same_size(v1, v2)?;
 
-            let norm = Vector::vector_norm(v);
+            let result = v1
+                .data
+                .iter()
+                .zip(v2.data.iter())
+                .fold(T::zero(), |acc, (&x, &y)| acc + x.conj() * y);
 
-            result.data = v.data.iter().map(|&x| x / norm).collect();
+            return Ok(result);
+        }
 
-            return result;
+        pub fn vector_cross(v1: &Vector<T>, v2: &Vector<T>) -> Vector<T> {
+            unwrap_or_panic(Vector::try_vector_cross(v1, v2))
         }
 
-        pub fn vector_cross(v1: &Vector, v2: &Vector) -> Vector {
+        pub fn try_vector_cross(v1: &Vector<T>, v2: &Vector<T>) -> Result<Vector<T>, LinAlgError> {
             // Check for same size
             // If not, return an error
-            assert_eq!(v1.size, v2.size, "Vectors must be of the same size!");
+            Vector::check_same_size(v1, v2)?;
+
+            // The cross product only exists in three dimensions
+            if v1.size != 3 {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![v1.size],
+                    right: vec![3],
+                });
+            }
 
-            let mut result = Vector::new(v1.size);
+            let mut result = Vector::zeros(v1.size);
 
             for i in 0..v1.size {
                 result.data[i] = v1.data[(i + 1) % v1.size] * v2.data[(i + 2) % v1.size]
                     - v1.data[(i + 2) % v1.size] * v2.data[(i + 1) % v1.size];
             }
 
-            return result;
+            return Ok(result);
         }
-    }
 
-    /* Matrix functions */
-    impl Matrix {
-        pub fn new(rows: usize, cols: usize) -> Matrix {
-            Matrix {
-                rows,
-                cols,
-                data: vec![vec![0.0; cols]; rows],
+        fn check_same_size(v: &Vector<T>, u: &Vector<T>) -> Result<(), LinAlgError> {
+            if v.size != u.size {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![v.size],
+                    right: vec![u.size],
+                });
             }
+
+            Ok(())
         }
+    }
 
-        pub fn matrix_set(&mut self, data: Vec<f64>) {
-            assert_eq!(
-                self.rows * self.cols,
-                data.len(),
-                "Matrix size must be equal to list size!"
-            );
+    /* Norms */
+
+    // Vector norms. P(p) is the p-norm (sum |x_i|^p)^(1/p), a norm for
+    // p >= 1 and a quasi-norm for 0 < p < 1.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum VectorNorm {
+        One,
+        Two,
+        Inf,
+        P(f64),
+    }
+
+    // Matrix norms. One and Inf are the induced norms (maximum absolute
+    // column and row sums), Max is the largest absolute entry, Nuclear the
+    // sum of the singular values and Spectral the largest one.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum MatrixNorm {
+        Frobenius,
+        One,
+        Inf,
+        Max,
+        Nuclear,
+        Spectral,
+    }
+
+    // Largest magnitude, or NaN if there is one
+    fn max_abs<T: Real>(values: &[T]) -> T {
+        values.iter().map(|x| x.abs()).fold(T::zero(), nan_max)
+    }
+
+    // The p-norm, scaled by the largest magnitude like `scaled_norm2`
+    fn scaled_p_norm<T: Real>(values: &[T], p: f64) -> T {
+        let scale = max_abs(values);
 
-            data.iter()
-                .enumerate()
-                .for_each(|(i, &x)| self.data[i / self.cols][i % self.cols] = x);
+        if scale == T::zero() || !scale.to_f64().is_finite() {
+            return scale;
         }
 
-        pub fn matrix_add(a: &Matrix, b: &Matrix) -> Matrix {
-            // Check so that `a` and `b` are of the same size
+        let p = T::from_f64(p);
+        let powers: Vec<T> = values.iter().map(|&x| (x.abs() / scale).powf(p)).collect();
 
-            assert_eq!(
-                (a.rows, a.cols),
-                (b.rows, b.cols),
-                "Matrices must be of the same size!"
-            );
+        return scale * pairwise_sum(&powers).powf(T::one() / p);
+    }
+
+    impl<T: Real> Vector<T> {
+        // Euclidean norm, safe from overflow and underflow in the squares
+        pub fn vector_norm(v: &Vector<T>) -> T {
+            scaled_norm2(&v.data)
+        }
 
-            let mut result = Matrix::new(a.rows, a.cols);
+        pub fn vector_p_norm(v: &Vector<T>, norm: VectorNorm) -> T {
+            unwrap_or_panic(Vector::try_vector_p_norm(v, norm))
+        }
 
-            result.data = a
-                .data
-                .iter()
-                .zip(b.data.iter())
-                .map(|(x, y)| x.iter().zip(y.iter()).map(|(&x, &y)| x + y).collect())
-                .collect();
+        // Fails for P(p) with p <= 0 or NaN
+        pub fn try_vector_p_norm(v: &Vector<T>, norm: VectorNorm) -> Result<T, LinAlgError> {
+            let result = match norm {
+                VectorNorm::P(p) if p.is_nan() || p <= 0.0 => {
+                    return Err(LinAlgError::InvalidParameter {
+                        name: "p",
+                        reason: "must be positive",
+                    });
+                }
+                VectorNorm::One | VectorNorm::P(1.0) => {
+                    let magnitudes: Vec<T> = v.data.iter().map(|x| x.abs()).collect();
+
+                    pairwise_sum(&magnitudes)
+                }
+                VectorNorm::Two | VectorNorm::P(2.0) => scaled_norm2(&v.data),
+                VectorNorm::Inf => max_abs(&v.data),
+                VectorNorm::P(p) if p == f64::INFINITY => max_abs(&v.data),
+                VectorNorm::P(p) => scaled_p_norm(&v.data, p),
+            };
+
+            return Ok(result);
+        }
+
+        pub fn vector_normalize(v: &Vector<T>) -> Vector<T> {
+            let mut result = Vector::zeros(v.size);
+
+            let norm = Vector::vector_norm(v);
+
+            result.data = v.data.iter().map(|&x| x / norm).collect();
 
             return result;
         }
+    }
 
-        pub fn matrix_sub(a: &Matrix, b: &Matrix) -> Matrix {
-            // Check so that `a` and `b` are of the same size
+    impl<T: Real> Matrix<T> {
+        pub fn matrix_norm(m: &Matrix<T>, norm: MatrixNorm) -> T {
+            unwrap_or_panic(Matrix::try_matrix_norm(m, norm))
+        }
 
-            assert_eq!(
-                (a.rows, a.cols),
-                (b.rows, b.cols),
-                "Matrices must be of the same size!"
-            );
+        // Only the nuclear and spectral norms can fail, when the SVD does
+        // not converge. They are computed in f64 whatever T is.
+        pub fn try_matrix_norm(m: &Matrix<T>, norm: MatrixNorm) -> Result<T, LinAlgError> {
+            let line_sums = |count: usize, line: &dyn Fn(usize) -> Vec<T>| -> T {
+                let sums: Vec<T> = (0..count)
+                    .map(|k| pairwise_sum(&line(k).iter().map(|x| x.abs()).collect::<Vec<T>>()))
+                    .collect();
+
+                max_abs(&sums)
+            };
+
+            let result = match norm {
+                MatrixNorm::Frobenius => scaled_norm2(&m.data),
+                MatrixNorm::Max => max_abs(&m.data),
+                MatrixNorm::One => line_sums(m.cols, &|j| {
+                    (0..m.rows).map(|i| m.data[i * m.cols + j]).collect()
+                }),
+                MatrixNorm::Inf => {
+                    line_sums(m.rows, &|i| m.data[i * m.cols..(i + 1) * m.cols].to_vec())
+                }
+                MatrixNorm::Nuclear | MatrixNorm::Spectral => {
+                    if m.data.is_empty() {
+                        return Ok(T::zero());
+                    }
+
+                    let converted = Matrix::from_vec(
+                        m.rows,
+                        m.cols,
+                        m.data.iter().map(|x| x.to_f64()).collect(),
+                    );
+                    let singular_values = SVD::new(&converted)?.singular_values.data;
+
+                    T::from_f64(match norm {
+                        MatrixNorm::Nuclear => pairwise_sum(&singular_values),
+                        _ => singular_values[0],
+                    })
+                }
+            };
 
-            let mut result = Matrix::new(a.rows, a.cols);
+            return Ok(result);
+        }
+    }
 
-            result.data = a
-                .data
-                .iter()
-                .zip(b.data.iter())
-                .map(|(x, y)| x.iter().zip(y.iter()).map(|(&x, &y)| x - y).collect())
-                .collect();
+    // Types with a family of norms, which `distance` measures differences in
+    pub trait Normed<T: Real>: Sized {
+        type Norm: Copy;
 
-            return result;
+        fn try_norm_with(&self, norm: Self::Norm) -> Result<T, LinAlgError>;
+        fn try_difference(a: &Self, b: &Self) -> Result<Self, LinAlgError>;
+    }
+
+    impl<T: Real> Normed<T> for Vector<T> {
+        type Norm = VectorNorm;
+
+        fn try_norm_with(&self, norm: VectorNorm) -> Result<T, LinAlgError> {
+            Vector::try_vector_p_norm(self, norm)
         }
 
-        pub fn matrix_scale(m: &Matrix, s: f64) -> Matrix {
-            let mut result = Matrix::new(m.rows, m.cols);
+        fn try_difference(a: &Vector<T>, b: &Vector<T>) -> Result<Vector<T>, LinAlgError> {
+            Vector::try_vector_sub(a, b)
+        }
+    }
 
-            result.data = m
-                .data
-                .iter()
-                .map(|x| x.iter().map(|&x| x * s).collect())
-                .collect();
+    impl<T: Real> Normed<T> for Matrix<T> {
+        type Norm = MatrixNorm;
 
-            return result;
+        fn try_norm_with(&self, norm: MatrixNorm) -> Result<T, LinAlgError> {
+            Matrix::try_matrix_norm(self, norm)
         }
 
-        pub fn matrix_mul(a: &Matrix, b: &Matrix) -> Matrix {
-            // Check so that `a` and `b` are of the same size
+        fn try_difference(a: &Matrix<T>, b: &Matrix<T>) -> Result<Matrix<T>, LinAlgError> {
+            Matrix::try_matrix_sub(a, b)
+        }
+    }
 
-            assert_eq!(
-                a.cols, b.rows,
-                "Number of columns in `a` must be equal to number of rows in `b`!"
-            );
+    // ||a - b|| in the given norm, for two vectors or two matrices
+    pub fn distance<T: Real, A: Normed<T>>(a: &A, b: &A, norm: A::Norm) -> T {
+        unwrap_or_panic(try_distance(a, b, norm))
+    }
+
+    pub fn try_distance<T: Real, A: Normed<T>>(
+        a: &A,
+        b: &A,
+        norm: A::Norm,
+    ) -> Result<T, LinAlgError> {
+        return A::try_difference(a, b)?.try_norm_with(norm);
+    }
 
-            let mut result = Matrix::new(a.rows, b.cols);
+    /* Matrix functions */
+    impl Matrix<f64> {
+        pub fn new(rows: usize, cols: usize) -> Matrix {
+            Matrix::zeros(rows, cols)
+        }
+    }
 
-            for i in 0..a.rows {
-                for j in 0..b.cols {
-                    for k in 0..a.cols {
-                        result.data[i][j] += a.data[i][k] * b.data[k][j];
-                    }
-                }
+    impl<T: Scalar> Matrix<T> {
+        pub fn zeros(rows: usize, cols: usize) -> Matrix<T> {
+            Matrix {
+                rows,
+                cols,
+                data: vec![T::zero(); rows * cols],
             }
+        }
 
-            return result;
+        pub fn from_vec(rows: usize, cols: usize, data: Vec<T>) -> Matrix<T> {
+            unwrap_or_panic(Matrix::try_from_vec(rows, cols, data))
         }
 
-        pub fn matrix_vector_mul(m: &Matrix, v: &Vector) -> Matrix {
-            assert_eq!(
-                m.cols, v.size,
-                "Number of columns in `m` must be equal to number of rows in `v`!"
-            );
+        pub fn try_from_vec(
+            rows: usize,
+            cols: usize,
+            data: Vec<T>,
+        ) -> Result<Matrix<T>, LinAlgError> {
+            if rows * cols != data.len() {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![rows, cols],
+                    right: vec![data.len()],
+                });
+            }
 
-            let mut result = Matrix::new(m.rows, 1);
+            Ok(Matrix { rows, cols, data })
+        }
 
-            for i in 0..m.rows {
-                for j in 0..m.cols {
-                    result.data[i][0] += m.data[i][j] * v.data[j];
-                }
+        pub fn matrix_set(&mut self, data: Vec<T>) {
+            unwrap_or_panic(self.try_matrix_set(data))
+        }
+
+        pub fn try_matrix_set(&mut self, data: Vec<T>) -> Result<(), LinAlgError> {
+            if self.rows * self.cols != data.len() {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![self.rows, self.cols],
+                    right: vec![data.len()],
+                });
             }
 
-            return result;
+            self.data = data;
+
+            Ok(())
         }
 
-        pub fn matrix_transpose(m: &Matrix) -> Matrix {
-            let mut result = Matrix::new(m.cols, m.rows);
+        pub fn get(&self, row: usize, col: usize) -> T {
+            unwrap_or_panic(self.try_get(row, col))
+        }
 
-            for i in 0..m.rows {
-                for j in 0..m.cols {
-                    result.data[j][i] = m.data[i][j];
-                }
-            }
+        pub fn try_get(&self, row: usize, col: usize) -> Result<T, LinAlgError> {
+            self.check_index(row, col)?;
 
-            return result;
+            Ok(self.data[row * self.cols + col])
         }
 
-        pub fn matrix_fill(&mut self, value: f64) {
-            self.data = self
-                .data
-                .iter()
-                .map(|x| x.iter().map(|_| value).collect())
-                .collect();
+        pub fn get_mut(&mut self, row: usize, col: usize) -> &mut T {
+            unwrap_or_panic(self.try_get_mut(row, col))
         }
-    }
 
-    impl Tensor {
-        pub fn tensor_new(rank: usize, rows: usize, cols: usize) -> Tensor {
-            Tensor {
-                rank,
-                rows,
-                cols,
-                data: vec![Matrix::new(rows, cols); rank],
+        pub fn try_get_mut(&mut self, row: usize, col: usize) -> Result<&mut T, LinAlgError> {
+            self.check_index(row, col)?;
+
+            Ok(&mut self.data[row * self.cols + col])
+        }
+
+        fn check_index(&self, row: usize, col: usize) -> Result<(), LinAlgError> {
+            if row >= self.rows || col >= self.cols {
+                return Err(LinAlgError::IndexOutOfBounds {
+                    index: vec![row, col],
+                    shape: vec![self.rows, self.cols],
+                });
             }
+
+            Ok(())
         }
 
-        pub fn tensor_set(&mut self, data: &Vec<Matrix>) {
-            assert_eq!(
-                self.rank,
-                data.len(),
-                "Tensor rank must be equal to list size!"
-            );
+        fn check_same_size(a: &Matrix<T>, b: &Matrix<T>) -> Result<(), LinAlgError> {
+            if (a.rows, a.cols) != (b.rows, b.cols) {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![a.rows, a.cols],
+                    right: vec![b.rows, b.cols],
+                });
+            }
 
-            self.data = data.clone();
+            Ok(())
         }
 
-        pub fn tensor_insert(&mut self, data: Matrix, index: usize) {
-            assert_eq!(
-                (self.rows, self.cols),
-                (data.rows, data.cols),
-                "Matrix size must be equal to tensor size!"
-            );
+        pub fn row_stride(&self) -> usize {
+            self.cols
+        }
 
-            self.data[index] = data;
+        pub fn col_stride(&self) -> usize {
+            1
         }
 
-        pub fn tensor_add(a: &Tensor, b: &Tensor) -> Tensor {
-            assert_eq!(
-                (a.rank, a.rows, a.cols),
-                (b.rank, b.rows, b.cols),
-                "Tensors must be of the same size!"
-            );
+        pub fn as_slice(&self) -> &[T] {
+            &self.data
+        }
+
+        pub fn as_mut_slice(&mut self) -> &mut [T] {
+            &mut self.data
+        }
+
+        pub fn view(&self) -> MatrixView<'_, T> {
+            MatrixView {
+                rows: self.rows,
+                cols: self.cols,
+                row_stride: self.cols,
+                col_stride: 1,
+                data: &self.data,
+            }
+        }
 
-            let mut result = Tensor::tensor_new(a.rank, a.rows, a.cols);
+        pub fn view_mut(&mut self) -> MatrixViewMut<'_, T> {
+            MatrixViewMut {
+                rows: self.rows,
+                cols: self.cols,
+                row_stride: self.cols,
+                col_stride: 1,
+                data: &mut self.data,
+            }
+        }
+
+        pub fn matrix_add(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+            unwrap_or_panic(Matrix::try_matrix_add(a, b))
+        }
+
+        pub fn try_matrix_add(a: &Matrix<T>, b: &Matrix<T>) -> Result<Matrix<T>, LinAlgError> {
+            // Check so that `a` and `b` are of the same size
+            Matrix::check_same_size(a, b)?;
+
+            let mut result = Matrix::zeros(a.rows, a.cols);
 
             result.data = a
                 .data
                 .iter()
                 .zip(b.data.iter())
-                .map(|(x, y)| Matrix::matrix_add(x, y))
+                .map(|(&x, &y)| x + y)
                 .collect();
 
-            return result;
+            return Ok(result);
         }
 
-        pub fn tensor_sub(a: &Tensor, b: &Tensor) -> Tensor {
-            assert_eq!(
-                (a.rank, a.rows, a.cols),
-                (b.rank, b.rows, b.cols),
-                "Tensors must be of the same size!"
-            );
+        pub fn matrix_sub(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+            unwrap_or_panic(Matrix::try_matrix_sub(a, b))
+        }
+
+        pub fn try_matrix_sub(a: &Matrix<T>, b: &Matrix<T>) -> Result<Matrix<T>, LinAlgError> {
+            // Check so that `a` and `b` are of the same size
+            Matrix::check_same_size(a, b)?;
 
-            let mut result = Tensor::tensor_new(a.rank, a.rows, a.cols);
+            let mut result = Matrix::zeros(a.rows, a.cols);
 
             result.data = a
                 .data
                 .iter()
                 .zip(b.data.iter())
-                .map(|(x, y)| Matrix::matrix_sub(x, y))
+                .map(|(&x, &y)| x - y)
                 .collect();
 
+            return Ok(result);
+        }
+
+        pub fn matrix_scale(m: &Matrix<T>, s: T) -> Matrix<T> {
+            let mut result = Matrix::zeros(m.rows, m.cols);
+
+            result.data = m.data.iter().map(|&x| x * s).collect();
+
             return result;
         }
 
-        pub fn tensor_scale(t: &Tensor, s: f64) -> Tensor {
-            let mut result = Tensor::tensor_new(t.rank, t.rows, t.cols);
+        pub fn matrix_mul(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+            unwrap_or_panic(Matrix::try_matrix_mul(a, b))
+        }
+
+        pub fn try_matrix_mul(a: &Matrix<T>, b: &Matrix<T>) -> Result<Matrix<T>, LinAlgError> {
+            // Check so that the number of columns in `a` is equal to the
+            // number of rows in `b`
+            if a.cols != b.rows {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![a.rows, a.cols],
+                    right: vec![b.rows, b.cols],
+                });
+            }
+
+            let mut result = Matrix::zeros(a.rows, b.cols);
+
+            gemm_rows(a, b, &mut result.data, 0);
+
+            return Ok(result);
+        }
+
+        // Splits the rows of the result between `threads` scoped threads, or
+        // one per available core when `threads` is 0. Every entry is summed in
+        // the same order as in `matrix_mul`, so the results are identical.
+        pub fn matrix_mul_parallel(a: &Matrix<T>, b: &Matrix<T>, threads: usize) -> Matrix<T> {
+            unwrap_or_panic(Matrix::try_matrix_mul_parallel(a, b, threads))
+        }
+
+        pub fn try_matrix_mul_parallel(
+            a: &Matrix<T>,
+            b: &Matrix<T>,
+            threads: usize,
+        ) -> Result<Matrix<T>, LinAlgError> {
+            if a.cols != b.rows {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![a.rows, a.cols],
+                    right: vec![b.rows, b.cols],
+                });
+            }
+
+            let threads = match threads {
+                0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+                n => n,
+            };
+
+            let mut result = Matrix::zeros(a.rows, b.cols);
+
+            if result.data.is_empty() {
+                return Ok(result);
+            }
+
+            let rows_per_thread = a.rows.div_ceil(threads);
+
+            std::thread::scope(|scope| {
+                for (chunk, rows) in result.data.chunks_mut(rows_per_thread * b.cols).enumerate() {
+                    scope.spawn(move || gemm_rows(a, b, rows, chunk * rows_per_thread));
+                }
+            });
+
+            return Ok(result);
+        }
+
+        pub fn matrix_vector_mul(m: &Matrix<T>, v: &Vector<T>) -> Matrix<T> {
+            unwrap_or_panic(Matrix::try_matrix_vector_mul(m, v))
+        }
+
+        pub fn try_matrix_vector_mul(
+            m: &Matrix<T>,
+            v: &Vector<T>,
+        ) -> Result<Matrix<T>, LinAlgError> {
+            if m.cols != v.size {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![m.rows, m.cols],
+                    right: vec![v.size],
+                });
+            }
+
+            let mut result = Matrix::zeros(m.rows, 1);
+
+            for i in 0..m.rows {
+                let row = &m.data[i * m.cols..(i + 1) * m.cols];
+
+                result.data[i] = row
+                    .iter()
+                    .zip(v.data.iter())
+                    .fold(T::zero(), |acc, (&x, &y)| acc + x * y);
+            }
+
+            return Ok(result);
+        }
+
+        pub fn matrix_transpose(m: &Matrix<T>) -> Matrix<T> {
+            // Materialize the zero-copy transposed view
+            return m.view().transpose().to_matrix();
+        }
 
-            result.data = t.data.iter().map(|x| Matrix::matrix_scale(x, s)).collect();
+        // A^H, the transpose with every element conjugated. The same as the
+        // transpose for real elements.
+        pub fn matrix_conjugate_transpose(m: &Matrix<T>) -> Matrix<T> {
+            let mut result = Matrix::matrix_transpose(m);
+            result.data.iter_mut().for_each(|x| *x = x.conj());
 
             return result;
         }
+
+        pub fn matrix_fill(&mut self, value: T) {
+            self.data.iter_mut().for_each(|x| *x = value);
+        }
+
+        /* Slicing and assembly */
+
+        fn check_row(&self, row: usize) -> Result<(), LinAlgError> {
+            if row >= self.rows {
+                return Err(LinAlgError::IndexOutOfBounds {
+                    index: vec![row, 0],
+                    shape: vec![self.rows, self.cols],
+                });
+            }
+
+            Ok(())
+        }
+
+        fn check_col(&self, col: usize) -> Result<(), LinAlgError> {
+            if col >= self.cols {
+                return Err(LinAlgError::IndexOutOfBounds {
+                    index: vec![0, col],
+                    shape: vec![self.rows, self.cols],
+                });
+            }
+
+            Ok(())
+        }
+
+        pub fn row(&self, index: usize) -> Vector<T> {
+            unwrap_or_panic(self.try_row(index))
+        }
+
+        pub fn try_row(&self, index: usize) -> Result<Vector<T>, LinAlgError> {
+            self.check_row(index)?;
+
+            let mut result = Vector::zeros(self.cols);
+            result
+                .data
+                .copy_from_slice(&self.data[index * self.cols..(index + 1) * self.cols]);
+
+            return Ok(result);
+        }
+
+        pub fn col(&self, index: usize) -> Vector<T> {
+            unwrap_or_panic(self.try_col(index))
+        }
+
+        pub fn try_col(&self, index: usize) -> Result<Vector<T>, LinAlgError> {
+            self.check_col(index)?;
+
+            let mut result = Vector::zeros(self.rows);
+            result.data = (0..self.rows)
+                .map(|i| self.data[i * self.cols + index])
+                .collect();
+
+            return Ok(result);
+        }
+
+        pub fn set_row(&mut self, index: usize, v: &Vector<T>) {
+            unwrap_or_panic(self.try_set_row(index, v))
+        }
+
+        pub fn try_set_row(&mut self, index: usize, v: &Vector<T>) -> Result<(), LinAlgError> {
+            self.check_row(index)?;
+            check_length(self.rows, self.cols, v.size, self.cols)?;
+
+            self.data[index * self.cols..(index + 1) * self.cols].copy_from_slice(&v.data);
+
+            Ok(())
+        }
+
+        pub fn set_col(&mut self, index: usize, v: &Vector<T>) {
+            unwrap_or_panic(self.try_set_col(index, v))
+        }
+
+        pub fn try_set_col(&mut self, index: usize, v: &Vector<T>) -> Result<(), LinAlgError> {
+            self.check_col(index)?;
+            check_length(self.rows, self.cols, v.size, self.rows)?;
+
+            for i in 0..self.rows {
+                self.data[i * self.cols + index] = v.data[i];
+            }
+
+            Ok(())
+        }
+
+        // Borrowed view of the given row and column ranges, e.g.
+        // `m.slice(1..3, ..)`
+        pub fn slice<R, C>(&self, rows: R, cols: C) -> MatrixView<'_, T>
+        where
+            R: RangeBounds<usize>,
+            C: RangeBounds<usize>,
+        {
+            unwrap_or_panic(self.try_slice(rows, cols))
+        }
+
+        pub fn try_slice<R, C>(&self, rows: R, cols: C) -> Result<MatrixView<'_, T>, LinAlgError>
+        where
+            R: RangeBounds<usize>,
+            C: RangeBounds<usize>,
+        {
+            let (row, row_end) = resolve_range(&rows, self.rows)?;
+            let (col, col_end) = resolve_range(&cols, self.cols)?;
+
+            return self
+                .view()
+                .try_submatrix(row, col, row_end - row, col_end - col);
+        }
+
+        pub fn slice_mut<R, C>(&mut self, rows: R, cols: C) -> MatrixViewMut<'_, T>
+        where
+            R: RangeBounds<usize>,
+            C: RangeBounds<usize>,
+        {
+            unwrap_or_panic(self.try_slice_mut(rows, cols))
+        }
+
+        pub fn try_slice_mut<R, C>(
+            &mut self,
+            rows: R,
+            cols: C,
+        ) -> Result<MatrixViewMut<'_, T>, LinAlgError>
+        where
+            R: RangeBounds<usize>,
+            C: RangeBounds<usize>,
+        {
+            let (row, row_end) = resolve_range(&rows, self.rows)?;
+            let (col, col_end) = resolve_range(&cols, self.cols)?;
+
+            return self
+                .view_mut()
+                .try_submatrix(row, col, row_end - row, col_end - col);
+        }
+
+        // Places the matrices side by side, they must all have as many rows
+        pub fn hstack(matrices: &[&Matrix<T>]) -> Matrix<T> {
+            unwrap_or_panic(Matrix::try_hstack(matrices))
+        }
+
+        pub fn try_hstack(matrices: &[&Matrix<T>]) -> Result<Matrix<T>, LinAlgError> {
+            let rows = matrices.first().map_or(0, |m| m.rows);
+
+            for m in matrices {
+                if m.rows != rows {
+                    return Err(LinAlgError::DimensionMismatch {
+                        left: vec![matrices[0].rows, matrices[0].cols],
+                        right: vec![m.rows, m.cols],
+                    });
+                }
+            }
+
+            let cols = matrices.iter().map(|m| m.cols).sum();
+            let mut result = Matrix::zeros(rows, cols);
+            let mut offset = 0;
+
+            for m in matrices {
+                result
+                    .view_mut()
+                    .submatrix(0, offset, m.rows, m.cols)
+                    .copy_from(&m.view());
+                offset += m.cols;
+            }
+
+            return Ok(result);
+        }
+
+        // Stacks the matrices on top of each other, they must all have as
+        // many columns
+        pub fn vstack(matrices: &[&Matrix<T>]) -> Matrix<T> {
+            unwrap_or_panic(Matrix::try_vstack(matrices))
+        }
+
+        pub fn try_vstack(matrices: &[&Matrix<T>]) -> Result<Matrix<T>, LinAlgError> {
+            let cols = matrices.first().map_or(0, |m| m.cols);
+
+            for m in matrices {
+                if m.cols != cols {
+                    return Err(LinAlgError::DimensionMismatch {
+                        left: vec![matrices[0].rows, matrices[0].cols],
+                        right: vec![m.rows, m.cols],
+                    });
+                }
+            }
+
+            let rows = matrices.iter().map(|m| m.rows).sum();
+            let mut result = Matrix::zeros(rows, cols);
+            let mut offset = 0;
+
+            // Rows are contiguous, so the buffers can be copied one by one
+            for m in matrices {
+                result.data[offset..offset + m.data.len()].copy_from_slice(&m.data);
+                offset += m.data.len();
+            }
+
+            return Ok(result);
+        }
+
+        // Places the matrices along the diagonal, zero everywhere else
+        pub fn block_diag(matrices: &[&Matrix<T>]) -> Matrix<T> {
+            let rows = matrices.iter().map(|m| m.rows).sum();
+            let cols = matrices.iter().map(|m| m.cols).sum();
+            let mut result = Matrix::zeros(rows, cols);
+            let (mut row, mut col) = (0, 0);
+
+            for m in matrices {
+                result
+                    .view_mut()
+                    .submatrix(row, col, m.rows, m.cols)
+                    .copy_from(&m.view());
+                row += m.rows;
+                col += m.cols;
+            }
+
+            return result;
+        }
+
+        pub fn swap_rows(&mut self, a: usize, b: usize) {
+            unwrap_or_panic(self.try_swap_rows(a, b))
+        }
+
+        pub fn try_swap_rows(&mut self, a: usize, b: usize) -> Result<(), LinAlgError> {
+            self.check_row(a.max(b))?;
+
+            for j in 0..self.cols {
+                self.data.swap(a * self.cols + j, b * self.cols + j);
+            }
+
+            Ok(())
+        }
+
+        pub fn swap_cols(&mut self, a: usize, b: usize) {
+            unwrap_or_panic(self.try_swap_cols(a, b))
+        }
+
+        pub fn try_swap_cols(&mut self, a: usize, b: usize) -> Result<(), LinAlgError> {
+            self.check_col(a.max(b))?;
+
+            for i in 0..self.rows {
+                self.data.swap(i * self.cols + a, i * self.cols + b);
+            }
+
+            Ok(())
+        }
+
+        // New matrix made of the listed rows in order, repeats allowed
+        pub fn select_rows(&self, indices: &[usize]) -> Matrix<T> {
+            unwrap_or_panic(self.try_select_rows(indices))
+        }
+
+        pub fn try_select_rows(&self, indices: &[usize]) -> Result<Matrix<T>, LinAlgError> {
+            let mut result = Matrix::zeros(indices.len(), self.cols);
+
+            for (k, &i) in indices.iter().enumerate() {
+                self.check_row(i)?;
+
+                result.data[k * self.cols..(k + 1) * self.cols]
+                    .copy_from_slice(&self.data[i * self.cols..(i + 1) * self.cols]);
+            }
+
+            return Ok(result);
+        }
+
+        // New matrix made of the listed columns in order, repeats allowed
+        pub fn select_cols(&self, indices: &[usize]) -> Matrix<T> {
+            unwrap_or_panic(self.try_select_cols(indices))
+        }
+
+        pub fn try_select_cols(&self, indices: &[usize]) -> Result<Matrix<T>, LinAlgError> {
+            let cols = indices.len();
+            let mut result = Matrix::zeros(self.rows, cols);
+
+            for (k, &j) in indices.iter().enumerate() {
+                self.check_col(j)?;
+
+                for i in 0..self.rows {
+                    result.data[i * cols + k] = self.data[i * self.cols + j];
+                }
+            }
+
+            return Ok(result);
+        }
+    }
+
+    // Check so that a vector has the length a row or column needs
+    fn check_length(
+        rows: usize,
+        cols: usize,
+        size: usize,
+        expected: usize,
+    ) -> Result<(), LinAlgError> {
+        if size != expected {
+            return Err(LinAlgError::DimensionMismatch {
+                left: vec![rows, cols],
+                right: vec![size],
+            });
+        }
+
+        Ok(())
+    }
+
+    // Turns a range into start and end indices inside 0..len
+    fn resolve_range<R: RangeBounds<usize>>(
+        range: &R,
+        len: usize,
+    ) -> Result<(usize, usize), LinAlgError> {
+        // An exclusive start or inclusive end of usize::MAX has no successor
+        let successor = |x: usize| {
+            x.checked_add(1).ok_or(LinAlgError::IndexOutOfBounds {
+                index: vec![x],
+                shape: vec![len],
+            })
+        };
+
+        let start = match range.start_bound() {
+            Bound::Included(&x) => x,
+            Bound::Excluded(&x) => successor(x)?,
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(&x) => successor(x)?,
+            Bound::Excluded(&x) => x,
+            Bound::Unbounded => len,
+        };
+
+        if start > end || end > len {
+            return Err(LinAlgError::IndexOutOfBounds {
+                index: vec![start, end],
+                shape: vec![len],
+            });
+        }
+
+        Ok((start, end))
+    }
+
+    // Tile sizes for the GEMM kernel, chosen so a GEMM_BLOCK_K x GEMM_BLOCK_N
+    // panel of `b` (256 KiB of f64) stays in L2 while it is reused for every
+    // row of `a`
+    const GEMM_BLOCK_K: usize = 128;
+    const GEMM_BLOCK_N: usize = 256;
+
+    // Accumulates rows `first_row..` of a * b into `c`, which holds whole
+    // rows of the (zeroed) result. The i-k-j loop order walks `b` and `c`
+    // along rows, and each entry still sums over k in increasing order.
+    fn gemm_rows<T: Scalar>(a: &Matrix<T>, b: &Matrix<T>, c: &mut [T], first_row: usize) {
+        let (inner, n) = (a.cols, b.cols);
+        let rows = c.len() / n.max(1);
+
+        for kk in (0..inner).step_by(GEMM_BLOCK_K) {
+            let k_end = (kk + GEMM_BLOCK_K).min(inner);
+
+            for jj in (0..n).step_by(GEMM_BLOCK_N) {
+                let j_end = (jj + GEMM_BLOCK_N).min(n);
+
+                for i in 0..rows {
+                    let a_row = &a.data[(first_row + i) * inner..(first_row + i + 1) * inner];
+                    let c_row = &mut c[i * n + jj..i * n + j_end];
+
+                    for k in kk..k_end {
+                        let a_ik = a_row[k];
+                        let b_row = &b.data[k * n + jj..k * n + j_end];
+
+                        for (c_ij, &b_kj) in c_row.iter_mut().zip(b_row) {
+                            *c_ij = *c_ij + a_ik * b_kj;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /* Matrix view functions */
+
+    // Check so that the last element of a strided view is inside the buffer
+    fn check_view_bounds(
+        len: usize,
+        rows: usize,
+        cols: usize,
+        row_stride: usize,
+        col_stride: usize,
+    ) -> Result<(), LinAlgError> {
+        if rows > 0 && cols > 0 && (rows - 1) * row_stride + (cols - 1) * col_stride >= len {
+            return Err(LinAlgError::IndexOutOfBounds {
+                index: vec![rows - 1, cols - 1],
+                shape: vec![len],
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_view_index(
+        rows: usize,
+        cols: usize,
+        row: usize,
+        col: usize,
+    ) -> Result<(), LinAlgError> {
+        if row >= rows || col >= cols {
+            return Err(LinAlgError::IndexOutOfBounds {
+                index: vec![row, col],
+                shape: vec![rows, cols],
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_submatrix(
+        rows: usize,
+        cols: usize,
+        row: usize,
+        col: usize,
+        sub_rows: usize,
+        sub_cols: usize,
+    ) -> Result<(), LinAlgError> {
+        if row + sub_rows > rows || col + sub_cols > cols {
+            return Err(LinAlgError::IndexOutOfBounds {
+                index: vec![row + sub_rows, col + sub_cols],
+                shape: vec![rows, cols],
+            });
+        }
+
+        Ok(())
+    }
+
+    impl<'a, T: Scalar> MatrixView<'a, T> {
+        pub fn new(
+            data: &'a [T],
+            rows: usize,
+            cols: usize,
+            row_stride: usize,
+            col_stride: usize,
+        ) -> MatrixView<'a, T> {
+            unwrap_or_panic(MatrixView::try_new(
+                data, rows, cols, row_stride, col_stride,
+            ))
+        }
+
+        pub fn try_new(
+            data: &'a [T],
+            rows: usize,
+            cols: usize,
+            row_stride: usize,
+            col_stride: usize,
+        ) -> Result<MatrixView<'a, T>, LinAlgError> {
+            check_view_bounds(data.len(), rows, cols, row_stride, col_stride)?;
+
+            Ok(MatrixView {
+                rows,
+                cols,
+                row_stride,
+                col_stride,
+                data,
+            })
+        }
+
+        pub fn rows(&self) -> usize {
+            self.rows
+        }
+
+        pub fn cols(&self) -> usize {
+            self.cols
+        }
+
+        pub fn row_stride(&self) -> usize {
+            self.row_stride
+        }
+
+        pub fn col_stride(&self) -> usize {
+            self.col_stride
+        }
+
+        pub fn get(&self, row: usize, col: usize) -> T {
+            unwrap_or_panic(self.try_get(row, col))
+        }
+
+        pub fn try_get(&self, row: usize, col: usize) -> Result<T, LinAlgError> {
+            check_view_index(self.rows, self.cols, row, col)?;
+
+            Ok(self.data[row * self.row_stride + col * self.col_stride])
+        }
+
+        pub fn transpose(self) -> MatrixView<'a, T> {
+            MatrixView {
+                rows: self.cols,
+                cols: self.rows,
+                row_stride: self.col_stride,
+                col_stride: self.row_stride,
+                data: self.data,
+            }
+        }
+
+        pub fn submatrix(
+            self,
+            row: usize,
+            col: usize,
+            rows: usize,
+            cols: usize,
+        ) -> MatrixView<'a, T> {
+            unwrap_or_panic(self.try_submatrix(row, col, rows, cols))
+        }
+
+        pub fn try_submatrix(
+            self,
+            row: usize,
+            col: usize,
+            rows: usize,
+            cols: usize,
+        ) -> Result<MatrixView<'a, T>, LinAlgError> {
+            check_submatrix(self.rows, self.cols, row, col, rows, cols)?;
+
+            // An empty view does not need to point anywhere in particular
+            if rows == 0 || cols == 0 {
+                return Ok(MatrixView {
+                    rows,
+                    cols,
+                    row_stride: self.row_stride,
+                    col_stride: self.col_stride,
+                    data: &[],
+                });
+            }
+
+            let offset = row * self.row_stride + col * self.col_stride;
+
+            Ok(MatrixView {
+                rows,
+                cols,
+                row_stride: self.row_stride,
+                col_stride: self.col_stride,
+                data: &self.data[offset..],
+            })
+        }
+
+        pub fn is_contiguous(&self) -> bool {
+            self.col_stride == 1 && (self.row_stride == self.cols || self.rows <= 1)
+        }
+
+        // The view as one row-major slice, if it is laid out that way
+        pub fn as_slice(&self) -> Option<&'a [T]> {
+            if !self.is_contiguous() {
+                return None;
+            }
+
+            Some(&self.data[..self.rows * self.cols])
+        }
+
+        // Elements in row-major order
+        pub fn iter(&self) -> impl Iterator<Item = T> + 'a {
+            let view = *self;
+
+            (0..view.rows).flat_map(move |i| {
+                (0..view.cols).map(move |j| view.data[i * view.row_stride + j * view.col_stride])
+            })
+        }
+
+        pub fn to_matrix(&self) -> Matrix<T> {
+            if let Some(slice) = self.as_slice() {
+                return Matrix::from_vec(self.rows, self.cols, slice.to_vec());
+            }
+
+            Matrix::from_vec(self.rows, self.cols, self.iter().collect())
+        }
+    }
+
+    impl<'a, T: Scalar> MatrixViewMut<'a, T> {
+        pub fn new(
+            data: &'a mut [T],
+            rows: usize,
+            cols: usize,
+            row_stride: usize,
+            col_stride: usize,
+        ) -> MatrixViewMut<'a, T> {
+            unwrap_or_panic(MatrixViewMut::try_new(
+                data, rows, cols, row_stride, col_stride,
+            ))
+        }
+
+        pub fn try_new(
+            data: &'a mut [T],
+            rows: usize,
+            cols: usize,
+            row_stride: usize,
+            col_stride: usize,
+        ) -> Result<MatrixViewMut<'a, T>, LinAlgError> {
+            check_view_bounds(data.len(), rows, cols, row_stride, col_stride)?;
+
+            Ok(MatrixViewMut {
+                rows,
+                cols,
+                row_stride,
+                col_stride,
+                data,
+            })
+        }
+
+        pub fn rows(&self) -> usize {
+            self.rows
+        }
+
+        pub fn cols(&self) -> usize {
+            self.cols
+        }
+
+        pub fn row_stride(&self) -> usize {
+            self.row_stride
+        }
+
+        pub fn col_stride(&self) -> usize {
+            self.col_stride
+        }
+
+        pub fn get(&self, row: usize, col: usize) -> T {
+            unwrap_or_panic(self.try_get(row, col))
+        }
+
+        pub fn try_get(&self, row: usize, col: usize) -> Result<T, LinAlgError> {
+            check_view_index(self.rows, self.cols, row, col)?;
+
+            Ok(self.data[row * self.row_stride + col * self.col_stride])
+        }
+
+        pub fn get_mut(&mut self, row: usize, col: usize) -> &mut T {
+            unwrap_or_panic(self.try_get_mut(row, col))
+        }
+
+        pub fn try_get_mut(&mut self, row: usize, col: usize) -> Result<&mut T, LinAlgError> {
+            check_view_index(self.rows, self.cols, row, col)?;
+
+            Ok(&mut self.data[row * self.row_stride + col * self.col_stride])
+        }
+
+        pub fn as_view(&self) -> MatrixView<'_, T> {
+            MatrixView {
+                rows: self.rows,
+                cols: self.cols,
+                row_stride: self.row_stride,
+                col_stride: self.col_stride,
+                data: self.data,
+            }
+        }
+
+        pub fn transpose(self) -> MatrixViewMut<'a, T> {
+            MatrixViewMut {
+                rows: self.cols,
+                cols: self.rows,
+                row_stride: self.col_stride,
+                col_stride: self.row_stride,
+                data: self.data,
+            }
+        }
+
+        pub fn submatrix(
+            self,
+            row: usize,
+            col: usize,
+            rows: usize,
+            cols: usize,
+        ) -> MatrixViewMut<'a, T> {
+            unwrap_or_panic(self.try_submatrix(row, col, rows, cols))
+        }
+
+        pub fn try_submatrix(
+            self,
+            row: usize,
+            col: usize,
+            rows: usize,
+            cols: usize,
+        ) -> Result<MatrixViewMut<'a, T>, LinAlgError> {
+            check_submatrix(self.rows, self.cols, row, col, rows, cols)?;
+
+            if rows == 0 || cols == 0 {
+                return Ok(MatrixViewMut {
+                    rows,
+                    cols,
+                    row_stride: self.row_stride,
+                    col_stride: self.col_stride,
+                    data: &mut [],
+                });
+            }
+
+            let offset = row * self.row_stride + col * self.col_stride;
+
+            Ok(MatrixViewMut {
+                rows,
+                cols,
+                row_stride: self.row_stride,
+                col_stride: self.col_stride,
+                data: &mut self.data[offset..],
+            })
+        }
+
+        pub fn fill(&mut self, value: T) {
+            for i in 0..self.rows {
+                for j in 0..self.cols {
+                    *self.get_mut(i, j) = value;
+                }
+            }
+        }
+
+        pub fn copy_from(&mut self, other: &MatrixView<T>) {
+            unwrap_or_panic(self.try_copy_from(other))
+        }
+
+        pub fn try_copy_from(&mut self, other: &MatrixView<T>) -> Result<(), LinAlgError> {
+            if (self.rows, self.cols) != (other.rows, other.cols) {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![self.rows, self.cols],
+                    right: vec![other.rows, other.cols],
+                });
+            }
+
+            for i in 0..self.rows {
+                for j in 0..self.cols {
+                    *self.get_mut(i, j) = other.get(i, j);
+                }
+            }
+
+            Ok(())
+        }
+
+        pub fn to_matrix(&self) -> Matrix<T> {
+            self.as_view().to_matrix()
+        }
+    }
+
+    impl Tensor<f64> {
+        pub fn tensor_new(shape: &[usize]) -> Tensor {
+            Tensor::zeros(shape)
+        }
+    }
+
+    // Row-major strides for a shape
+    fn contiguous_strides(shape: &[usize]) -> Vec<usize> {
+        let mut strides = vec![1; shape.len()];
+
+        for k in (0..shape.len().saturating_sub(1)).rev() {
+            strides[k] = strides[k + 1] * shape[k + 1];
+        }
+
+        return strides;
+    }
+
+    impl<T: Scalar> Tensor<T> {
+        pub fn zeros(shape: &[usize]) -> Tensor<T> {
+            Tensor {
+                shape: shape.to_vec(),
+                strides: contiguous_strides(shape),
+                data: vec![T::zero(); shape.iter().product()],
+            }
+        }
+
+        pub fn from_vec(shape: &[usize], data: Vec<T>) -> Tensor<T> {
+            unwrap_or_panic(Tensor::try_from_vec(shape, data))
+        }
+
+        pub fn try_from_vec(shape: &[usize], data: Vec<T>) -> Result<Tensor<T>, LinAlgError> {
+            if shape.iter().product::<usize>() != data.len() {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: shape.to_vec(),
+                    right: vec![data.len()],
+                });
+            }
+
+            Ok(Tensor {
+                shape: shape.to_vec(),
+                strides: contiguous_strides(shape),
+                data,
+            })
+        }
+
+        // A stack of equally sized matrices as a [count, rows, cols] tensor
+        pub fn from_matrices(matrices: &[Matrix<T>]) -> Tensor<T> {
+            unwrap_or_panic(Tensor::try_from_matrices(matrices))
+        }
+
+        pub fn try_from_matrices(matrices: &[Matrix<T>]) -> Result<Tensor<T>, LinAlgError> {
+            let (rows, cols) = matrices.first().map_or((0, 0), |m| (m.rows, m.cols));
+            let mut result = Tensor::zeros(&[matrices.len(), rows, cols]);
+
+            result.try_tensor_set(matrices)?;
+
+            return Ok(result);
+        }
+
+        pub fn ndim(&self) -> usize {
+            self.shape.len()
+        }
+
+        // Number of elements
+        pub fn size(&self) -> usize {
+            self.shape.iter().product()
+        }
+
+        pub fn is_contiguous(&self) -> bool {
+            self.strides == contiguous_strides(&self.shape)
+        }
+
+        pub fn strides(&self) -> &[usize] {
+            &self.strides
+        }
+
+        // The underlying buffer, which is in row-major order only when the
+        // tensor `is_contiguous`
+        pub fn as_slice(&self) -> &[T] {
+            &self.data
+        }
+
+        fn check_element(&self, index: &[usize]) -> Result<(), LinAlgError> {
+            if index.len() != self.ndim() || index.iter().zip(&self.shape).any(|(i, n)| i >= n) {
+                return Err(LinAlgError::IndexOutOfBounds {
+                    index: index.to_vec(),
+                    shape: self.shape.clone(),
+                });
+            }
+
+            Ok(())
+        }
+
+        fn offset(&self, index: &[usize]) -> usize {
+            index.iter().zip(&self.strides).map(|(i, s)| i * s).sum()
+        }
+
+        pub fn get(&self, index: &[usize]) -> T {
+            unwrap_or_panic(self.try_get(index))
+        }
+
+        pub fn try_get(&self, index: &[usize]) -> Result<T, LinAlgError> {
+            self.check_element(index)?;
+
+            Ok(self.data[self.offset(index)])
+        }
+
+        pub fn get_mut(&mut self, index: &[usize]) -> &mut T {
+            unwrap_or_panic(self.check_element(index));
+
+            let offset = self.offset(index);
+
+            &mut self.data[offset]
+        }
+
+        // Elements in row-major order of the logical shape, whatever the
+        // strides are
+        pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+            let mut index = vec![0; self.ndim()];
+            let mut remaining = self.size();
+
+            std::iter::from_fn(move || {
+                if remaining == 0 {
+                    return None;
+                }
+
+                let value = self.data[self.offset(&index)];
+                remaining -= 1;
+
+                // Odometer increment, the last axis moving fastest
+                for k in (0..index.len()).rev() {
+                    index[k] += 1;
+
+                    if index[k] < self.shape[k] {
+                        break;
+                    }
+
+                    index[k] = 0;
+                }
+
+                Some(value)
+            })
+        }
+
+        // The same tensor with a row-major buffer
+        pub fn to_contiguous(&self) -> Tensor<T> {
+            if self.is_contiguous() {
+                return self.clone();
+            }
+
+            Tensor {
+                shape: self.shape.clone(),
+                strides: contiguous_strides(&self.shape),
+                data: self.iter().collect(),
+            }
+        }
+
+        pub fn reshape(&self, shape: &[usize]) -> Tensor<T> {
+            unwrap_or_panic(self.try_reshape(shape))
+        }
+
+        // Reinterprets the elements, in row-major order, with a new shape of
+        // the same size
+        pub fn try_reshape(&self, shape: &[usize]) -> Result<Tensor<T>, LinAlgError> {
+            if shape.iter().product::<usize>() != self.size() {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: self.shape.clone(),
+                    right: shape.to_vec(),
+                });
+            }
+
+            let mut result = self.to_contiguous();
+            result.shape = shape.to_vec();
+            result.strides = contiguous_strides(shape);
+
+            return Ok(result);
+        }
+
+        pub fn permute(self, axes: &[usize]) -> Tensor<T> {
+            unwrap_or_panic(self.try_permute(axes))
+        }
+
+        // Reorders the axes, so axis k of the result is axis `axes[k]` of
+        // `self`. Only the shape and strides change, the buffer is kept.
+        pub fn try_permute(self, axes: &[usize]) -> Result<Tensor<T>, LinAlgError> {
+            let mut seen = vec![false; self.ndim()];
+
+            for &axis in axes {
+                if axis >= self.ndim() || seen[axis] {
+                    return Err(LinAlgError::IndexOutOfBounds {
+                        index: axes.to_vec(),
+                        shape: self.shape.clone(),
+                    });
+                }
+
+                seen[axis] = true;
+            }
+
+            if axes.len() != self.ndim() {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: self.shape.clone(),
+                    right: axes.to_vec(),
+                });
+            }
+
+            Ok(Tensor {
+                shape: axes.iter().map(|&k| self.shape[k]).collect(),
+                strides: axes.iter().map(|&k| self.strides[k]).collect(),
+                data: self.data,
+            })
+        }
+
+        pub fn to_vector(&self) -> Vector<T> {
+            unwrap_or_panic(self.try_to_vector())
+        }
+
+        pub fn try_to_vector(&self) -> Result<Vector<T>, LinAlgError> {
+            if self.ndim() != 1 {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: self.shape.clone(),
+                    right: vec![self.size()],
+                });
+            }
+
+            let mut result = Vector::zeros(self.shape[0]);
+            result.data = self.iter().collect();
+
+            return Ok(result);
+        }
+
+        pub fn to_matrix(&self) -> Matrix<T> {
+            unwrap_or_panic(self.try_to_matrix())
+        }
+
+        pub fn try_to_matrix(&self) -> Result<Matrix<T>, LinAlgError> {
+            if self.ndim() != 2 {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: self.shape.clone(),
+                    right: vec![self.size()],
+                });
+            }
+
+            Ok(Matrix::from_vec(
+                self.shape[0],
+                self.shape[1],
+                self.iter().collect(),
+            ))
+        }
+
+        /* Matrices of a tensor */
+
+        // Tensors with at least two axes are treated as a row-major list of
+        // matrices over the last two axes. A [batch, channels, rows, cols]
+        // tensor holds batch * channels matrices.
+        pub fn matrix_count(&self) -> usize {
+            if self.ndim() < 2 {
+                return 0;
+            }
+
+            self.shape[..self.ndim() - 2].iter().product()
+        }
+
+        fn matrix_shape(&self) -> (usize, usize) {
+            (self.shape[self.ndim() - 2], self.shape[self.ndim() - 1])
+        }
+
+        fn check_index(&self, index: usize) -> Result<(), LinAlgError> {
+            if index >= self.matrix_count() {
+                return Err(LinAlgError::IndexOutOfBounds {
+                    index: vec![index],
+                    shape: self.shape.clone(),
+                });
+            }
+
+            Ok(())
+        }
+
+        // Offset of the first element of matrix `index`
+        fn matrix_offset(&self, index: usize) -> usize {
+            let leading = self.ndim() - 2;
+            let mut remainder = index;
+            let mut offset = 0;
+
+            for k in (0..leading).rev() {
+                offset += (remainder % self.shape[k]) * self.strides[k];
+                remainder /= self.shape[k];
+            }
+
+            return offset;
+        }
+
+        fn check_matrix_size(&self, data: &Matrix<T>) -> Result<(), LinAlgError> {
+            if self.ndim() < 2 || self.matrix_shape() != (data.rows, data.cols) {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: self.shape.clone(),
+                    right: vec![data.rows, data.cols],
+                });
+            }
+
+            Ok(())
+        }
+
+        fn check_same_size(a: &Tensor<T>, b: &Tensor<T>) -> Result<(), LinAlgError> {
+            if a.shape != b.shape {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: a.shape.clone(),
+                    right: b.shape.clone(),
+                });
+            }
+
+            Ok(())
+        }
+
+        pub fn tensor_set(&mut self, data: &[Matrix<T>]) {
+            unwrap_or_panic(self.try_tensor_set(data))
+        }
+
+        pub fn try_tensor_set(&mut self, data: &[Matrix<T>]) -> Result<(), LinAlgError> {
+            if self.matrix_count() != data.len() {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: self.shape.clone(),
+                    right: vec![data.len()],
+                });
+            }
+
+            // Validate every matrix before writing any of them
+            for matrix in data.iter() {
+                self.check_matrix_size(matrix)?;
+            }
+
+            for (index, matrix) in data.iter().enumerate() {
+                self.try_tensor_insert(matrix.clone(), index)?;
+            }
+
+            Ok(())
+        }
+
+        pub fn tensor_insert(&mut self, data: Matrix<T>, index: usize) {
+            unwrap_or_panic(self.try_tensor_insert(data, index))
+        }
+
+        pub fn try_tensor_insert(
+            &mut self,
+            data: Matrix<T>,
+            index: usize,
+        ) -> Result<(), LinAlgError> {
+            self.check_matrix_size(&data)?;
+
+            self.try_tensor_view_mut(index)?.copy_from(&data.view());
+
+            Ok(())
+        }
+
+        // A view of the matrix at `index`, without copying
+        pub fn tensor_view(&self, index: usize) -> MatrixView<'_, T> {
+            unwrap_or_panic(self.try_tensor_view(index))
+        }
+
+        pub fn try_tensor_view(&self, index: usize) -> Result<MatrixView<'_, T>, LinAlgError> {
+            self.check_index(index)?;
+
+            let (rows, cols) = self.matrix_shape();
+            let n = self.ndim();
+
+            MatrixView::try_new(
+                &self.data[self.matrix_offset(index)..],
+                rows,
+                cols,
+                self.strides[n - 2],
+                self.strides[n - 1],
+            )
+        }
+
+        pub fn tensor_view_mut(&mut self, index: usize) -> MatrixViewMut<'_, T> {
+            unwrap_or_panic(self.try_tensor_view_mut(index))
+        }
+
+        pub fn try_tensor_view_mut(
+            &mut self,
+            index: usize,
+        ) -> Result<MatrixViewMut<'_, T>, LinAlgError> {
+            self.check_index(index)?;
+
+            let (rows, cols) = self.matrix_shape();
+            let n = self.ndim();
+            let (row_stride, col_stride) = (self.strides[n - 2], self.strides[n - 1]);
+            let offset = self.matrix_offset(index);
+
+            MatrixViewMut::try_new(&mut self.data[offset..], rows, cols, row_stride, col_stride)
+        }
+
+        // Applies `f` elementwise. Operands with the same layout are zipped
+        // buffer to buffer, anything else produces a row-major result.
+        fn zip_map<F: Fn(T, T) -> T>(a: &Tensor<T>, b: &Tensor<T>, f: F) -> Tensor<T> {
+            if a.strides == b.strides {
+                return Tensor {
+                    shape: a.shape.clone(),
+                    strides: a.strides.clone(),
+                    data: a.data.iter().zip(&b.data).map(|(&x, &y)| f(x, y)).collect(),
+                };
+            }
+
+            Tensor {
+                shape: a.shape.clone(),
+                strides: contiguous_strides(&a.shape),
+                data: a.iter().zip(b.iter()).map(|(x, y)| f(x, y)).collect(),
+            }
+        }
+
+        pub fn tensor_add(a: &Tensor<T>, b: &Tensor<T>) -> Tensor<T> {
+            unwrap_or_panic(Tensor::try_tensor_add(a, b))
+        }
+
+        pub fn try_tensor_add(a: &Tensor<T>, b: &Tensor<T>) -> Result<Tensor<T>, LinAlgError> {
+            Tensor::check_same_size(a, b)?;
+
+            return Ok(Tensor::zip_map(a, b, |x, y| x + y));
+        }
+
+        pub fn tensor_sub(a: &Tensor<T>, b: &Tensor<T>) -> Tensor<T> {
+            unwrap_or_panic(Tensor::try_tensor_sub(a, b))
+        }
+
+        pub fn try_tensor_sub(a: &Tensor<T>, b: &Tensor<T>) -> Result<Tensor<T>, LinAlgError> {
+            Tensor::check_same_size(a, b)?;
+
+            return Ok(Tensor::zip_map(a, b, |x, y| x - y));
+        }
+
+        pub fn tensor_scale(t: &Tensor<T>, s: T) -> Tensor<T> {
+            let mut result = t.clone();
+
+            result.data.iter_mut().for_each(|x| *x = *x * s);
+
+            return result;
+        }
+    }
+
+    // Tensors compare by shape and logical contents, not by memory layout
+    impl<T: Scalar> PartialEq for Tensor<T> {
+        fn eq(&self, other: &Tensor<T>) -> bool {
+            self.shape == other.shape && self.iter().eq(other.iter())
+        }
+    }
+
+    impl<T: Scalar> From<Vector<T>> for Tensor<T> {
+        fn from(v: Vector<T>) -> Tensor<T> {
+            Tensor {
+                shape: vec![v.size],
+                strides: vec![1],
+                data: v.data,
+            }
+        }
+    }
+
+    impl<T: Scalar> From<Matrix<T>> for Tensor<T> {
+        fn from(m: Matrix<T>) -> Tensor<T> {
+            Tensor {
+                shape: vec![m.rows, m.cols],
+                strides: vec![m.cols, 1],
+                data: m.data,
+            }
+        }
+    }
+
+    /* Broadcasting */
+
+    // Operands of the broadcasting functions, seen as a buffer with a shape
+    // and strides. Scalars are 0-D, vectors 1-D and matrices 2-D, so a
+    // `Vector` of length `cols` broadcasts against every row of a `Matrix`.
+    pub trait Broadcast<T: Scalar> {
+        fn broadcast_layout(&self) -> (&[T], Vec<usize>, Vec<usize>);
+    }
+
+    impl<T: Scalar> Broadcast<T> for T {
+        fn broadcast_layout(&self) -> (&[T], Vec<usize>, Vec<usize>) {
+            (std::slice::from_ref(self), vec![], vec![])
+        }
+    }
+
+    impl<T: Scalar> Broadcast<T> for Vector<T> {
+        fn broadcast_layout(&self) -> (&[T], Vec<usize>, Vec<usize>) {
+            (&self.data, vec![self.size], vec![1])
+        }
+    }
+
+    impl<T: Scalar> Broadcast<T> for Matrix<T> {
+        fn broadcast_layout(&self) -> (&[T], Vec<usize>, Vec<usize>) {
+            (&self.data, vec![self.rows, self.cols], vec![self.cols, 1])
+        }
+    }
+
+    impl<T: Scalar> Broadcast<T> for Tensor<T> {
+        fn broadcast_layout(&self) -> (&[T], Vec<usize>, Vec<usize>) {
+            (&self.data, self.shape.clone(), self.strides.clone())
+        }
+    }
+
+    // NumPy rules: shapes are aligned on their last axis, missing leading
+    // axes count as size 1, and each pair of sizes must be equal or contain
+    // a 1
+    pub fn broadcast_shapes(a: &[usize], b: &[usize]) -> Result<Vec<usize>, LinAlgError> {
+        let n = a.len().max(b.len());
+        let mut shape = vec![0; n];
+
+        for k in 0..n {
+            let x = if k + a.len() >= n {
+                a[k + a.len() - n]
+            } else {
+                1
+            };
+            let y = if k + b.len() >= n {
+                b[k + b.len() - n]
+            } else {
+                1
+            };
+
+            shape[k] = if x == y || y == 1 {
+                x
+            } else if x == 1 {
+                y
+            } else {
+                return Err(LinAlgError::BroadcastMismatch {
+                    left: a.to_vec(),
+                    right: b.to_vec(),
+                    axis: k,
+                });
+            };
+        }
+
+        Ok(shape)
+    }
+
+    // Strides that stretch an operand to `target`, 0 along broadcast axes
+    fn broadcast_strides(shape: &[usize], strides: &[usize], target: &[usize]) -> Vec<usize> {
+        let offset = target.len() - shape.len();
+
+        (0..target.len())
+            .map(|k| {
+                if k < offset || shape[k - offset] != target[k] {
+                    0
+                } else {
+                    strides[k - offset]
+                }
+            })
+            .collect()
+    }
+
+    // Applies `f` to every pair of broadcast elements, producing a
+    // row-major tensor of the broadcast shape
+    fn broadcast_map<T, A, B, F>(a: &A, b: &B, f: F) -> Result<Tensor<T>, LinAlgError>
+    where
+        T: Scalar,
+        A: Broadcast<T> + ?Sized,
+        B: Broadcast<T> + ?Sized,
+        F: Fn(T, T) -> T,
+    {
+        let (a_data, a_shape, a_strides) = a.broadcast_layout();
+        let (b_data, b_shape, b_strides) = b.broadcast_layout();
+
+        let shape = broadcast_shapes(&a_shape, &b_shape)?;
+        let a_strides = broadcast_strides(&a_shape, &a_strides, &shape);
+        let b_strides = broadcast_strides(&b_shape, &b_strides, &shape);
+
+        let size = shape.iter().product();
+        let mut data = Vec::with_capacity(size);
+        let mut index = vec![0; shape.len()];
+        let (mut a_offset, mut b_offset) = (0, 0);
+
+        for _ in 0..size {
+            data.push(f(a_data[a_offset], b_data[b_offset]));
+
+            // Odometer increment, tracking both offsets
+            for k in (0..shape.len()).rev() {
+                index[k] += 1;
+                a_offset += a_strides[k];
+                b_offset += b_strides[k];
+
+                if index[k] < shape[k] {
+                    break;
+                }
+
+                a_offset -= a_strides[k] * shape[k];
+                b_offset -= b_strides[k] * shape[k];
+                index[k] = 0;
+            }
+        }
+
+        Ok(Tensor {
+            strides: contiguous_strides(&shape),
+            shape,
+            data,
+        })
+    }
+
+    fn broadcast_vector<T, B, F>(a: &Vector<T>, b: &B, f: F) -> Result<Vector<T>, LinAlgError>
+    where
+        T: Scalar,
+        B: Broadcast<T> + ?Sized,
+        F: Fn(T, T) -> T,
+    {
+        let (_, b_shape, _) = b.broadcast_layout();
+
+        if b_shape.len() > 1 {
+            return Err(LinAlgError::DimensionMismatch {
+                left: vec![a.size],
+                right: b_shape,
+            });
+        }
+
+        let result = broadcast_map(a, b, f)?;
+
+        return Ok(Vector {
+            size: result.data.len(),
+            data: result.data,
+        });
+    }
+
+    fn broadcast_matrix<T, B, F>(a: &Matrix<T>, b: &B, f: F) -> Result<Matrix<T>, LinAlgError>
+    where
+        T: Scalar,
+        B: Broadcast<T> + ?Sized,
+        F: Fn(T, T) -> T,
+    {
+        let (_, b_shape, _) = b.broadcast_layout();
+
+        if b_shape.len() > 2 {
+            return Err(LinAlgError::DimensionMismatch {
+                left: vec![a.rows, a.cols],
+                right: b_shape,
+            });
+        }
+
+        let result = broadcast_map(a, b, f)?;
+
+        return Ok(Matrix::from_vec(
+            result.shape[0],
+            result.shape[1],
+            result.data,
+        ));
+    }
+
+    fn broadcast_tensor<T, B, F>(a: &Tensor<T>, b: &B, f: F) -> Result<Tensor<T>, LinAlgError>
+    where
+        T: Scalar,
+        B: Broadcast<T> + ?Sized,
+        F: Fn(T, T) -> T,
+    {
+        broadcast_map(a, b, f)
+    }
+
+    // NaN-propagating minimum and maximum, like NumPy's `minimum`/`maximum`
+    fn nan_min<T: PartialOrd>(x: T, y: T) -> T {
+        match x.partial_cmp(&y) {
+            Some(Ordering::Greater) => y,
+            Some(_) => x,
+            None if x.partial_cmp(&x).is_none() => x,
+            None => y,
+        }
+    }
+
+    fn nan_max<T: PartialOrd>(x: T, y: T) -> T {
+        match x.partial_cmp(&y) {
+            Some(Ordering::Less) => y,
+            Some(_) => x,
+            None if x.partial_cmp(&x).is_none() => x,
+            None => y,
+        }
+    }
+
+    // Elementwise functions where `b` is broadcast against `a`. The result
+    // takes the broadcast shape, which must keep the type of `a`: vectors
+    // only combine with vectors and scalars, matrices with anything up to 2-D.
+    macro_rules! impl_broadcast_fns {
+        ($t:ident, [$($bound:tt)*], $map:ident, $($name:ident, $try_name:ident, $f:expr);*) => {
+            impl<T: $($bound)*> $t<T> {
+                $(
+                    pub fn $name<B: Broadcast<T> + ?Sized>(a: &$t<T>, b: &B) -> $t<T> {
+                        unwrap_or_panic($t::$try_name(a, b))
+                    }
+
+                    pub fn $try_name<B: Broadcast<T> + ?Sized>(
+                        a: &$t<T>,
+                        b: &B,
+                    ) -> Result<$t<T>, LinAlgError> {
+                        $map(a, b, $f)
+                    }
+                )*
+            }
+        };
+    }
+
+    macro_rules! impl_all_broadcast_fns {
+        ($($t:ident, $map:ident);*) => {
+            $(
+                impl_broadcast_fns!(
+                    $t, [Scalar], $map,
+                    broadcast_add, try_broadcast_add, |x: T, y: T| x + y;
+                    broadcast_sub, try_broadcast_sub, |x: T, y: T| x - y;
+                    broadcast_mul, try_broadcast_mul, |x: T, y: T| x * y;
+                    broadcast_div, try_broadcast_div, |x: T, y: T| x / y
+                );
+
+                impl_broadcast_fns!(
+                    $t, [Scalar + PartialOrd], $map,
+                    broadcast_min, try_broadcast_min, nan_min;
+                    broadcast_max, try_broadcast_max, nan_max
+                );
+
+                impl_broadcast_fns!(
+                    $t, [Real], $map,
+                    broadcast_pow, try_broadcast_pow, |x: T, p: T| x.powf(p)
+                );
+            )*
+        };
+    }
+
+    impl_all_broadcast_fns!(Vector, broadcast_vector; Matrix, broadcast_matrix; Tensor, broadcast_tensor);
+
+    impl<T: Scalar> Tensor<T> {
+        pub fn broadcast_to(&self, shape: &[usize]) -> Tensor<T> {
+            unwrap_or_panic(self.try_broadcast_to(shape))
+        }
+
+        // A contiguous tensor of the given shape that repeats `self` along
+        // broadcast axes. The elements are copied out, since a stride-0 view
+        // would let one write through IndexMut change every repeat.
+        pub fn try_broadcast_to(&self, shape: &[usize]) -> Result<Tensor<T>, LinAlgError> {
+            if shape.len() < self.ndim() || broadcast_shapes(&self.shape, shape)? != shape {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: self.shape.clone(),
+                    right: shape.to_vec(),
+                });
+            }
+
+            let strides = broadcast_strides(&self.shape, &self.strides, shape);
+            let mut data = Vec::with_capacity(shape.iter().product());
+
+            for_each_offset(shape, &strides, |offset| data.push(self.data[offset]));
+
+            Ok(Tensor {
+                shape: shape.to_vec(),
+                strides: contiguous_strides(shape),
+                data,
+            })
+        }
+    }
+
+    /* Reductions */
+
+    // Calls `f` with the offset of every element of a strided layout, in
+    // row-major order of the shape
+    fn for_each_offset<F: FnMut(usize)>(shape: &[usize], strides: &[usize], mut f: F) {
+        if shape.contains(&0) {
+            return;
+        }
+
+        let mut index = vec![0; shape.len()];
+        let mut offset = 0;
+
+        loop {
+            f(offset);
+
+            let mut k = shape.len();
+
+            loop {
+                if k == 0 {
+                    return;
+                }
+
+                k -= 1;
+                index[k] += 1;
+                offset += strides[k];
+
+                if index[k] < shape[k] {
+                    break;
+                }
+
+                offset -= strides[k] * shape[k];
+                index[k] = 0;
+            }
+        }
+    }
+
+    // Gathers the lanes along `axis`, or along every axis when it is None,
+    // and reduces each lane with `f`. Reduced axes are dropped from the
+    // result, or kept with size 1 when `keep_dims` is set.
+    fn reduce<T, U, A, F>(
+        a: &A,
+        axis: Option<usize>,
+        keep_dims: bool,
+        f: F,
+    ) -> Result<Tensor<U>, LinAlgError>
+    where
+        T: Scalar,
+        U: Scalar,
+        A: Broadcast<T> + ?Sized,
+        F: Fn(&[T]) -> U,
+    {
+        let (data, shape, strides) = a.broadcast_layout();
+
+        let reduced: Vec<bool> = match axis {
+            None => vec![true; shape.len()],
+            Some(axis) if axis < shape.len() => (0..shape.len()).map(|k| k == axis).collect(),
+            Some(axis) => {
+                return Err(LinAlgError::IndexOutOfBounds {
+                    index: vec![axis],
+                    shape,
+                });
+            }
+        };
+
+        let pick = |values: &[usize], lane: bool| -> Vec<usize> {
+            (0..shape.len())
+                .filter(|&k| reduced[k] == lane)
+                .map(|k| values[k])
+                .collect()
+        };
+
+        let (outer_shape, outer_strides) = (pick(&shape, false), pick(&strides, false));
+        let (lane_shape, lane_strides) = (pick(&shape, true), pick(&strides, true));
+
+        let mut lane = Vec::with_capacity(lane_shape.iter().product());
+        let mut result = Vec::with_capacity(outer_shape.iter().product());
+
+        for_each_offset(&outer_shape, &outer_strides, |base| {
+            lane.clear();
+            for_each_offset(&lane_shape, &lane_strides, |offset| {
+                lane.push(data[base + offset])
+            });
+            result.push(f(&lane));
+        });
+
+        let result_shape = if keep_dims {
+            (0..shape.len())
+                .map(|k| if reduced[k] { 1 } else { shape[k] })
+                .collect()
+        } else {
+            outer_shape
+        };
+
+        return Tensor::try_from_vec(&result_shape, result);
+    }
+
+    // Pairwise summation: the rounding error grows with log(n) instead of n,
+    // at the cost of one recursion per 32 elements
+    fn pairwise_sum<T: Scalar>(values: &[T]) -> T {
+        if values.len() <= 32 {
+            return values.iter().fold(T::zero(), |acc, &x| acc + x);
+        }
+
+        let (left, right) = values.split_at(values.len() / 2);
+
+        return pairwise_sum(left) + pairwise_sum(right);
+    }
+
+    // Euclidean norm. The plain sum of squares is used unless it overflows
+    // or gets close to underflowing, in which case the values are scaled by
+    // the largest magnitude first. A NaN or infinite magnitude is the norm
+    // itself, NaN taking precedence.
+    pub(crate) fn scaled_norm2<T: Real>(values: &[T]) -> T {
+        let squares: Vec<T> = values.iter().map(|&x| x * x).collect();
+        let sum = pairwise_sum(&squares);
+
+        if sum.to_f64().is_finite() && sum >= T::min_positive().sqrt() {
+            return sum.sqrt();
+        }
+
+        let scale = max_abs(values);
+
+        if scale == T::zero() || !scale.to_f64().is_finite() {
+            return scale;
+        }
+
+        let squares: Vec<T> = values.iter().map(|&x| (x / scale) * (x / scale)).collect();
+
+        return scale * pairwise_sum(&squares).sqrt();
+    }
+
+    // Index of the first extreme element, or of the first NaN
+    fn arg_extreme<T: PartialOrd>(values: &[T], better: Ordering) -> usize {
+        let mut best = 0;
+
+        for (k, x) in values.iter().enumerate() {
+            match x.partial_cmp(&values[best]) {
+                Some(ordering) if ordering == better => best = k,
+                None if x.partial_cmp(x).is_none() => return k,
+                _ => {}
+            }
+        }
+
+        return best;
+    }
+
+    // Min, max and their indices have no value on an empty lane
+    fn check_not_empty<T, A>(a: &A, axis: Option<usize>) -> Result<(), LinAlgError>
+    where
+        T: Scalar,
+        A: Broadcast<T> + ?Sized,
+    {
+        let (_, shape, _) = a.broadcast_layout();
+
+        let empty = match axis {
+            None => shape.contains(&0),
+            Some(axis) => shape.get(axis) == Some(&0),
+        };
+
+        if empty {
+            return Err(LinAlgError::EmptyReduction { shape });
+        }
+
+        Ok(())
+    }
+
+    // The variance divides by n - ddof, which must be positive
+    fn check_ddof<T, A>(a: &A, axis: Option<usize>, ddof: usize) -> Result<(), LinAlgError>
+    where
+        T: Scalar,
+        A: Broadcast<T> + ?Sized,
+    {
+        let (_, shape, _) = a.broadcast_layout();
+
+        // An invalid axis is left for `reduce` to report
+        let lane = match axis {
+            None => Some(shape.iter().product()),
+            Some(axis) => shape.get(axis).copied(),
+        };
+
+        if lane.is_some_and(|n| ddof >= n) {
+            return Err(LinAlgError::InvalidParameter {
+                name: "ddof",
+                reason: "must be less than the number of elements reduced",
+            });
+        }
+
+        Ok(())
+    }
+
+    // Reductions along one axis (`Some(axis)`) or all axes (`None`). For a
+    // matrix axis 0 runs down the rows, so `m.mean(Some(0), false)` holds
+    // the column means. Results are tensors, `to_vector` and `to_matrix`
+    // convert them back.
+    macro_rules! impl_reductions {
+        ($($t:ident),*) => {
+            $(
+                impl<T: Scalar> $t<T> {
+                    pub fn sum(&self, axis: Option<usize>, keep_dims: bool) -> Tensor<T> {
+                        unwrap_or_panic(self.try_sum(axis, keep_dims))
+                    }
+
+                    pub fn try_sum(
+                        &self,
+                        axis: Option<usize>,
+                        keep_dims: bool,
+                    ) -> Result<Tensor<T>, LinAlgError> {
+                        reduce(self, axis, keep_dims, pairwise_sum)
+                    }
+
+                    pub fn prod(&self, axis: Option<usize>, keep_dims: bool) -> Tensor<T> {
+                        unwrap_or_panic(self.try_prod(axis, keep_dims))
+                    }
+
+                    pub fn try_prod(
+                        &self,
+                        axis: Option<usize>,
+                        keep_dims: bool,
+                    ) -> Result<Tensor<T>, LinAlgError> {
+                        reduce(self, axis, keep_dims, |lane: &[T]| {
+                            lane.iter().fold(T::one(), |acc, &x| acc * x)
+                        })
+                    }
+                }
+
+                impl<T: Scalar + PartialOrd> $t<T> {
+                    pub fn min(&self, axis: Option<usize>, keep_dims: bool) -> Tensor<T> {
+                        unwrap_or_panic(self.try_min(axis, keep_dims))
+                    }
+
+                    pub fn try_min(
+                        &self,
+                        axis: Option<usize>,
+                        keep_dims: bool,
+                    ) -> Result<Tensor<T>, LinAlgError> {
+                        check_not_empty(self, axis)?;
+
+                        reduce(self, axis, keep_dims, |lane: &[T]| {
+                            lane[arg_extreme(lane, Ordering::Less)]
+                        })
+                    }
+
+                    pub fn max(&self, axis: Option<usize>, keep_dims: bool) -> Tensor<T> {
+                        unwrap_or_panic(self.try_max(axis, keep_dims))
+                    }
+
+                    pub fn try_max(
+                        &self,
+                        axis: Option<usize>,
+                        keep_dims: bool,
+                    ) -> Result<Tensor<T>, LinAlgError> {
+                        check_not_empty(self, axis)?;
+
+                        reduce(self, axis, keep_dims, |lane: &[T]| {
+                            lane[arg_extreme(lane, Ordering::Greater)]
+                        })
+                    }
+
+                    // With no axis the index is into the row-major flattening
+                    pub fn argmin(&self, axis: Option<usize>, keep_dims: bool) -> Tensor<usize> {
+                        unwrap_or_panic(self.try_argmin(axis, keep_dims))
+                    }
+
+                    pub fn try_argmin(
+                        &self,
+                        axis: Option<usize>,
+                        keep_dims: bool,
+                    ) -> Result<Tensor<usize>, LinAlgError> {
+                        check_not_empty(self, axis)?;
+
+                        reduce(self, axis, keep_dims, |lane: &[T]| {
+                            arg_extreme(lane, Ordering::Less)
+                        })
+                    }
+
+                    pub fn argmax(&self, axis: Option<usize>, keep_dims: bool) -> Tensor<usize> {
+                        unwrap_or_panic(self.try_argmax(axis, keep_dims))
+                    }
+
+                    pub fn try_argmax(
+                        &self,
+                        axis: Option<usize>,
+                        keep_dims: bool,
+                    ) -> Result<Tensor<usize>, LinAlgError> {
+                        check_not_empty(self, axis)?;
+
+                        reduce(self, axis, keep_dims, |lane: &[T]| {
+                            arg_extreme(lane, Ordering::Greater)
+                        })
+                    }
+                }
+
+                impl<T: Real> $t<T> {
+                    pub fn mean(&self, axis: Option<usize>, keep_dims: bool) -> Tensor<T> {
+                        unwrap_or_panic(self.try_mean(axis, keep_dims))
+                    }
+
+                    pub fn try_mean(
+                        &self,
+                        axis: Option<usize>,
+                        keep_dims: bool,
+                    ) -> Result<Tensor<T>, LinAlgError> {
+                        reduce(self, axis, keep_dims, |lane: &[T]| {
+                            pairwise_sum(lane) / T::from_f64(lane.len() as f64)
+                        })
+                    }
+
+                    // Divides by n - ddof, so ddof = 0 is the population and
+                    // ddof = 1 the sample variance, and ddof must be less than
+                    // the lane length. Two passes, subtracting the mean before
+                    // squaring.
+                    pub fn variance(
+                        &self,
+                        axis: Option<usize>,
+                        keep_dims: bool,
+                        ddof: usize,
+                    ) -> Tensor<T> {
+                        unwrap_or_panic(self.try_variance(axis, keep_dims, ddof))
+                    }
+
+                    pub fn try_variance(
+                        &self,
+                        axis: Option<usize>,
+                        keep_dims: bool,
+                        ddof: usize,
+                    ) -> Result<Tensor<T>, LinAlgError> {
+                        check_ddof(self, axis, ddof)?;
+
+                        reduce(self, axis, keep_dims, |lane: &[T]| {
+                            let n = T::from_f64(lane.len() as f64);
+                            let mean = pairwise_sum(lane) / n;
+
+                            let squares: Vec<T> =
+                                lane.iter().map(|&x| (x - mean) * (x - mean)).collect();
+
+                            pairwise_sum(&squares) / (n - T::from_f64(ddof as f64))
+                        })
+                    }
+
+                    // Euclidean norm of each lane
+                    pub fn norm(&self, axis: Option<usize>, keep_dims: bool) -> Tensor<T> {
+                        unwrap_or_panic(self.try_norm(axis, keep_dims))
+                    }
+
+                    pub fn try_norm(
+                        &self,
+                        axis: Option<usize>,
+                        keep_dims: bool,
+                    ) -> Result<Tensor<T>, LinAlgError> {
+                        reduce(self, axis, keep_dims, scaled_norm2)
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_reductions!(Matrix, Tensor);
+
+    /* Operators */
+
+    // Implements a binary operator for every owned/borrowed combination of
+    // operands by forwarding to one of the associated functions above
+    macro_rules! impl_binary_op {
+        ($op:ident, $method:ident, $lhs:ident, $rhs:ident, $out:ident, $func:expr) => {
+            impl<T: Scalar> $op<&$rhs<T>> for &$lhs<T> {
+                type Output = $out<T>;
+
+                fn $method(self, rhs: &$rhs<T>) -> $out<T> {
+                    $func(self, rhs)
+                }
+            }
+
+            impl<T: Scalar> $op<$rhs<T>> for &$lhs<T> {
+                type Output = $out<T>;
+
+                fn $method(self, rhs: $rhs<T>) -> $out<T> {
+                    $func(self, &rhs)
+                }
+            }
+
+            impl<T: Scalar> $op<&$rhs<T>> for $lhs<T> {
+                type Output = $out<T>;
+
+                fn $method(self, rhs: &$rhs<T>) -> $out<T> {
+                    $func(&self, rhs)
+                }
+            }
+
+            impl<T: Scalar> $op<$rhs<T>> for $lhs<T> {
+                type Output = $out<T>;
+
+                fn $method(self, rhs: $rhs<T>) -> $out<T> {
+                    $func(&self, &rhs)
+                }
+            }
+        };
+    }
+
+    // Scaling by an element on the right, plus negation as scaling by -1
+    macro_rules! impl_scalar_ops {
+        ($t:ident, $scale:expr) => {
+            impl<T: Scalar> Mul<T> for &$t<T> {
+                type Output = $t<T>;
+
+                fn mul(self, rhs: T) -> $t<T> {
+                    $scale(self, rhs)
+                }
+            }
+
+            impl<T: Scalar> Mul<T> for $t<T> {
+                type Output = $t<T>;
+
+                fn mul(self, rhs: T) -> $t<T> {
+                    $scale(&self, rhs)
+                }
+            }
+
+            impl<T: Scalar> MulAssign<T> for $t<T> {
+                fn mul_assign(&mut self, rhs: T) {
+                    *self = $scale(self, rhs);
+                }
+            }
+
+            impl<T: Scalar + Neg<Output = T>> Neg for &$t<T> {
+                type Output = $t<T>;
+
+                fn neg(self) -> $t<T> {
+                    $scale(self, -T::one())
+                }
+            }
+
+            impl<T: Scalar + Neg<Output = T>> Neg for $t<T> {
+                type Output = $t<T>;
+
+                fn neg(self) -> $t<T> {
+                    $scale(&self, -T::one())
+                }
+            }
+        };
+    }
+
+    // Scaling by a primitive on the left. Coherence rules do not allow this
+    // generically, so every primitive scalar type is listed.
+    macro_rules! impl_left_scalar_mul {
+        ($t:ident, $scale:expr, $($s:ty),*) => {
+            $(
+                impl Mul<&$t<$s>> for $s {
+                    type Output = $t<$s>;
+
+                    fn mul(self, rhs: &$t<$s>) -> $t<$s> {
+                        $scale(rhs, self)
+                    }
+                }
+
+                impl Mul<$t<$s>> for $s {
+                    type Output = $t<$s>;
+
+                    fn mul(self, rhs: $t<$s>) -> $t<$s> {
+                        $scale(&rhs, self)
+                    }
+                }
+            )*
+        };
+    }
+
+    // `+=` and `-=` for both owned and borrowed right hand sides
+    macro_rules! impl_assign_ops {
+        ($t:ident, $add:expr, $sub:expr) => {
+            impl<T: Scalar> AddAssign<&$t<T>> for $t<T> {
+                fn add_assign(&mut self, rhs: &$t<T>) {
+                    *self = $add(self, rhs);
+                }
+            }
+
+            impl<T: Scalar> AddAssign<$t<T>> for $t<T> {
+                fn add_assign(&mut self, rhs: $t<T>) {
+                    *self = $add(self, &rhs);
+                }
+            }
+
+            impl<T: Scalar> SubAssign<&$t<T>> for $t<T> {
+                fn sub_assign(&mut self, rhs: &$t<T>) {
+                    *self = $sub(self, rhs);
+                }
+            }
+
+            impl<T: Scalar> SubAssign<$t<T>> for $t<T> {
+                fn sub_assign(&mut self, rhs: $t<T>) {
+                    *self = $sub(self, &rhs);
+                }
+            }
+        };
+    }
+
+    macro_rules! impl_all_left_scalar_mul {
+        ($t:ident, $scale:expr) => {
+            impl_left_scalar_mul!(
+                $t, $scale, f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128,
+                usize
+            );
+        };
+    }
+
+    impl_binary_op!(Add, add, Vector, Vector, Vector, Vector::vector_add);
+    impl_binary_op!(Sub, sub, Vector, Vector, Vector, Vector::vector_sub);
+    impl_scalar_ops!(Vector, Vector::vector_scale);
+    impl_all_left_scalar_mul!(Vector, Vector::vector_scale);
+    impl_assign_ops!(Vector, Vector::vector_add, Vector::vector_sub);
+
+    impl_binary_op!(Add, add, Matrix, Matrix, Matrix, Matrix::matrix_add);
+    impl_binary_op!(Sub, sub, Matrix, Matrix, Matrix, Matrix::matrix_sub);
+    impl_binary_op!(Mul, mul, Matrix, Matrix, Matrix, Matrix::matrix_mul);
+    impl_binary_op!(
+        Mul,
+        mul,
+        Matrix,
+        Vector,
+        Vector,
+        |m: &Matrix<T>, v: &Vector<T>| {
+            let column = Matrix::matrix_vector_mul(m, v);
+
+            Vector {
+                size: column.rows,
+                data: column.data,
+            }
+        }
+    );
+    impl_scalar_ops!(Matrix, Matrix::matrix_scale);
+    impl_all_left_scalar_mul!(Matrix, Matrix::matrix_scale);
+    impl_assign_ops!(Matrix, Matrix::matrix_add, Matrix::matrix_sub);
+
+    impl<T: Scalar> MulAssign<&Matrix<T>> for Matrix<T> {
+        fn mul_assign(&mut self, rhs: &Matrix<T>) {
+            *self = Matrix::matrix_mul(self, rhs);
+        }
+    }
+
+    impl<T: Scalar> MulAssign<Matrix<T>> for Matrix<T> {
+        fn mul_assign(&mut self, rhs: Matrix<T>) {
+            *self = Matrix::matrix_mul(self, &rhs);
+        }
+    }
+
+    impl_binary_op!(Add, add, Tensor, Tensor, Tensor, Tensor::tensor_add);
+    impl_binary_op!(Sub, sub, Tensor, Tensor, Tensor, Tensor::tensor_sub);
+    impl_scalar_ops!(Tensor, Tensor::tensor_scale);
+    impl_all_left_scalar_mul!(Tensor, Tensor::tensor_scale);
+    impl_assign_ops!(Tensor, Tensor::tensor_add, Tensor::tensor_sub);
+
+    impl<T> Index<usize> for Vector<T> {
+        type Output = T;
+
+        fn index(&self, index: usize) -> &T {
+            &self.data[index]
+        }
+    }
+
+    impl<T> IndexMut<usize> for Vector<T> {
+        fn index_mut(&mut self, index: usize) -> &mut T {
+            &mut self.data[index]
+        }
+    }
+
+    impl<T: Scalar> Index<(usize, usize)> for Matrix<T> {
+        type Output = T;
+
+        fn index(&self, (row, col): (usize, usize)) -> &T {
+            unwrap_or_panic(self.check_index(row, col));
+
+            &self.data[row * self.cols + col]
+        }
+    }
+
+    impl<T: Scalar> IndexMut<(usize, usize)> for Matrix<T> {
+        fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+            self.get_mut(row, col)
+        }
+    }
+
+    // Tensors are indexed with one index per axis, `t[[i, j, k]]`
+    impl<T: Scalar, const N: usize> Index<[usize; N]> for Tensor<T> {
+        type Output = T;
+
+        fn index(&self, index: [usize; N]) -> &T {
+            unwrap_or_panic(self.check_element(&index));
+
+            &self.data[self.offset(&index)]
+        }
+    }
+
+    impl<T: Scalar, const N: usize> IndexMut<[usize; N]> for Tensor<T> {
+        fn index_mut(&mut self, index: [usize; N]) -> &mut T {
+            self.get_mut(&index)
+        }
     }
 }
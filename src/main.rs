@@ -1,3 +1 @@
-use LAMS_rs::linear_algebra::*;
-
 pub fn main() {}
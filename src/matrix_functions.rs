@@ -0,0 +1,348 @@
+// src/matrix_functions.rs
+
+pub mod matrix_functions {
+    use crate::decomposition::decomposition::{check_square, identity, norm_one, LU};
+    use crate::eigen::eigen::GeneralEigen;
+    use crate::linear_algebra::linear_algebra::{scaled_norm2, LinAlgError, Matrix};
+
+    // Maximum number of Denman-Beavers iterations for one square root
+    const MAX_ITERATIONS: usize = 100;
+
+    // Maximum number of square roots taken by logm before the Pade step
+    const MAX_SQUARE_ROOTS: usize = 64;
+
+    // Maximum number of squarings in expm, enough to bring any finite
+    // 1-norm below EXPM_THETA
+    const MAX_SQUARINGS: i32 = 1024;
+
+    // Largest 1-norm for which each Pade approximant of exp is accurate to
+    // double precision without scaling (Higham, 2005)
+    const EXPM_THETA: [(usize, f64); 5] = [
+        (3, 1.495585217958292e-2),
+        (5, 2.53939833006323e-1),
+        (7, 9.504178996162932e-1),
+        (9, 2.097847961257068e0),
+        (13, 5.371920351148152e0),
+    ];
+
+    const PADE_3: [f64; 4] = [120.0, 60.0, 12.0, 1.0];
+    const PADE_5: [f64; 6] = [30240.0, 15120.0, 3360.0, 420.0, 30.0, 1.0];
+    const PADE_7: [f64; 8] = [
+        17297280.0, 8648640.0, 1995840.0, 277200.0, 25200.0, 1512.0, 56.0, 1.0,
+    ];
+    const PADE_9: [f64; 10] = [
+        17643225600.0,
+        8821612800.0,
+        2075673600.0,
+        302702400.0,
+        30270240.0,
+        2162160.0,
+        110880.0,
+        3960.0,
+        90.0,
+        1.0,
+    ];
+    const PADE_13: [f64; 14] = [
+        64764752532480000.0,
+        32382376266240000.0,
+        7771770303897600.0,
+        1187353796428800.0,
+        129060195264000.0,
+        10559470521600.0,
+        670442572800.0,
+        33522128640.0,
+        1323241920.0,
+        40840800.0,
+        960960.0,
+        16380.0,
+        182.0,
+        1.0,
+    ];
+
+    // 7-point Gauss-Legendre nodes and weights on [0, 1], for the partial
+    // fraction form of the [7/7] Pade approximant of log(I + X)
+    const LOG_NODES: [f64; 7] = [
+        0.025446043828620757,
+        0.12923440720030277,
+        0.2970774243113014,
+        0.5,
+        0.7029225756886986,
+        0.8707655927996972,
+        0.9745539561713792,
+    ];
+    const LOG_WEIGHTS: [f64; 7] = [
+        0.06474248308443485,
+        0.13985269574463832,
+        0.1909150252525595,
+        0.2089795918367347,
+        0.1909150252525595,
+        0.13985269574463832,
+        0.06474248308443485,
+    ];
+
+    // Largest ||A - I||_1 for which the [7/7] Pade approximant of the log is
+    // accurate to double precision
+    const LOG_THETA: f64 = 0.25;
+
+    // Matrix exponential by scaling and squaring with Pade approximants
+    // (Higham, 2005). The degree is the lowest one accurate for ||A||_1,
+    // and larger norms are scaled by 2^-s and squared back s times.
+    pub fn expm(a: &Matrix) -> Result<Matrix, LinAlgError> {
+        check_square(a)?;
+
+        if a.data.iter().any(|x| !x.is_finite()) {
+            return Err(LinAlgError::InvalidParameter {
+                name: "matrix",
+                reason: "has a non-finite entry",
+            });
+        }
+
+        let n = a.rows;
+        let norm = norm_one(a);
+
+        for &(degree, theta) in &EXPM_THETA[..4] {
+            if norm <= theta {
+                let coefficients: &[f64] = match degree {
+                    3 => &PADE_3,
+                    5 => &PADE_5,
+                    7 => &PADE_7,
+                    _ => &PADE_9,
+                };
+
+                return pade_low(a, coefficients);
+            }
+        }
+
+        // The 1-norm itself can overflow for finite entries
+        let s = if norm > EXPM_THETA[4].1 {
+            ((norm / EXPM_THETA[4].1).log2().ceil() as i32).min(MAX_SQUARINGS)
+        } else {
+            0
+        };
+
+        let scaled = a * 2f64.powi(-s);
+        let b = &PADE_13;
+        let i = identity(n);
+
+        let a2 = &scaled * &scaled;
+        let a4 = &a2 * &a2;
+        let a6 = &a4 * &a2;
+
+        // Degree 13 needs only A^2, A^4 and A^6 (and three more products)
+        let u_high = &a6 * &combine(&[(&a6, b[13]), (&a4, b[11]), (&a2, b[9])]);
+        let u_low = combine(&[(&a6, b[7]), (&a4, b[5]), (&a2, b[3]), (&i, b[1])]);
+        let u = &scaled * &(&u_high + &u_low);
+
+        let v_high = &a6 * &combine(&[(&a6, b[12]), (&a4, b[10]), (&a2, b[8])]);
+        let v_low = combine(&[(&a6, b[6]), (&a4, b[4]), (&a2, b[2]), (&i, b[0])]);
+        let v = &v_high + &v_low;
+
+        let mut result = pade_solve(&u, &v)?;
+
+        for _ in 0..s {
+            result = &result * &result;
+        }
+
+        if result.data.iter().any(|x| !x.is_finite()) {
+            return Err(LinAlgError::Overflow);
+        }
+
+        return Ok(result);
+    }
+
+    // Pade approximants of degree 3 to 9, with the odd powers in U and the
+    // even ones in V
+    fn pade_low(a: &Matrix, b: &[f64]) -> Result<Matrix, LinAlgError> {
+        let a2 = a * a;
+
+        let mut power = identity(a.rows);
+        let mut u = &power * b[1];
+        let mut v = &power * b[0];
+
+        for k in 1..b.len() / 2 {
+            power = &power * &a2;
+            u += &(&power * b[2 * k + 1]);
+            v += &(&power * b[2 * k]);
+        }
+
+        return pade_solve(&(a * &u), &v);
+    }
+
+    // Sum of c_k M_k
+    fn combine(terms: &[(&Matrix, f64)]) -> Matrix {
+        let (first, c) = terms[0];
+        let mut result = first * c;
+
+        for &(m, c) in &terms[1..] {
+            result += &(m * c);
+        }
+
+        return result;
+    }
+
+    // r(A) = (V - U)^-1 (V + U)
+    fn pade_solve(u: &Matrix, v: &Matrix) -> Result<Matrix, LinAlgError> {
+        return LU::new(&(v - u))?.solve_matrix(&(v + u));
+    }
+
+    // Real principal square roots and logarithms exist only without
+    // eigenvalues on the closed negative real axis
+    fn check_principal(a: &Matrix) -> Result<(), LinAlgError> {
+        let eigen = GeneralEigen::eigenvalues(a)?;
+
+        for k in 0..a.rows {
+            if eigen.imag[k] == 0.0 && eigen.real[k] <= 0.0 {
+                return Err(LinAlgError::EigenvalueOnNegativeAxis {
+                    eigenvalue: eigen.real[k],
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Principal square root by the Denman-Beavers iteration
+    pub fn sqrtm(a: &Matrix) -> Result<Matrix, LinAlgError> {
+        check_square(a)?;
+        check_principal(a)?;
+
+        return denman_beavers(a);
+    }
+
+    // Y converges to A^1/2 and Z to A^-1/2. Scaling both by
+    // |det Y det Z|^(-1/2n) speeds up the early iterations, and is switched
+    // off once the iterates settle so it cannot disturb the quadratic
+    // convergence at the end.
+    fn denman_beavers(a: &Matrix) -> Result<Matrix, LinAlgError> {
+        let n = a.rows;
+
+        // The relative change below would be 0/0
+        if n == 0 {
+            return Ok(a.clone());
+        }
+
+        let mut y = a.clone();
+        let mut z = identity(n);
+        let mut scaling = true;
+
+        for _ in 0..MAX_ITERATIONS {
+            let y_lu = LU::new(&y)?;
+            let z_lu = LU::new(&z)?;
+
+            // From the log determinants, as the determinants of large
+            // matrices overflow
+            let mu = if scaling {
+                (-(log_abs_det(&y_lu) + log_abs_det(&z_lu)) / (2.0 * n as f64)).exp()
+            } else {
+                1.0
+            };
+
+            let y_next = &(&(&y * mu) + &(&z_lu.inverse()? * (1.0 / mu))) * 0.5;
+            let z_next = &(&(&z * mu) + &(&y_lu.inverse()? * (1.0 / mu))) * 0.5;
+
+            let change = scaled_norm2(&(&y_next - &y).data) / scaled_norm2(&y_next.data);
+
+            y = y_next;
+            z = z_next;
+
+            if !change.is_finite() {
+                break;
+            }
+
+            if change < 1e-2 {
+                scaling = false;
+            }
+
+            // The last step squared an error of this size
+            if change <= 1e-10 {
+                return Ok(y);
+            }
+        }
+
+        return Err(LinAlgError::NonConvergence {
+            iterations: MAX_ITERATIONS,
+        });
+    }
+
+    // Principal logarithm by inverse scaling and squaring: square roots
+    // bring A within LOG_THETA of I, where log(A) = 2^k log(A^(1/2^k)) is
+    // evaluated by a Pade approximant
+    pub fn logm(a: &Matrix) -> Result<Matrix, LinAlgError> {
+        check_square(a)?;
+        check_principal(a)?;
+
+        let n = a.rows;
+        let i = identity(n);
+        let mut x = a.clone();
+        let mut k = 0;
+
+        while norm_one(&(&x - &i)) > LOG_THETA {
+            if k == MAX_SQUARE_ROOTS {
+                return Err(LinAlgError::NonConvergence { iterations: k });
+            }
+
+            x = denman_beavers(&x)?;
+            k += 1;
+        }
+
+        // log(I + E) = sum_j w_j E (I + t_j E)^-1
+        let e = &x - &i;
+        let mut result = Matrix::new(n, n);
+
+        for (&t, &w) in LOG_NODES.iter().zip(LOG_WEIGHTS.iter()) {
+            let term = LU::new(&(&i + &(&e * t)))?.solve_matrix(&e)?;
+            result += &(&term * w);
+        }
+
+        return Ok(&result * 2f64.powi(k as i32));
+    }
+
+    // A^k by repeated squaring, through the inverse for negative k
+    pub fn matrix_power(a: &Matrix, k: i64) -> Result<Matrix, LinAlgError> {
+        check_square(a)?;
+
+        let mut base = if k < 0 {
+            LU::new(a)?.inverse()?
+        } else {
+            a.clone()
+        };
+
+        let mut exponent = k.unsigned_abs();
+        let mut result = identity(a.rows);
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+
+            exponent >>= 1;
+
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+
+        return Ok(result);
+    }
+
+    // A^p for real p. Integer powers are exact products, anything else is
+    // the principal power exp(p log(A)).
+    pub fn powm(a: &Matrix, p: f64) -> Result<Matrix, LinAlgError> {
+        if p.fract() == 0.0 && p.abs() <= i64::MAX as f64 {
+            return matrix_power(a, p as i64);
+        }
+
+        if p == 0.5 {
+            return sqrtm(a);
+        }
+
+        return expm(&(&logm(a)? * p));
+    }
+
+    // log |det A| from the diagonal of U
+    fn log_abs_det(lu: &LU) -> f64 {
+        let n = lu.size();
+
+        (0..n).map(|i| lu.lu.data[i * n + i].abs().ln()).sum()
+    }
+}
@@ -0,0 +1,829 @@
+// src/sparse.rs
+
+pub mod sparse {
+    use crate::linear_algebra::linear_algebra::{
+        unwrap_or_panic, LinAlgError, Matrix, Scalar, Vector,
+    };
+    use std::ops::{Add, Mul};
+
+    // Coordinate format, a list of (row, col, value) triplets in any order.
+    // Duplicate entries are summed when converting to another format. Meant
+    // for assembling matrices, not for arithmetic.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct CooMatrix<T = f64> {
+        pub rows: usize,
+        pub cols: usize,
+        pub row_indices: Vec<usize>,
+        pub col_indices: Vec<usize>,
+        pub values: Vec<T>,
+    }
+
+    // Compressed sparse row format. The entries of row i are
+    // `values[indptr[i]..indptr[i + 1]]`, with their columns in `indices`
+    // sorted in increasing order and without duplicates.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct CsrMatrix<T = f64> {
+        pub rows: usize,
+        pub cols: usize,
+        pub indptr: Vec<usize>,
+        pub indices: Vec<usize>,
+        pub values: Vec<T>,
+    }
+
+    // Compressed sparse column format, the same layout as CSR with the roles
+    // of rows and columns swapped
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct CscMatrix<T = f64> {
+        pub rows: usize,
+        pub cols: usize,
+        pub indptr: Vec<usize>,
+        pub indices: Vec<usize>,
+        pub values: Vec<T>,
+    }
+
+    fn check_index(index: (usize, usize), shape: (usize, usize)) -> Result<(), LinAlgError> {
+        if index.0 >= shape.0 || index.1 >= shape.1 {
+            return Err(LinAlgError::IndexOutOfBounds {
+                index: vec![index.0, index.1],
+                shape: vec![shape.0, shape.1],
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_same_size(a: (usize, usize), b: (usize, usize)) -> Result<(), LinAlgError> {
+        if a != b {
+            return Err(LinAlgError::DimensionMismatch {
+                left: vec![a.0, a.1],
+                right: vec![b.0, b.1],
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_inner(a: (usize, usize), b: (usize, usize)) -> Result<(), LinAlgError> {
+        if a.1 != b.0 {
+            return Err(LinAlgError::DimensionMismatch {
+                left: vec![a.0, a.1],
+                right: vec![b.0, b.1],
+            });
+        }
+
+        Ok(())
+    }
+
+    fn check_vector(a: (usize, usize), v: &Vector<impl Scalar>) -> Result<(), LinAlgError> {
+        if a.1 != v.size {
+            return Err(LinAlgError::DimensionMismatch {
+                left: vec![a.0, a.1],
+                right: vec![v.size],
+            });
+        }
+
+        Ok(())
+    }
+
+    // The arrays shared by the CSR and CSC layouts
+    struct Compressed<T> {
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        values: Vec<T>,
+    }
+
+    // A borrowed CSR or CSC matrix with `major` rows (or columns) of `minor`
+    // entries each, which all the arithmetic works on
+    struct CompressedView<'a, T> {
+        major: usize,
+        minor: usize,
+        indptr: &'a [usize],
+        indices: &'a [usize],
+        values: &'a [T],
+    }
+
+    impl<T: Scalar> Compressed<T> {
+        // Sorts the triplets by major then minor index and sums duplicates
+        fn from_triplets(
+            major: usize,
+            major_indices: &[usize],
+            minor_indices: &[usize],
+            values: &[T],
+        ) -> Compressed<T> {
+            let mut order: Vec<usize> = (0..values.len()).collect();
+            order.sort_by_key(|&k| (major_indices[k], minor_indices[k]));
+
+            let mut indptr = vec![0; major + 1];
+            let mut indices: Vec<usize> = Vec::with_capacity(values.len());
+            let mut data: Vec<T> = Vec::with_capacity(values.len());
+            let mut last: Option<(usize, usize)> = None;
+
+            for k in order {
+                let key = (major_indices[k], minor_indices[k]);
+
+                if last == Some(key) {
+                    let end = data.len() - 1;
+                    data[end] = data[end] + values[k];
+                } else {
+                    indices.push(key.1);
+                    data.push(values[k]);
+                    indptr[key.0 + 1] += 1;
+                    last = Some(key);
+                }
+            }
+
+            for i in 0..major {
+                indptr[i + 1] += indptr[i];
+            }
+
+            Compressed {
+                indptr,
+                indices,
+                values: data,
+            }
+        }
+    }
+
+    impl<T: Scalar> CompressedView<'_, T> {
+        fn nnz(&self) -> usize {
+            self.values.len()
+        }
+
+        fn get(&self, i: usize, j: usize) -> T {
+            let range = self.indptr[i]..self.indptr[i + 1];
+
+            match self.indices[range.clone()].binary_search(&j) {
+                Ok(k) => self.values[range.start + k],
+                Err(_) => T::zero(),
+            }
+        }
+
+        // The same matrix with major and minor swapped, i.e. CSR <-> CSC of
+        // the same entries, by a counting sort over the minor indices
+        fn swap_major(&self) -> Compressed<T> {
+            let mut indptr = vec![0; self.minor + 1];
+
+            for &j in self.indices {
+                indptr[j + 1] += 1;
+            }
+
+            for j in 0..self.minor {
+                indptr[j + 1] += indptr[j];
+            }
+
+            let mut next = indptr.clone();
+            let mut indices = vec![0; self.nnz()];
+            let mut values = vec![T::zero(); self.nnz()];
+
+            for i in 0..self.major {
+                for k in self.indptr[i]..self.indptr[i + 1] {
+                    let j = self.indices[k];
+                    indices[next[j]] = i;
+                    values[next[j]] = self.values[k];
+                    next[j] += 1;
+                }
+            }
+
+            Compressed {
+                indptr,
+                indices,
+                values,
+            }
+        }
+
+        // Merges the sorted rows of both operands, dropping exact zeros
+        fn add(&self, other: &CompressedView<T>) -> Compressed<T> {
+            let mut indptr = vec![0; self.major + 1];
+            let mut indices = Vec::with_capacity(self.nnz() + other.nnz());
+            let mut values = Vec::with_capacity(self.nnz() + other.nnz());
+
+            for i in 0..self.major {
+                let (mut p, p_end) = (self.indptr[i], self.indptr[i + 1]);
+                let (mut q, q_end) = (other.indptr[i], other.indptr[i + 1]);
+
+                while p < p_end || q < q_end {
+                    let a = if p < p_end {
+                        self.indices[p]
+                    } else {
+                        usize::MAX
+                    };
+                    let b = if q < q_end {
+                        other.indices[q]
+                    } else {
+                        usize::MAX
+                    };
+
+                    let (j, value) = if a < b {
+                        p += 1;
+                        (a, self.values[p - 1])
+                    } else if b < a {
+                        q += 1;
+                        (b, other.values[q - 1])
+                    } else {
+                        p += 1;
+                        q += 1;
+                        (a, self.values[p - 1] + other.values[q - 1])
+                    };
+
+                    if value != T::zero() {
+                        indices.push(j);
+                        values.push(value);
+                    }
+                }
+
+                indptr[i + 1] = indices.len();
+            }
+
+            Compressed {
+                indptr,
+                indices,
+                values,
+            }
+        }
+
+        // Gustavson's row-by-row product with a dense accumulator for the
+        // current row, dropping exact zeros
+        fn mul(&self, other: &CompressedView<T>) -> Compressed<T> {
+            let n = other.minor;
+            let mut accumulator = vec![T::zero(); n];
+            let mut occupied = vec![false; n];
+            let mut pattern: Vec<usize> = Vec::new();
+
+            let mut indptr = vec![0; self.major + 1];
+            let mut indices = Vec::new();
+            let mut values = Vec::new();
+
+            for i in 0..self.major {
+                for p in self.indptr[i]..self.indptr[i + 1] {
+                    let (k, a_ik) = (self.indices[p], self.values[p]);
+
+                    for q in other.indptr[k]..other.indptr[k + 1] {
+                        let j = other.indices[q];
+
+                        if !occupied[j] {
+                            occupied[j] = true;
+                            pattern.push(j);
+                        }
+
+                        accumulator[j] = accumulator[j] + a_ik * other.values[q];
+                    }
+                }
+
+                pattern.sort_unstable();
+
+                for &j in &pattern {
+                    if accumulator[j] != T::zero() {
+                        indices.push(j);
+                        values.push(accumulator[j]);
+                    }
+
+                    accumulator[j] = T::zero();
+                    occupied[j] = false;
+                }
+
+                pattern.clear();
+                indptr[i + 1] = indices.len();
+            }
+
+            Compressed {
+                indptr,
+                indices,
+                values,
+            }
+        }
+    }
+
+    impl<T: Scalar> CooMatrix<T> {
+        pub fn new(rows: usize, cols: usize) -> CooMatrix<T> {
+            CooMatrix {
+                rows,
+                cols,
+                row_indices: Vec::new(),
+                col_indices: Vec::new(),
+                values: Vec::new(),
+            }
+        }
+
+        pub fn push(&mut self, row: usize, col: usize, value: T) {
+            unwrap_or_panic(self.try_push(row, col, value))
+        }
+
+        pub fn try_push(&mut self, row: usize, col: usize, value: T) -> Result<(), LinAlgError> {
+            check_index((row, col), (self.rows, self.cols))?;
+
+            self.row_indices.push(row);
+            self.col_indices.push(col);
+            self.values.push(value);
+
+            Ok(())
+        }
+
+        pub fn from_triplets(
+            rows: usize,
+            cols: usize,
+            triplets: &[(usize, usize, T)],
+        ) -> CooMatrix<T> {
+            unwrap_or_panic(CooMatrix::try_from_triplets(rows, cols, triplets))
+        }
+
+        pub fn try_from_triplets(
+            rows: usize,
+            cols: usize,
+            triplets: &[(usize, usize, T)],
+        ) -> Result<CooMatrix<T>, LinAlgError> {
+            let mut result = CooMatrix::new(rows, cols);
+
+            for &(row, col, value) in triplets {
+                result.try_push(row, col, value)?;
+            }
+
+            return Ok(result);
+        }
+
+        // Number of stored triplets, duplicates included
+        pub fn nnz(&self) -> usize {
+            self.values.len()
+        }
+
+        pub fn transpose(&self) -> CooMatrix<T> {
+            CooMatrix {
+                rows: self.cols,
+                cols: self.rows,
+                row_indices: self.col_indices.clone(),
+                col_indices: self.row_indices.clone(),
+                values: self.values.clone(),
+            }
+        }
+
+        pub fn to_csr(&self) -> CsrMatrix<T> {
+            CsrMatrix::from_compressed(
+                self.rows,
+                self.cols,
+                Compressed::from_triplets(
+                    self.rows,
+                    &self.row_indices,
+                    &self.col_indices,
+                    &self.values,
+                ),
+            )
+        }
+
+        pub fn to_csc(&self) -> CscMatrix<T> {
+            CscMatrix::from_compressed(
+                self.rows,
+                self.cols,
+                Compressed::from_triplets(
+                    self.cols,
+                    &self.col_indices,
+                    &self.row_indices,
+                    &self.values,
+                ),
+            )
+        }
+
+        pub fn to_dense(&self) -> Matrix<T> {
+            let mut result = Matrix::zeros(self.rows, self.cols);
+
+            for k in 0..self.nnz() {
+                let index = self.row_indices[k] * self.cols + self.col_indices[k];
+                result.data[index] = result.data[index] + self.values[k];
+            }
+
+            return result;
+        }
+
+        pub fn from_dense(m: &Matrix<T>) -> CooMatrix<T> {
+            let mut result = CooMatrix::new(m.rows, m.cols);
+
+            for i in 0..m.rows {
+                for j in 0..m.cols {
+                    if m.data[i * m.cols + j] != T::zero() {
+                        result.row_indices.push(i);
+                        result.col_indices.push(j);
+                        result.values.push(m.data[i * m.cols + j]);
+                    }
+                }
+            }
+
+            return result;
+        }
+    }
+
+    impl<T: Scalar> CsrMatrix<T> {
+        pub fn new(rows: usize, cols: usize) -> CsrMatrix<T> {
+            CsrMatrix {
+                rows,
+                cols,
+                indptr: vec![0; rows + 1],
+                indices: Vec::new(),
+                values: Vec::new(),
+            }
+        }
+
+        fn from_compressed(rows: usize, cols: usize, c: Compressed<T>) -> CsrMatrix<T> {
+            CsrMatrix {
+                rows,
+                cols,
+                indptr: c.indptr,
+                indices: c.indices,
+                values: c.values,
+            }
+        }
+
+        fn compressed(&self) -> CompressedView<'_, T> {
+            CompressedView {
+                major: self.rows,
+                minor: self.cols,
+                indptr: &self.indptr,
+                indices: &self.indices,
+                values: &self.values,
+            }
+        }
+
+        pub fn nnz(&self) -> usize {
+            self.values.len()
+        }
+
+        pub fn get(&self, row: usize, col: usize) -> T {
+            unwrap_or_panic(self.try_get(row, col))
+        }
+
+        pub fn try_get(&self, row: usize, col: usize) -> Result<T, LinAlgError> {
+            check_index((row, col), (self.rows, self.cols))?;
+
+            Ok(self.compressed().get(row, col))
+        }
+
+        pub fn from_dense(m: &Matrix<T>) -> CsrMatrix<T> {
+            CooMatrix::from_dense(m).to_csr()
+        }
+
+        pub fn to_dense(&self) -> Matrix<T> {
+            let mut result = Matrix::zeros(self.rows, self.cols);
+
+            for i in 0..self.rows {
+                for k in self.indptr[i]..self.indptr[i + 1] {
+                    result.data[i * self.cols + self.indices[k]] = self.values[k];
+                }
+            }
+
+            return result;
+        }
+
+        pub fn to_coo(&self) -> CooMatrix<T> {
+            let mut result = CooMatrix::new(self.rows, self.cols);
+
+            for i in 0..self.rows {
+                for k in self.indptr[i]..self.indptr[i + 1] {
+                    result.row_indices.push(i);
+                    result.col_indices.push(self.indices[k]);
+                    result.values.push(self.values[k]);
+                }
+            }
+
+            return result;
+        }
+
+        pub fn to_csc(&self) -> CscMatrix<T> {
+            CscMatrix::from_compressed(self.rows, self.cols, self.compressed().swap_major())
+        }
+
+        pub fn transpose(&self) -> CsrMatrix<T> {
+            CsrMatrix::from_compressed(self.cols, self.rows, self.compressed().swap_major())
+        }
+
+        pub fn csr_vector_mul(a: &CsrMatrix<T>, v: &Vector<T>) -> Vector<T> {
+            unwrap_or_panic(CsrMatrix::try_csr_vector_mul(a, v))
+        }
+
+        pub fn try_csr_vector_mul(
+            a: &CsrMatrix<T>,
+            v: &Vector<T>,
+        ) -> Result<Vector<T>, LinAlgError> {
+            check_vector((a.rows, a.cols), v)?;
+
+            let mut result = Vector::zeros(a.rows);
+
+            for i in 0..a.rows {
+                let mut sum = T::zero();
+
+                for k in a.indptr[i]..a.indptr[i + 1] {
+                    sum = sum + a.values[k] * v.data[a.indices[k]];
+                }
+
+                result.data[i] = sum;
+            }
+
+            return Ok(result);
+        }
+
+        pub fn csr_matrix_mul(a: &CsrMatrix<T>, b: &Matrix<T>) -> Matrix<T> {
+            unwrap_or_panic(CsrMatrix::try_csr_matrix_mul(a, b))
+        }
+
+        // Sparse times dense, adding scaled rows of `b` into each result row
+        pub fn try_csr_matrix_mul(
+            a: &CsrMatrix<T>,
+            b: &Matrix<T>,
+        ) -> Result<Matrix<T>, LinAlgError> {
+            check_inner((a.rows, a.cols), (b.rows, b.cols))?;
+
+            let n = b.cols;
+            let mut result = Matrix::zeros(a.rows, n);
+
+            for i in 0..a.rows {
+                for k in a.indptr[i]..a.indptr[i + 1] {
+                    let (a_ik, row) = (a.values[k], a.indices[k]);
+
+                    for j in 0..n {
+                        result.data[i * n + j] =
+                            result.data[i * n + j] + a_ik * b.data[row * n + j];
+                    }
+                }
+            }
+
+            return Ok(result);
+        }
+
+        pub fn csr_add(a: &CsrMatrix<T>, b: &CsrMatrix<T>) -> CsrMatrix<T> {
+            unwrap_or_panic(CsrMatrix::try_csr_add(a, b))
+        }
+
+        pub fn try_csr_add(
+            a: &CsrMatrix<T>,
+            b: &CsrMatrix<T>,
+        ) -> Result<CsrMatrix<T>, LinAlgError> {
+            check_same_size((a.rows, a.cols), (b.rows, b.cols))?;
+
+            Ok(CsrMatrix::from_compressed(
+                a.rows,
+                a.cols,
+                a.compressed().add(&b.compressed()),
+            ))
+        }
+
+        pub fn csr_mul(a: &CsrMatrix<T>, b: &CsrMatrix<T>) -> CsrMatrix<T> {
+            unwrap_or_panic(CsrMatrix::try_csr_mul(a, b))
+        }
+
+        pub fn try_csr_mul(
+            a: &CsrMatrix<T>,
+            b: &CsrMatrix<T>,
+        ) -> Result<CsrMatrix<T>, LinAlgError> {
+            check_inner((a.rows, a.cols), (b.rows, b.cols))?;
+
+            Ok(CsrMatrix::from_compressed(
+                a.rows,
+                b.cols,
+                a.compressed().mul(&b.compressed()),
+            ))
+        }
+    }
+
+    // CSC operations run on the CSR arrays of the transpose, since the CSC
+    // arrays of A are exactly the CSR arrays of A^T
+    impl<T: Scalar> CscMatrix<T> {
+        pub fn new(rows: usize, cols: usize) -> CscMatrix<T> {
+            CscMatrix {
+                rows,
+                cols,
+                indptr: vec![0; cols + 1],
+                indices: Vec::new(),
+                values: Vec::new(),
+            }
+        }
+
+        fn from_compressed(rows: usize, cols: usize, c: Compressed<T>) -> CscMatrix<T> {
+            CscMatrix {
+                rows,
+                cols,
+                indptr: c.indptr,
+                indices: c.indices,
+                values: c.values,
+            }
+        }
+
+        fn compressed(&self) -> CompressedView<'_, T> {
+            CompressedView {
+                major: self.cols,
+                minor: self.rows,
+                indptr: &self.indptr,
+                indices: &self.indices,
+                values: &self.values,
+            }
+        }
+
+        pub fn nnz(&self) -> usize {
+            self.values.len()
+        }
+
+        pub fn get(&self, row: usize, col: usize) -> T {
+            unwrap_or_panic(self.try_get(row, col))
+        }
+
+        pub fn try_get(&self, row: usize, col: usize) -> Result<T, LinAlgError> {
+            check_index((row, col), (self.rows, self.cols))?;
+
+            Ok(self.compressed().get(col, row))
+        }
+
+        pub fn from_dense(m: &Matrix<T>) -> CscMatrix<T> {
+            CooMatrix::from_dense(m).to_csc()
+        }
+
+        pub fn to_dense(&self) -> Matrix<T> {
+            let mut result = Matrix::zeros(self.rows, self.cols);
+
+            for j in 0..self.cols {
+                for k in self.indptr[j]..self.indptr[j + 1] {
+                    result.data[self.indices[k] * self.cols + j] = self.values[k];
+                }
+            }
+
+            return result;
+        }
+
+        pub fn to_coo(&self) -> CooMatrix<T> {
+            return self.to_csr().to_coo();
+        }
+
+        pub fn to_csr(&self) -> CsrMatrix<T> {
+            CsrMatrix::from_compressed(self.rows, self.cols, self.compressed().swap_major())
+        }
+
+        pub fn transpose(&self) -> CscMatrix<T> {
+            CscMatrix::from_compressed(self.cols, self.rows, self.compressed().swap_major())
+        }
+
+        pub fn csc_vector_mul(a: &CscMatrix<T>, v: &Vector<T>) -> Vector<T> {
+            unwrap_or_panic(CscMatrix::try_csc_vector_mul(a, v))
+        }
+
+        // Accumulates v_j times column j
+        pub fn try_csc_vector_mul(
+            a: &CscMatrix<T>,
+            v: &Vector<T>,
+        ) -> Result<Vector<T>, LinAlgError> {
+            check_vector((a.rows, a.cols), v)?;
+
+            let mut result = Vector::zeros(a.rows);
+
+            for j in 0..a.cols {
+                for k in a.indptr[j]..a.indptr[j + 1] {
+                    let i = a.indices[k];
+                    result.data[i] = result.data[i] + a.values[k] * v.data[j];
+                }
+            }
+
+            return Ok(result);
+        }
+
+        pub fn csc_matrix_mul(a: &CscMatrix<T>, b: &Matrix<T>) -> Matrix<T> {
+            unwrap_or_panic(CscMatrix::try_csc_matrix_mul(a, b))
+        }
+
+        pub fn try_csc_matrix_mul(
+            a: &CscMatrix<T>,
+            b: &Matrix<T>,
+        ) -> Result<Matrix<T>, LinAlgError> {
+            check_inner((a.rows, a.cols), (b.rows, b.cols))?;
+
+            let n = b.cols;
+            let mut result = Matrix::zeros(a.rows, n);
+
+            for k in 0..a.cols {
+                for p in a.indptr[k]..a.indptr[k + 1] {
+                    let (i, a_ik) = (a.indices[p], a.values[p]);
+
+                    for j in 0..n {
+                        result.data[i * n + j] = result.data[i * n + j] + a_ik * b.data[k * n + j];
+                    }
+                }
+            }
+
+            return Ok(result);
+        }
+
+        pub fn csc_add(a: &CscMatrix<T>, b: &CscMatrix<T>) -> CscMatrix<T> {
+            unwrap_or_panic(CscMatrix::try_csc_add(a, b))
+        }
+
+        pub fn try_csc_add(
+            a: &CscMatrix<T>,
+            b: &CscMatrix<T>,
+        ) -> Result<CscMatrix<T>, LinAlgError> {
+            check_same_size((a.rows, a.cols), (b.rows, b.cols))?;
+
+            Ok(CscMatrix::from_compressed(
+                a.rows,
+                a.cols,
+                a.compressed().add(&b.compressed()),
+            ))
+        }
+
+        pub fn csc_mul(a: &CscMatrix<T>, b: &CscMatrix<T>) -> CscMatrix<T> {
+            unwrap_or_panic(CscMatrix::try_csc_mul(a, b))
+        }
+
+        // (AB)^T = B^T A^T, so the CSR product of B^T and A^T gives the CSC
+        // arrays of AB
+        pub fn try_csc_mul(
+            a: &CscMatrix<T>,
+            b: &CscMatrix<T>,
+        ) -> Result<CscMatrix<T>, LinAlgError> {
+            check_inner((a.rows, a.cols), (b.rows, b.cols))?;
+
+            Ok(CscMatrix::from_compressed(
+                a.rows,
+                b.cols,
+                b.compressed().mul(&a.compressed()),
+            ))
+        }
+    }
+
+    macro_rules! impl_sparse_op {
+        ($trait:ident, $method:ident, $lhs:ident, $rhs:ident, $out:ident, $func:path) => {
+            impl<T: Scalar> $trait<&$rhs<T>> for &$lhs<T> {
+                type Output = $out<T>;
+
+                fn $method(self, rhs: &$rhs<T>) -> $out<T> {
+                    $func(self, rhs)
+                }
+            }
+
+            impl<T: Scalar> $trait<$rhs<T>> for $lhs<T> {
+                type Output = $out<T>;
+
+                fn $method(self, rhs: $rhs<T>) -> $out<T> {
+                    $func(&self, &rhs)
+                }
+            }
+        };
+    }
+
+    impl_sparse_op!(
+        Add,
+        add,
+        CsrMatrix,
+        CsrMatrix,
+        CsrMatrix,
+        CsrMatrix::csr_add
+    );
+    impl_sparse_op!(
+        Mul,
+        mul,
+        CsrMatrix,
+        CsrMatrix,
+        CsrMatrix,
+        CsrMatrix::csr_mul
+    );
+    impl_sparse_op!(
+        Mul,
+        mul,
+        CsrMatrix,
+        Vector,
+        Vector,
+        CsrMatrix::csr_vector_mul
+    );
+    impl_sparse_op!(
+        Mul,
+        mul,
+        CsrMatrix,
+        Matrix,
+        Matrix,
+        CsrMatrix::csr_matrix_mul
+    );
+
+    impl_sparse_op!(
+        Add,
+        add,
+        CscMatrix,
+        CscMatrix,
+        CscMatrix,
+        CscMatrix::csc_add
+    );
+    impl_sparse_op!(
+        Mul,
+        mul,
+        CscMatrix,
+        CscMatrix,
+        CscMatrix,
+        CscMatrix::csc_mul
+    );
+    impl_sparse_op!(
+        Mul,
+        mul,
+        CscMatrix,
+        Vector,
+        Vector,
+        CscMatrix::csc_vector_mul
+    );
+    impl_sparse_op!(
+        Mul,
+        mul,
+        CscMatrix,
+        Matrix,
+        Matrix,
+        CscMatrix::csc_matrix_mul
+    );
+}
@@ -0,0 +1,363 @@
+// src/static_matrix.rs
+
+pub mod static_matrix {
+    use crate::linear_algebra::linear_algebra::{
+        unwrap_or_panic, LinAlgError, Matrix, Real, Scalar, Vector,
+    };
+    use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+
+    // Fixed-size vectors and matrices. The dimensions are const generic
+    // parameters and the elements live inline in arrays, so these are
+    // `Copy`, never touch the heap, and a product of mismatched shapes is a
+    // type error rather than a panic. Like the dynamic containers they
+    // default to `f64` elements.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct SVector<const N: usize, T = f64> {
+        pub data: [T; N],
+    }
+
+    // Row-major, element (i, j) lives at `data[i][j]`
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct SMatrix<const R: usize, const C: usize, T = f64> {
+        pub data: [[T; C]; R],
+    }
+
+    /* Static vector functions */
+    impl<const N: usize, T: Scalar> SVector<N, T> {
+        pub fn new(data: [T; N]) -> SVector<N, T> {
+            SVector { data }
+        }
+
+        pub fn zeros() -> SVector<N, T> {
+            SVector {
+                data: [T::zero(); N],
+            }
+        }
+
+        pub fn dot(&self, other: &SVector<N, T>) -> T {
+            let mut result = T::zero();
+
+            for i in 0..N {
+                result = result + self.data[i] * other.data[i];
+            }
+
+            return result;
+        }
+
+        pub fn to_vector(&self) -> Vector<T> {
+            Vector {
+                size: N,
+                data: self.data.to_vec(),
+            }
+        }
+
+        pub fn from_vector(v: &Vector<T>) -> SVector<N, T> {
+            unwrap_or_panic(SVector::try_from_vector(v))
+        }
+
+        pub fn try_from_vector(v: &Vector<T>) -> Result<SVector<N, T>, LinAlgError> {
+            if v.size != N {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![v.size],
+                    right: vec![N],
+                });
+            }
+
+            let mut result = SVector::zeros();
+            result.data.copy_from_slice(&v.data);
+
+            return Ok(result);
+        }
+
+        fn map(self, f: impl Fn(T) -> T) -> SVector<N, T> {
+            SVector::new(self.data.map(f))
+        }
+
+        fn zip_with(self, other: SVector<N, T>, f: impl Fn(T, T) -> T) -> SVector<N, T> {
+            SVector::new(std::array::from_fn(|i| f(self.data[i], other.data[i])))
+        }
+    }
+
+    impl<const N: usize, T: Real> SVector<N, T> {
+        pub fn norm(&self) -> T {
+            self.dot(self).sqrt()
+        }
+    }
+
+    /* Static matrix functions */
+    impl<const R: usize, const C: usize, T: Scalar> SMatrix<R, C, T> {
+        pub fn new(data: [[T; C]; R]) -> SMatrix<R, C, T> {
+            SMatrix { data }
+        }
+
+        pub fn zeros() -> SMatrix<R, C, T> {
+            SMatrix {
+                data: [[T::zero(); C]; R],
+            }
+        }
+
+        pub fn transpose(&self) -> SMatrix<C, R, T> {
+            let mut result = SMatrix::zeros();
+
+            for i in 0..R {
+                for j in 0..C {
+                    result.data[j][i] = self.data[i][j];
+                }
+            }
+
+            return result;
+        }
+
+        pub fn row(&self, i: usize) -> SVector<C, T> {
+            SVector::new(self.data[i])
+        }
+
+        pub fn column(&self, j: usize) -> SVector<R, T> {
+            let mut result = SVector::zeros();
+
+            for i in 0..R {
+                result.data[i] = self.data[i][j];
+            }
+
+            return result;
+        }
+
+        pub fn to_matrix(&self) -> Matrix<T> {
+            Matrix::from_vec(R, C, self.data.concat())
+        }
+
+        pub fn from_matrix(m: &Matrix<T>) -> SMatrix<R, C, T> {
+            unwrap_or_panic(SMatrix::try_from_matrix(m))
+        }
+
+        pub fn try_from_matrix(m: &Matrix<T>) -> Result<SMatrix<R, C, T>, LinAlgError> {
+            if m.rows != R || m.cols != C {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![m.rows, m.cols],
+                    right: vec![R, C],
+                });
+            }
+
+            let mut result = SMatrix::zeros();
+
+            for i in 0..R {
+                result.data[i].copy_from_slice(&m.data[i * C..(i + 1) * C]);
+            }
+
+            return Ok(result);
+        }
+
+        fn map(self, f: impl Fn(T) -> T) -> SMatrix<R, C, T> {
+            SMatrix::new(self.data.map(|row| row.map(&f)))
+        }
+
+        fn zip_with(self, other: SMatrix<R, C, T>, f: impl Fn(T, T) -> T) -> SMatrix<R, C, T> {
+            SMatrix::new(std::array::from_fn(|i| {
+                std::array::from_fn(|j| f(self.data[i][j], other.data[i][j]))
+            }))
+        }
+    }
+
+    impl<const N: usize, T: Scalar> SMatrix<N, N, T> {
+        pub fn identity() -> SMatrix<N, N, T> {
+            let mut result = SMatrix::zeros();
+
+            for i in 0..N {
+                result.data[i][i] = T::one();
+            }
+
+            return result;
+        }
+
+        pub fn trace(&self) -> T {
+            let mut result = T::zero();
+
+            for i in 0..N {
+                result = result + self.data[i][i];
+            }
+
+            return result;
+        }
+    }
+
+    impl<const N: usize, T: Scalar> From<SVector<N, T>> for Vector<T> {
+        fn from(v: SVector<N, T>) -> Vector<T> {
+            v.to_vector()
+        }
+    }
+
+    impl<const R: usize, const C: usize, T: Scalar> From<SMatrix<R, C, T>> for Matrix<T> {
+        fn from(m: SMatrix<R, C, T>) -> Matrix<T> {
+            m.to_matrix()
+        }
+    }
+
+    /* Operators */
+
+    /// The inner dimension K is shared by both operands, so only conforming
+    /// products type check:
+    ///
+    /// ```compile_fail
+    /// use LAMS_rs::static_matrix::static_matrix::SMatrix;
+    ///
+    /// let a: SMatrix<2, 3> = SMatrix::zeros();
+    /// let _ = a * a;
+    /// ```
+    impl<const R: usize, const K: usize, const C: usize, T: Scalar> Mul<SMatrix<K, C, T>>
+        for SMatrix<R, K, T>
+    {
+        type Output = SMatrix<R, C, T>;
+
+        fn mul(self, rhs: SMatrix<K, C, T>) -> SMatrix<R, C, T> {
+            let mut result = SMatrix::zeros();
+
+            for i in 0..R {
+                for k in 0..K {
+                    let a = self.data[i][k];
+
+                    for j in 0..C {
+                        result.data[i][j] = result.data[i][j] + a * rhs.data[k][j];
+                    }
+                }
+            }
+
+            return result;
+        }
+    }
+
+    impl<const R: usize, const C: usize, T: Scalar> Mul<SVector<C, T>> for SMatrix<R, C, T> {
+        type Output = SVector<R, T>;
+
+        fn mul(self, rhs: SVector<C, T>) -> SVector<R, T> {
+            let mut result = SVector::zeros();
+
+            for i in 0..R {
+                result.data[i] = self.row(i).dot(&rhs);
+            }
+
+            return result;
+        }
+    }
+
+    impl<const N: usize, T: Scalar> MulAssign<SMatrix<N, N, T>> for SMatrix<N, N, T> {
+        fn mul_assign(&mut self, rhs: SMatrix<N, N, T>) {
+            *self = *self * rhs;
+        }
+    }
+
+    // Elementwise arithmetic, written once for both shapes in terms of
+    // their `map` and `zip_with`
+    macro_rules! impl_static_ops {
+        ($t:ident, $($dim:ident),*) => {
+            impl<$(const $dim: usize,)* T: Scalar> Add for $t<$($dim,)* T> {
+                type Output = $t<$($dim,)* T>;
+
+                fn add(self, rhs: $t<$($dim,)* T>) -> $t<$($dim,)* T> {
+                    self.zip_with(rhs, |a, b| a + b)
+                }
+            }
+
+            impl<$(const $dim: usize,)* T: Scalar> Sub for $t<$($dim,)* T> {
+                type Output = $t<$($dim,)* T>;
+
+                fn sub(self, rhs: $t<$($dim,)* T>) -> $t<$($dim,)* T> {
+                    self.zip_with(rhs, |a, b| a - b)
+                }
+            }
+
+            impl<$(const $dim: usize,)* T: Scalar> Mul<T> for $t<$($dim,)* T> {
+                type Output = $t<$($dim,)* T>;
+
+                fn mul(self, rhs: T) -> $t<$($dim,)* T> {
+                    self.map(|a| a * rhs)
+                }
+            }
+
+            impl<$(const $dim: usize,)* T: Scalar + Neg<Output = T>> Neg for $t<$($dim,)* T> {
+                type Output = $t<$($dim,)* T>;
+
+                fn neg(self) -> $t<$($dim,)* T> {
+                    self.map(|a| -a)
+                }
+            }
+
+            impl<$(const $dim: usize,)* T: Scalar> AddAssign for $t<$($dim,)* T> {
+                fn add_assign(&mut self, rhs: $t<$($dim,)* T>) {
+                    *self = *self + rhs;
+                }
+            }
+
+            impl<$(const $dim: usize,)* T: Scalar> SubAssign for $t<$($dim,)* T> {
+                fn sub_assign(&mut self, rhs: $t<$($dim,)* T>) {
+                    *self = *self - rhs;
+                }
+            }
+
+            impl<$(const $dim: usize,)* T: Scalar> MulAssign<T> for $t<$($dim,)* T> {
+                fn mul_assign(&mut self, rhs: T) {
+                    *self = *self * rhs;
+                }
+            }
+        };
+    }
+
+    // Scaling by a primitive on the left, one primitive at a time because
+    // the dimensions and the primitives cannot share a repetition
+    macro_rules! impl_static_left_scalar_mul {
+        ($t:ident, [$($dim:ident),*];) => {};
+        ($t:ident, [$($dim:ident),*]; $s:ty $(, $rest:ty)*) => {
+            impl<$(const $dim: usize),*> Mul<$t<$($dim,)* $s>> for $s {
+                type Output = $t<$($dim,)* $s>;
+
+                fn mul(self, rhs: $t<$($dim,)* $s>) -> $t<$($dim,)* $s> {
+                    rhs * self
+                }
+            }
+
+            impl_static_left_scalar_mul!($t, [$($dim),*]; $($rest),*);
+        };
+    }
+
+    macro_rules! impl_all_static_left_scalar_mul {
+        ($t:ident, $($dim:ident),*) => {
+            impl_static_left_scalar_mul!(
+                $t, [$($dim),*]; f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64,
+                u128, usize
+            );
+        };
+    }
+
+    impl_static_ops!(SVector, N);
+    impl_all_static_left_scalar_mul!(SVector, N);
+
+    impl_static_ops!(SMatrix, R, C);
+    impl_all_static_left_scalar_mul!(SMatrix, R, C);
+
+    impl<const N: usize, T> Index<usize> for SVector<N, T> {
+        type Output = T;
+
+        fn index(&self, i: usize) -> &T {
+            &self.data[i]
+        }
+    }
+
+    impl<const N: usize, T> IndexMut<usize> for SVector<N, T> {
+        fn index_mut(&mut self, i: usize) -> &mut T {
+            &mut self.data[i]
+        }
+    }
+
+    impl<const R: usize, const C: usize, T> Index<(usize, usize)> for SMatrix<R, C, T> {
+        type Output = T;
+
+        fn index(&self, (i, j): (usize, usize)) -> &T {
+            &self.data[i][j]
+        }
+    }
+
+    impl<const R: usize, const C: usize, T> IndexMut<(usize, usize)> for SMatrix<R, C, T> {
+        fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+            &mut self.data[i][j]
+        }
+    }
+}
@@ -0,0 +1,341 @@
+// src/svd.rs
+
+pub mod svd {
+    use crate::linear_algebra::linear_algebra::{LinAlgError, Matrix, Vector};
+
+    // Maximum number of Jacobi sweeps over all column pairs
+    const MAX_SWEEPS: usize = 60;
+
+    // Singular value decomposition A = U diag(singular_values) V^T, with the
+    // singular values sorted in descending order. With k = min(m, n), the
+    // thin form has U m x k and V n x k, the full form U m x m and V n x n.
+    #[derive(Debug, Clone)]
+    pub struct SVD {
+        pub u: Matrix,
+        pub singular_values: Vector,
+        pub v: Matrix,
+    }
+
+    impl SVD {
+        pub fn new(a: &Matrix) -> Result<SVD, LinAlgError> {
+            return decompose(a, false);
+        }
+
+        pub fn full(a: &Matrix) -> Result<SVD, LinAlgError> {
+            return decompose(a, true);
+        }
+
+        // Shape (m, n) of the decomposed matrix
+        pub fn shape(&self) -> (usize, usize) {
+            (self.u.rows, self.v.rows)
+        }
+
+        // Singular values above max(m, n) * eps * sigma_max unless a tolerance
+        // is given
+        pub fn rank(&self, tolerance: Option<f64>) -> usize {
+            let tolerance = tolerance.unwrap_or(self.default_tolerance());
+
+            self.singular_values
+                .data
+                .iter()
+                .take_while(|&&s| s > tolerance)
+                .count()
+        }
+
+        fn default_tolerance(&self) -> f64 {
+            let sigma_max = self.singular_values.data.first().copied().unwrap_or(0.0);
+
+            let (m, n) = self.shape();
+
+            return m.max(n) as f64 * f64::EPSILON * sigma_max;
+        }
+
+        // The 2-norm, sigma_max
+        pub fn norm2(&self) -> f64 {
+            self.singular_values.data.first().copied().unwrap_or(0.0)
+        }
+
+        // sigma_max / sigma_min, infinite for rank deficient matrices
+        pub fn condition_number(&self) -> f64 {
+            match self.singular_values.data.last() {
+                Some(&sigma_min) if sigma_min > 0.0 => self.norm2() / sigma_min,
+                Some(_) => f64::INFINITY,
+                None => 0.0,
+            }
+        }
+
+        // Moore-Penrose pseudo-inverse V diag(1 / sigma) U^T, with singular
+        // values at or below the rank tolerance treated as zero
+        pub fn pinv(&self, tolerance: Option<f64>) -> Matrix {
+            let (m, n) = self.shape();
+            let rank = self.rank(tolerance);
+            let (ku, kv) = (self.u.cols, self.v.cols);
+            let mut result = Matrix::new(n, m);
+
+            for i in 0..n {
+                for j in 0..m {
+                    let mut sum = 0.0;
+
+                    for k in 0..rank {
+                        sum += self.v.data[i * kv + k] * self.u.data[j * ku + k]
+                            / self.singular_values.data[k];
+                    }
+
+                    result.data[i * m + j] = sum;
+                }
+            }
+
+            return result;
+        }
+
+        // Best rank-k approximation in both the 2-norm and the Frobenius norm
+        pub fn truncate(&self, k: usize) -> Matrix {
+            let (m, n) = self.shape();
+            let k = k.min(self.singular_values.size);
+            let (ku, kv) = (self.u.cols, self.v.cols);
+            let mut result = Matrix::new(m, n);
+
+            for i in 0..m {
+                for j in 0..n {
+                    let mut sum = 0.0;
+
+                    for l in 0..k {
+                        sum += self.u.data[i * ku + l]
+                            * self.singular_values.data[l]
+                            * self.v.data[j * kv + l];
+                    }
+
+                    result.data[i * n + j] = sum;
+                }
+            }
+
+            return result;
+        }
+
+        pub fn reconstruct(&self) -> Matrix {
+            return self.truncate(self.singular_values.size);
+        }
+
+        // Minimum norm least squares solution of Ax = b
+        pub fn solve_least_squares(
+            &self,
+            b: &Vector,
+            tolerance: Option<f64>,
+        ) -> Result<Vector, LinAlgError> {
+            let (m, n) = self.shape();
+
+            if b.size != m {
+                return Err(LinAlgError::DimensionMismatch {
+                    left: vec![m, n],
+                    right: vec![b.size],
+                });
+            }
+
+            let rank = self.rank(tolerance);
+            let (ku, kv) = (self.u.cols, self.v.cols);
+            let mut result = Vector::new(n);
+
+            for k in 0..rank {
+                let mut coefficient = 0.0;
+
+                for i in 0..m {
+                    coefficient += self.u.data[i * ku + k] * b.data[i];
+                }
+
+                coefficient /= self.singular_values.data[k];
+
+                for j in 0..n {
+                    result.data[j] += coefficient * self.v.data[j * kv + k];
+                }
+            }
+
+            return Ok(result);
+        }
+    }
+
+    pub fn pinv(a: &Matrix, tolerance: Option<f64>) -> Result<Matrix, LinAlgError> {
+        return Ok(SVD::new(a)?.pinv(tolerance));
+    }
+
+    pub fn rank(a: &Matrix, tolerance: Option<f64>) -> Result<usize, LinAlgError> {
+        return Ok(SVD::new(a)?.rank(tolerance));
+    }
+
+    pub fn condition_number(a: &Matrix) -> Result<f64, LinAlgError> {
+        return Ok(SVD::new(a)?.condition_number());
+    }
+
+    pub fn norm2(a: &Matrix) -> Result<f64, LinAlgError> {
+        return Ok(SVD::new(a)?.norm2());
+    }
+
+    fn decompose(a: &Matrix, full: bool) -> Result<SVD, LinAlgError> {
+        // Wide matrices are handled through A^T = V S U^T
+        if a.rows < a.cols {
+            let transposed = decompose(&a.view().transpose().to_matrix(), full)?;
+
+            return Ok(SVD {
+                u: transposed.v,
+                singular_values: transposed.singular_values,
+                v: transposed.u,
+            });
+        }
+
+        let (m, n) = (a.rows, a.cols);
+        let mut w = a.clone();
+        let mut v = Matrix::new(n, n);
+
+        for i in 0..n {
+            v.data[i * n + i] = 1.0;
+        }
+
+        one_sided_jacobi(&mut w, &mut v)?;
+
+        // The columns of W are now mutually orthogonal, W = U S
+        let norms: Vec<f64> = (0..n)
+            .map(|j| {
+                (0..m)
+                    .map(|i| w.data[i * n + j].powi(2))
+                    .sum::<f64>()
+                    .sqrt()
+            })
+            .collect();
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&x, &y| norms[y].total_cmp(&norms[x]));
+
+        let u_cols = if full { m } else { n };
+        let mut u = Matrix::new(m, u_cols);
+        let mut filled = vec![false; u_cols];
+        let mut singular_values = Vector::new(n);
+        let mut sorted_v = Matrix::new(n, n);
+
+        for (k, &j) in order.iter().enumerate() {
+            singular_values.data[k] = norms[j];
+
+            for i in 0..n {
+                sorted_v.data[i * n + k] = v.data[i * n + j];
+            }
+
+            if norms[j] > 0.0 {
+                for i in 0..m {
+                    u.data[i * u_cols + k] = w.data[i * n + j] / norms[j];
+                }
+
+                filled[k] = true;
+            }
+        }
+
+        complete_basis(&mut u, &filled);
+
+        Ok(SVD {
+            u,
+            singular_values,
+            v: sorted_v,
+        })
+    }
+
+    // Hestenes' one-sided Jacobi: rotates pairs of columns of W (accumulating
+    // the rotations in V) until all columns are orthogonal to working
+    // precision. Small singular values come out with high relative accuracy.
+    fn one_sided_jacobi(w: &mut Matrix, v: &mut Matrix) -> Result<(), LinAlgError> {
+        let (m, n) = (w.rows, w.cols);
+
+        for _ in 0..MAX_SWEEPS {
+            let mut rotated = false;
+
+            for p in 0..n {
+                for q in p + 1..n {
+                    let (mut alpha, mut beta, mut gamma) = (0.0, 0.0, 0.0);
+
+                    for i in 0..m {
+                        let (x, y) = (w.data[i * n + p], w.data[i * n + q]);
+                        alpha += x * x;
+                        beta += y * y;
+                        gamma += x * y;
+                    }
+
+                    if gamma == 0.0 || gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt() {
+                        continue;
+                    }
+
+                    rotated = true;
+
+                    let zeta = (beta - alpha) / (2.0 * gamma);
+                    let t = zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+                    let c = 1.0 / (1.0 + t * t).sqrt();
+                    let s = c * t;
+
+                    rotate_columns(w, p, q, c, s);
+                    rotate_columns(v, p, q, c, s);
+                }
+            }
+
+            if !rotated {
+                return Ok(());
+            }
+        }
+
+        Err(LinAlgError::NonConvergence {
+            iterations: MAX_SWEEPS,
+        })
+    }
+
+    fn rotate_columns(a: &mut Matrix, p: usize, q: usize, c: f64, s: f64) {
+        let n = a.cols;
+
+        for i in 0..a.rows {
+            let (x, y) = (a.data[i * n + p], a.data[i * n + q]);
+            a.data[i * n + p] = c * x - s * y;
+            a.data[i * n + q] = s * x + c * y;
+        }
+    }
+
+    // Fills the unset columns of `u` with unit vectors orthogonal to all other
+    // columns, taken from the standard basis by Gram-Schmidt. Each column
+    // uses the basis vector with the largest residual, which is at least
+    // 1/sqrt(m) since the squared residuals of all m sum to the number of
+    // directions still missing.
+    fn complete_basis(u: &mut Matrix, filled: &[bool]) {
+        let (m, cols) = (u.rows, u.cols);
+        let mut done: Vec<usize> = (0..cols).filter(|&j| filled[j]).collect();
+
+        for j in 0..cols {
+            if filled[j] {
+                continue;
+            }
+
+            let mut best = (vec![0.0; m], 0.0);
+
+            for candidate in 0..m {
+                let mut x = vec![0.0; m];
+                x[candidate] = 1.0;
+
+                // Orthogonalize twice for numerical safety
+                for _ in 0..2 {
+                    for &k in &done {
+                        let dot: f64 = (0..m).map(|i| u.data[i * cols + k] * x[i]).sum();
+
+                        for i in 0..m {
+                            x[i] -= dot * u.data[i * cols + k];
+                        }
+                    }
+                }
+
+                let norm = x.iter().map(|x| x * x).sum::<f64>().sqrt();
+
+                if norm > best.1 {
+                    best = (x, norm);
+                }
+            }
+
+            let (x, norm) = best;
+
+            for i in 0..m {
+                u.data[i * cols + j] = x[i] / norm;
+            }
+
+            done.push(j);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+// tests/common/mod.rs
+
+// Helpers shared by the integration tests. Each test binary compiles its
+// own copy and uses only some of them.
+#![allow(dead_code)]
+
+use LAMS_rs::linear_algebra::*;
+
+pub fn assert_close(actual: &[f64], expected: &[f64], tolerance: f64) {
+    assert_eq!(actual.len(), expected.len());
+
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!(
+            (a - e).abs() <= tolerance,
+            "{:?} != {:?} (tolerance {})",
+            actual,
+            expected,
+            tolerance
+        );
+    }
+}
+
+pub fn identity(n: usize) -> linear_algebra::Matrix {
+    let mut m = linear_algebra::Matrix::new(n, n);
+
+    for i in 0..n {
+        m[(i, i)] = 1.0;
+    }
+
+    m
+}
+
+pub fn transpose(a: &linear_algebra::Matrix) -> linear_algebra::Matrix {
+    a.view().transpose().to_matrix()
+}
+
+// Q^T Q = I
+pub fn assert_orthonormal_columns(q: &linear_algebra::Matrix) {
+    assert_close(&(&transpose(q) * q).data, &identity(q.cols).data, 1e-12);
+}
@@ -0,0 +1,252 @@
+// tests/complex_tests.rs
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use LAMS_rs::complex::*;
+use LAMS_rs::eigen::*;
+use LAMS_rs::linear_algebra::*;
+
+fn assert_complex_close(actual: complex::Complex64, expected: complex::Complex64, tolerance: f64) {
+    assert!(
+        (actual - expected).norm() <= tolerance,
+        "{} != {} (tolerance {})",
+        actual,
+        expected,
+        tolerance
+    );
+}
+
+fn c(re: f64, im: f64) -> complex::Complex64 {
+    complex::Complex64::new(re, im)
+}
+
+#[test]
+fn test_complex_arithmetic() {
+    let z = c(3.0, 4.0);
+    let w = c(1.0, -2.0);
+
+    assert_eq!(z + w, c(4.0, 2.0));
+    assert_eq!(z - w, c(2.0, 6.0));
+    assert_eq!(z * w, c(11.0, -2.0));
+    assert_eq!(z / w, c(-1.0, 2.0));
+    assert_eq!(-z, c(-3.0, -4.0));
+    assert_eq!(z.conj(), c(3.0, -4.0));
+    assert_eq!(
+        complex::Complex64::i() * complex::Complex64::i(),
+        c(-1.0, 0.0)
+    );
+
+    // Mixed with reals on either side
+    assert_eq!(z * 2.0, c(6.0, 8.0));
+    assert_eq!(2.0 * z, c(6.0, 8.0));
+    assert_eq!(1.0 - z, c(-2.0, -4.0));
+    assert_eq!(z / 2.0, c(1.5, 2.0));
+    assert_complex_close(10.0 / c(1.0, 3.0), c(1.0, -3.0), 1e-15);
+
+    let mut u = z;
+    u += w;
+    u -= 1.0;
+    u *= complex::Complex64::i();
+    u /= 2.0;
+
+    assert_eq!(u, c(-1.0, 1.5));
+    assert_eq!(z.recip() * z, c(1.0, 0.0));
+
+    // Smith's division does not overflow on huge divisors
+    let huge = c(1e300, 1e300);
+
+    assert_complex_close(huge / huge, c(1.0, 0.0), 1e-15);
+
+    assert_eq!(z.to_string(), "3+4i");
+    assert_eq!(w.to_string(), "1-2i");
+}
+
+#[test]
+fn test_complex_polar() {
+    let z = c(3.0, 4.0);
+
+    assert_eq!(z.norm(), 5.0);
+    assert_eq!(z.norm_sqr(), 25.0);
+    assert_eq!(c(-1.0, 0.0).arg(), PI);
+    assert_eq!(c(0.0, -2.0).arg(), -FRAC_PI_2);
+
+    let (r, theta) = z.to_polar();
+
+    assert_complex_close(complex::Complex64::from_polar(r, theta), z, 1e-15);
+    assert_complex_close(
+        complex::Complex64::from_polar(2.0, FRAC_PI_2),
+        c(0.0, 2.0),
+        1e-15,
+    );
+
+    // The modulus of huge numbers does not overflow
+    assert_eq!(c(3e300, 4e300).norm(), 5e300);
+}
+
+#[test]
+fn test_complex_functions() {
+    // Euler's identity
+    assert_complex_close(c(0.0, PI).exp(), c(-1.0, 0.0), 1e-15);
+
+    let z = c(0.5, -1.25);
+
+    assert_complex_close(z.ln().exp(), z, 1e-15);
+    assert_complex_close(c(-1.0, 0.0).ln(), c(0.0, PI), 1e-15);
+    assert_complex_close(c(-4.0, 0.0).sqrt(), c(0.0, 2.0), 1e-15);
+    assert_complex_close(c(0.0, 2.0).sqrt(), c(1.0, 1.0), 1e-15);
+    assert_complex_close(z.sqrt() * z.sqrt(), z, 1e-15);
+    assert_eq!(c(0.0, 0.0).sqrt(), c(0.0, 0.0));
+
+    // The principal square root sits in the right half plane, on both sides
+    // of the branch cut
+    assert_complex_close(c(-4.0, -0.0).sqrt(), c(0.0, -2.0), 1e-15);
+    assert!(c(-3.0, 1e-3).sqrt().re > 0.0);
+
+    assert_eq!(c(1.0, 1.0).powi(4), c(-4.0, 0.0));
+    assert_eq!(c(1.0, 1.0).powi(0), c(1.0, 0.0));
+    assert_complex_close(c(1.0, 1.0).powi(-2), c(0.0, -0.5), 1e-15);
+    assert_complex_close(z.powf(0.5), z.sqrt(), 1e-15);
+    assert_complex_close(z.powf(3.0), z * z * z, 1e-14);
+
+    // i^i = e^(-pi/2)
+    assert_complex_close(
+        complex::Complex64::i().powc(complex::Complex64::i()),
+        c((-FRAC_PI_2).exp(), 0.0),
+        1e-15,
+    );
+    assert_eq!(c(0.0, 0.0).powc(c(2.0, 1.0)), c(0.0, 0.0));
+    assert_eq!(c(0.0, 0.0).powf(0.0), c(1.0, 0.0));
+}
+
+#[test]
+fn test_complex_matrices() {
+    let i = complex::Complex64::i();
+    let a = linear_algebra::Matrix::from_vec(
+        2,
+        2,
+        vec![c(1.0, 0.0), c(2.0, -1.0), c(2.0, 1.0), c(3.0, 0.0)],
+    );
+
+    assert!(a.is_hermitian());
+    assert_eq!(complex::check_hermitian(&a), Ok(()));
+    assert_eq!(linear_algebra::Matrix::matrix_conjugate_transpose(&a), a);
+
+    // Products go through the generic matrix multiply
+    let b = linear_algebra::Matrix::from_vec(2, 2, vec![i, c(1.0, 0.0), c(0.0, 0.0), -i]);
+    let product = &a * &b;
+
+    assert_eq!(
+        product.data,
+        vec![c(0.0, 1.0), c(0.0, -2.0), c(-1.0, 2.0), c(2.0, -2.0)]
+    );
+
+    // (AB)^H = B^H A^H
+    assert_eq!(
+        linear_algebra::Matrix::matrix_conjugate_transpose(&product),
+        &linear_algebra::Matrix::matrix_conjugate_transpose(&b)
+            * &linear_algebra::Matrix::matrix_conjugate_transpose(&a)
+    );
+    assert!(!b.is_hermitian());
+    assert_eq!(
+        complex::check_hermitian(&b),
+        Err(linear_algebra::LinAlgError::NotHermitian { row: 0, col: 0 })
+    );
+    assert_eq!(
+        complex::check_hermitian(&linear_algebra::Matrix::zeros(2, 3)),
+        Err(linear_algebra::LinAlgError::NotSquare { rows: 2, cols: 3 })
+    );
+    assert_eq!(
+        linear_algebra::LinAlgError::NotHermitian { row: 1, col: 0 }.to_string(),
+        "Matrix is not Hermitian at (1, 0)!"
+    );
+
+    // Complex scaling on either side
+    assert_eq!(i * &b, &b * i);
+    assert_eq!((&b * i).data[0], c(-1.0, 0.0));
+
+    // Parts round trip through real matrices
+    let re = a.real();
+    let im = a.imag();
+
+    assert_eq!(re.data, vec![1.0, 2.0, 2.0, 3.0]);
+    assert_eq!(im.data, vec![0.0, -1.0, 1.0, 0.0]);
+    assert_eq!(linear_algebra::Matrix::from_parts(&re, &im), a);
+    assert_eq!(
+        linear_algebra::Matrix::from_real(&re).imag(),
+        linear_algebra::Matrix::new(2, 2)
+    );
+    assert!(
+        linear_algebra::Matrix::try_from_parts(&re, &linear_algebra::Matrix::new(2, 3)).is_err()
+    );
+
+    // The real transpose is its own conjugate transpose
+    assert_eq!(
+        linear_algebra::Matrix::matrix_conjugate_transpose(&re),
+        linear_algebra::Matrix::matrix_transpose(&re)
+    );
+}
+
+#[test]
+fn test_complex_vectors() {
+    let v = linear_algebra::Vector::from_parts(
+        &linear_algebra::Vector {
+            size: 2,
+            data: vec![1.0, 0.0],
+        },
+        &linear_algebra::Vector {
+            size: 2,
+            data: vec![1.0, 2.0],
+        },
+    );
+
+    // v^H v is real and equal to the squared norm
+    assert_eq!(linear_algebra::Vector::vector_conj_dot(&v, &v), c(6.0, 0.0));
+    assert_eq!(linear_algebra::Vector::vector_dot(&v, &v), c(-4.0, 2.0));
+    assert_eq!(v.norm(), 6f64.sqrt());
+
+    // Huge and tiny entries neither overflow nor underflow
+    let big = linear_algebra::Vector {
+        size: 2,
+        data: vec![c(3e200, 4e200), c(0.0, 0.0)],
+    };
+    let small = linear_algebra::Vector {
+        size: 2,
+        data: vec![c(3e-170, 0.0), c(0.0, -4e-170)],
+    };
+
+    assert!((big.norm() / 5e200 - 1.0).abs() < 1e-15);
+    assert!((small.norm() / 5e-170 - 1.0).abs() < 1e-15);
+    assert_eq!(v.real().data, vec![1.0, 0.0]);
+    assert_eq!(v.imag().data, vec![1.0, 2.0]);
+    assert_eq!((&v + &v).data, vec![c(2.0, 2.0), c(0.0, 4.0)]);
+    assert!(
+        linear_algebra::Vector::try_vector_conj_dot(&v, &linear_algebra::Vector::zeros(3)).is_err()
+    );
+}
+
+#[test]
+fn test_complex_eigen() {
+    // A rotation by 90 degrees has eigenvalues +-i
+    let r = linear_algebra::Matrix::from_vec(2, 2, vec![0.0, -1.0, 1.0, 0.0]);
+    let eigen = eigen::GeneralEigen::new(&r).unwrap();
+    let values = eigen.complex_eigenvalues();
+
+    assert_complex_close(values.data[0], c(0.0, 1.0), 1e-15);
+    assert_complex_close(values.data[1], c(0.0, -1.0), 1e-15);
+
+    // A v = lambda v, checked in complex arithmetic
+    let a = linear_algebra::Matrix::from_real(&r);
+    let vectors = eigen.right.unwrap().to_complex();
+
+    for j in 0..2 {
+        let v = linear_algebra::Vector {
+            size: 2,
+            data: vec![vectors[(0, j)], vectors[(1, j)]],
+        };
+        let av = linear_algebra::Matrix::matrix_vector_mul(&a, &v);
+
+        for k in 0..2 {
+            assert_complex_close(av.data[k], values.data[j] * v.data[k], 1e-14);
+        }
+    }
+}
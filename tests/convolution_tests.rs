@@ -0,0 +1,327 @@
+// tests/convolution_tests.rs
+
+mod common;
+
+use common::assert_close;
+use LAMS_rs::convolution::*;
+use LAMS_rs::linear_algebra::*;
+
+// Deterministic, irregular test data
+fn tensor(shape: &[usize], seed: usize) -> linear_algebra::Tensor {
+    let size = shape.iter().product::<usize>();
+    let data = (0..size)
+        .map(|j| ((j * 7 + seed * 3 + j * j) % 13) as f64 / 4.0 - 1.5)
+        .collect();
+
+    linear_algebra::Tensor::from_vec(shape, data)
+}
+
+fn options(stride: usize, padding: usize, dilation: usize) -> convolution::ConvOptions {
+    convolution::ConvOptions {
+        stride: vec![stride],
+        padding: vec![padding],
+        dilation: vec![dilation],
+        ..Default::default()
+    }
+}
+
+// Direct 2-D convolution straight from the definition
+fn naive_conv2d(
+    x: &linear_algebra::Tensor,
+    w: &linear_algebra::Tensor,
+    (stride, padding, dilation): (usize, usize, usize),
+) -> linear_algebra::Tensor {
+    let (n, c_in, h, width) = (x.shape[0], x.shape[1], x.shape[2], x.shape[3]);
+    let (c_out, kh, kw) = (w.shape[0], w.shape[2], w.shape[3]);
+    let oh = (h + 2 * padding - dilation * (kh - 1) - 1) / stride + 1;
+    let ow = (width + 2 * padding - dilation * (kw - 1) - 1) / stride + 1;
+
+    let mut result = linear_algebra::Tensor::tensor_new(&[n, c_out, oh, ow]);
+
+    for b in 0..n {
+        for co in 0..c_out {
+            for i in 0..oh {
+                for j in 0..ow {
+                    let mut sum = 0.0;
+
+                    for ci in 0..c_in {
+                        for p in 0..kh {
+                            for q in 0..kw {
+                                let r = (i * stride + p * dilation) as isize - padding as isize;
+                                let s = (j * stride + q * dilation) as isize - padding as isize;
+
+                                if r >= 0 && s >= 0 && (r as usize) < h && (s as usize) < width {
+                                    sum += x[[b, ci, r as usize, s as usize]] * w[[co, ci, p, q]];
+                                }
+                            }
+                        }
+                    }
+
+                    result[[b, co, i, j]] = sum;
+                }
+            }
+        }
+    }
+
+    result
+}
+
+fn inner(a: &linear_algebra::Tensor, b: &linear_algebra::Tensor) -> f64 {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+#[test]
+fn test_conv1d() {
+    let x = linear_algebra::Tensor::from_vec(&[1, 1, 5], vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+    let w = linear_algebra::Tensor::from_vec(&[1, 1, 2], vec![1.0, -1.0]);
+
+    // Cross-correlation, the kernel is not flipped
+    let y = convolution::conv(&x, &w, &Default::default());
+
+    assert_eq!(y.shape, vec![1, 1, 4]);
+    assert_eq!(y.as_slice(), vec![-1.0, -1.0, -1.0, -1.0]);
+
+    let y = convolution::conv(&x, &w, &options(2, 1, 1));
+
+    assert_eq!(y.as_slice(), vec![-1.0, -1.0, -1.0]);
+
+    // Integer elements
+    let xi = linear_algebra::Tensor::from_vec(&[1, 1, 4], vec![1, 2, 3, 4]);
+    let wi = linear_algebra::Tensor::from_vec(&[1, 1, 3], vec![1, 0, 2]);
+
+    assert_eq!(
+        convolution::conv(&xi, &wi, &Default::default()).as_slice(),
+        vec![7, 10]
+    );
+}
+
+#[test]
+fn test_conv2d() {
+    let x = tensor(&[2, 3, 7, 6], 1);
+    let w = tensor(&[4, 3, 3, 2], 2);
+
+    for settings in [(1, 0, 1), (2, 1, 1), (1, 2, 2), (3, 1, 2)] {
+        let (stride, padding, dilation) = settings;
+        let y = convolution::conv(&x, &w, &options(stride, padding, dilation));
+        let expected = naive_conv2d(&x, &w, settings);
+
+        assert_eq!(y.shape, expected.shape);
+        assert_close(y.as_slice(), expected.as_slice(), 1e-12);
+    }
+
+    // Per axis settings: stride 2 down the rows only
+    let per_axis = convolution::ConvOptions {
+        stride: vec![2, 1],
+        ..Default::default()
+    };
+
+    assert_eq!(convolution::conv(&x, &w, &per_axis).shape, vec![2, 4, 3, 5]);
+
+    // A permuted (non-contiguous) input gives the same result
+    let nhwc = x.clone().permute(&[0, 2, 3, 1]).to_contiguous();
+    let nchw = nhwc.permute(&[0, 3, 1, 2]);
+
+    assert!(!nchw.is_contiguous());
+    assert_eq!(
+        convolution::conv(&nchw, &w, &Default::default()).as_slice(),
+        convolution::conv(&x, &w, &Default::default()).as_slice()
+    );
+}
+
+#[test]
+fn test_conv3d() {
+    let x = tensor(&[1, 2, 4, 5, 3], 3);
+    let w = tensor(&[3, 2, 2, 3, 1], 4);
+    let y = convolution::conv(&x, &w, &options(1, 1, 1));
+
+    assert_eq!(y.shape, vec![1, 3, 5, 5, 5]);
+
+    // Spot check one output against the definition
+    let mut expected = 0.0;
+
+    for c in 0..2 {
+        for p in 0..2 {
+            for q in 0..3 {
+                // Output (1, 2, 1) reads input (p, q + 1, 0) after padding
+                expected += x[[0, c, p, q + 1, 0]] * w[[2, c, p, q, 0]];
+            }
+        }
+    }
+
+    assert!((y[[0, 2, 1, 2, 1]] - expected).abs() < 1e-12);
+}
+
+#[test]
+fn test_conv_transpose() {
+    // Stride 2 spreads the input out, overlapping taps add up
+    let x = linear_algebra::Tensor::from_vec(&[1, 1, 2], vec![1.0, 2.0]);
+    let w = linear_algebra::Tensor::from_vec(&[1, 1, 3], vec![1.0, 1.0, 1.0]);
+    let y = convolution::conv_transpose(&x, &w, &options(2, 0, 1));
+
+    assert_eq!(y.as_slice(), vec![1.0, 1.0, 3.0, 2.0, 2.0]);
+
+    // The adjoint of conv: <conv(x), y> = <x, conv_transpose(y)>
+    for settings in [(1, 0, 1), (2, 1, 1), (2, 0, 2), (3, 2, 1)] {
+        let (stride, padding, dilation) = settings;
+        let o = options(stride, padding, dilation);
+
+        let x = tensor(&[2, 3, 8, 7], 5);
+        let w = tensor(&[4, 3, 3, 3], 6);
+        let forward = convolution::conv(&x, &w, &o);
+        let y = tensor(&forward.shape, 7);
+
+        // Restores the input shape, with output padding where the stride
+        // dropped rows or columns
+        let mut back = o.clone();
+        back.output_padding = (0..2)
+            .map(|a| {
+                let span = dilation * 2 + 1;
+                (x.shape[a + 2] + 2 * padding - span) % stride
+            })
+            .collect();
+
+        let adjoint = convolution::conv_transpose(&y, &w, &back);
+
+        assert_eq!(adjoint.shape, x.shape);
+        assert!((inner(&forward, &y) - inner(&x, &adjoint)).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn test_pooling() {
+    let x = linear_algebra::Tensor::from_vec(
+        &[1, 1, 4, 4],
+        vec![
+            1.0, 2.0, 5.0, 6.0, //
+            3.0, 4.0, 7.0, 8.0, //
+            -1.0, -2.0, 0.0, 1.0, //
+            -3.0, -4.0, 1.0, 0.0,
+        ],
+    );
+
+    // The stride defaults to the kernel size
+    let max = convolution::max_pool(&x, &[2, 2], &Default::default());
+    let avg = convolution::avg_pool(&x, &[2, 2], &Default::default());
+
+    assert_eq!(max.shape, vec![1, 1, 2, 2]);
+    assert_eq!(max.as_slice(), vec![4.0, 8.0, -1.0, 1.0]);
+    assert_eq!(avg.as_slice(), vec![2.5, 6.5, -2.5, 0.5]);
+
+    // Overlapping windows
+    let overlapping = convolution::max_pool(
+        &x,
+        &[2, 2],
+        &convolution::ConvOptions {
+            stride: vec![1],
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(overlapping.shape, vec![1, 1, 3, 3]);
+    assert_eq!(overlapping.as_slice()[..3], [4.0, 7.0, 8.0]);
+
+    // Padding is left out of the average rather than counted as zero
+    let padded = convolution::avg_pool(&x, &[2, 2], &options(2, 1, 1));
+
+    assert_eq!(padded.shape, vec![1, 1, 3, 3]);
+    assert_eq!(padded.as_slice()[0], 1.0);
+    assert_eq!(padded.as_slice()[1], 3.5);
+    assert_eq!(padded.as_slice()[4], 2.25);
+
+    // NaN propagates through the max
+    let mut nan = x.clone();
+    nan[[0, 0, 3, 3]] = f64::NAN;
+
+    let pooled = convolution::max_pool(&nan, &[2, 2], &Default::default());
+
+    assert!(pooled.as_slice()[3].is_nan());
+    assert_eq!(pooled.as_slice()[..3], [4.0, 8.0, -1.0]);
+
+    // Every channel and batch element is pooled separately
+    let volume = tensor(&[2, 3, 4, 4, 6], 8);
+    let pooled = convolution::avg_pool(&volume, &[2, 2, 3], &Default::default());
+
+    assert_eq!(pooled.shape, vec![2, 3, 2, 2, 2]);
+
+    let mut expected = 0.0;
+
+    for i in 0..2 {
+        for j in 0..2 {
+            for k in 0..3 {
+                expected += volume[[1, 2, 2 + i, j, 3 + k]];
+            }
+        }
+    }
+
+    assert!((pooled[[1, 2, 1, 0, 1]] - expected / 12.0).abs() < 1e-15);
+}
+
+#[test]
+fn test_convolution_errors() {
+    let x = tensor(&[1, 3, 5, 5], 1);
+    let w = tensor(&[2, 3, 3, 3], 2);
+
+    assert_eq!(
+        convolution::try_conv(&x, &tensor(&[2, 4, 3, 3], 2), &Default::default()),
+        Err(linear_algebra::LinAlgError::DimensionMismatch {
+            left: vec![1, 3, 5, 5],
+            right: vec![2, 4, 3, 3],
+        })
+    );
+    assert!(convolution::try_conv(&x, &tensor(&[2, 3, 3], 2), &Default::default()).is_err());
+    assert!(convolution::try_conv(&tensor(&[3, 5], 1), &w, &Default::default()).is_err());
+
+    assert_eq!(
+        convolution::try_conv(&x, &w, &options(0, 0, 1)),
+        Err(linear_algebra::LinAlgError::InvalidParameter {
+            name: "stride or dilation",
+            reason: "must be positive",
+        })
+    );
+    assert_eq!(
+        convolution::try_conv(&x, &w, &options(1, 0, 3)),
+        Err(linear_algebra::LinAlgError::InvalidParameter {
+            name: "kernel",
+            reason: "is larger than the padded input",
+        })
+    );
+    assert_eq!(
+        convolution::try_conv(
+            &x,
+            &w,
+            &convolution::ConvOptions {
+                padding: vec![1, 1, 1],
+                ..Default::default()
+            }
+        ),
+        Err(linear_algebra::LinAlgError::InvalidParameter {
+            name: "padding",
+            reason: "needs one value or one per spatial axis",
+        })
+    );
+
+    let mut too_much = options(2, 0, 1);
+    too_much.output_padding = vec![2];
+
+    assert!(convolution::try_conv_transpose(&x, &tensor(&[3, 2, 3, 3], 2), &too_much).is_err());
+    assert!(convolution::try_max_pool(&x, &[2], &Default::default()).is_err());
+    assert!(convolution::try_avg_pool(&x, &[6, 6], &Default::default()).is_err());
+
+    assert_eq!(
+        linear_algebra::LinAlgError::InvalidParameter {
+            name: "stride",
+            reason: "must be positive",
+        }
+        .to_string(),
+        "Invalid stride: must be positive!"
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_conv_panic() {
+    let x = tensor(&[1, 2, 4, 4], 1);
+    let w = tensor(&[1, 3, 2, 2], 2);
+
+    let _y = convolution::conv(&x, &w, &Default::default());
+}
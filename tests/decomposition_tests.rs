@@ -0,0 +1,401 @@
+// tests/decomposition_tests.rs
+
+mod common;
+
+use common::{assert_close, assert_orthonormal_columns, identity, transpose};
+use LAMS_rs::decomposition::*;
+use LAMS_rs::linear_algebra::*;
+
+#[test]
+fn test_lu_factors() {
+    let a =
+        linear_algebra::Matrix::from_vec(3, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 10.0]);
+
+    let lu = decomposition::LU::new(&a).unwrap();
+
+    // PA = LU
+    let pa = &lu.p() * &a;
+    let product = &lu.l() * &lu.u();
+
+    assert_close(&pa.data, &product.data, 1e-12);
+
+    // The largest entry of the first column is chosen as the first pivot
+    assert_eq!(lu.pivots[0], 2);
+}
+
+#[test]
+fn test_lu_not_square() {
+    let a = linear_algebra::Matrix::new(2, 3);
+
+    assert_eq!(
+        decomposition::LU::new(&a).unwrap_err(),
+        linear_algebra::LinAlgError::NotSquare { rows: 2, cols: 3 }
+    );
+}
+
+#[test]
+fn test_lu_solve() {
+    let a = linear_algebra::Matrix::from_vec(
+        3,
+        3,
+        vec![2.0, 1.0, -1.0, -3.0, -1.0, 2.0, -2.0, 1.0, 2.0],
+    );
+
+    let mut b = linear_algebra::Vector::new(3);
+    b.vector_set(vec![8.0, -11.0, -3.0]);
+
+    let lu = decomposition::LU::new(&a).unwrap();
+    let x = lu.solve(&b).unwrap();
+
+    assert_close(&x.data, &[2.0, 3.0, -1.0], 1e-12);
+
+    let wrong = linear_algebra::Vector::new(2);
+
+    assert!(lu.solve(&wrong).is_err());
+}
+
+#[test]
+fn test_lu_solve_matrix() {
+    let a = linear_algebra::Matrix::from_vec(2, 2, vec![4.0, 3.0, 6.0, 3.0]);
+    let b = linear_algebra::Matrix::from_vec(2, 2, vec![10.0, 7.0, 12.0, 9.0]);
+
+    let lu = decomposition::LU::new(&a).unwrap();
+    let x = lu.solve_matrix(&b).unwrap();
+
+    assert_close(&(&a * &x).data, &b.data, 1e-12);
+}
+
+#[test]
+fn test_lu_determinant() {
+    let a =
+        linear_algebra::Matrix::from_vec(3, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 10.0]);
+
+    let lu = decomposition::LU::new(&a).unwrap();
+
+    assert!((lu.determinant() - -3.0).abs() < 1e-12);
+
+    // A single row swap flips the sign
+    let b = linear_algebra::Matrix::from_vec(2, 2, vec![0.0, 1.0, 1.0, 0.0]);
+
+    assert_eq!(decomposition::LU::new(&b).unwrap().determinant(), -1.0);
+}
+
+#[test]
+fn test_lu_inverse() {
+    let a =
+        linear_algebra::Matrix::from_vec(3, 3, vec![4.0, 7.0, 2.0, 3.0, 6.0, 1.0, 2.0, 5.0, 3.0]);
+
+    let inverse = decomposition::LU::new(&a).unwrap().inverse().unwrap();
+
+    assert_close(&(&a * &inverse).data, &identity(3).data, 1e-12);
+    assert_close(&(&inverse * &a).data, &identity(3).data, 1e-12);
+}
+
+#[test]
+fn test_lu_singular() {
+    let a =
+        linear_algebra::Matrix::from_vec(3, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+
+    let lu = decomposition::LU::new(&a).unwrap();
+
+    assert!(lu.is_singular());
+    assert_eq!(lu.rcond(), 0.0);
+    assert_eq!(
+        lu.inverse().unwrap_err(),
+        linear_algebra::LinAlgError::SingularMatrix
+    );
+    assert!(lu.determinant().abs() < 1e-12);
+}
+
+#[test]
+fn test_lu_rcond() {
+    let lu = decomposition::LU::new(&identity(4)).unwrap();
+
+    assert!((lu.rcond() - 1.0).abs() < 1e-12);
+
+    // Nearly dependent rows give a tiny reciprocal condition number
+    let a = linear_algebra::Matrix::from_vec(2, 2, vec![1.0, 1.0, 1.0, 1.0 + 1e-10]);
+    let lu = decomposition::LU::new(&a).unwrap();
+
+    assert!(!lu.is_singular());
+    assert!(lu.rcond() < 1e-9);
+
+    // Exact value for a diagonal matrix: 1 / (||A|| ||A^-1||)
+    let d = linear_algebra::Matrix::from_vec(2, 2, vec![2.0, 0.0, 0.0, 0.5]);
+
+    assert!((decomposition::LU::new(&d).unwrap().rcond() - 0.25).abs() < 1e-12);
+}
+
+#[test]
+fn test_qr_tall() {
+    let a = linear_algebra::Matrix::from_vec(
+        4,
+        3,
+        vec![
+            12.0, -51.0, 4.0, 6.0, 167.0, -68.0, -4.0, 24.0, -41.0, 1.0, 2.0, 3.0,
+        ],
+    );
+
+    let qr = decomposition::QR::new(&a);
+
+    let q = qr.q_thin();
+    let r = qr.r();
+
+    assert_eq!((q.rows, q.cols), (4, 3));
+    assert_eq!((r.rows, r.cols), (3, 3));
+    assert_orthonormal_columns(&q);
+    assert_close(&(&q * &r).data, &a.data, 1e-10);
+
+    // R is upper triangular
+    assert_eq!(r[(1, 0)], 0.0);
+    assert_eq!(r[(2, 0)], 0.0);
+    assert_eq!(r[(2, 1)], 0.0);
+
+    let q_full = qr.q_full();
+    let r_full = qr.r_full();
+
+    assert_eq!((q_full.rows, q_full.cols), (4, 4));
+    assert_eq!((r_full.rows, r_full.cols), (4, 3));
+    assert_orthonormal_columns(&q_full);
+    assert_close(&(&q_full * &r_full).data, &a.data, 1e-10);
+}
+
+#[test]
+fn test_qr_wide() {
+    let a = linear_algebra::Matrix::from_vec(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    let qr = decomposition::QR::new(&a);
+
+    assert_eq!((qr.q_thin().rows, qr.q_thin().cols), (2, 2));
+    assert_eq!((qr.r().rows, qr.r().cols), (2, 3));
+    assert_close(&(&qr.q_thin() * &qr.r()).data, &a.data, 1e-12);
+}
+
+#[test]
+fn test_qr_pivoted() {
+    // The third column is the sum of the first two
+    let a = linear_algebra::Matrix::from_vec(
+        4,
+        3,
+        vec![1.0, 0.0, 1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 2.0, 2.0, 1.0, 3.0],
+    );
+
+    let qr = decomposition::QR::new_pivoted(&a);
+
+    let ap = &a * &qr.p();
+
+    assert_close(&(&qr.q_thin() * &qr.r()).data, &ap.data, 1e-12);
+    assert_eq!(qr.rank(None), 2);
+
+    // The largest column comes first and the diagonal of R is non-increasing
+    assert_eq!(qr.permutation[0], 2);
+
+    let r = qr.r();
+
+    assert!(r[(0, 0)].abs() >= r[(1, 1)].abs());
+    assert!(r[(1, 1)].abs() >= r[(2, 2)].abs());
+}
+
+#[test]
+fn test_lstsq_line_fit() {
+    // Fit y = c0 + c1 x through points that are not exactly on a line
+    let a = linear_algebra::Matrix::from_vec(4, 2, vec![1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0]);
+
+    let mut b = linear_algebra::Vector::new(4);
+    b.vector_set(vec![1.0, 3.0, 4.0, 4.0]);
+
+    let result = decomposition::lstsq(&a, &b).unwrap();
+
+    // Normal equations give c0 = 1.5, c1 = 1.0
+    assert_close(&result.solution.data, &[1.5, 1.0], 1e-12);
+    assert_close(&result.residuals.data, &[-0.5, 0.5, 0.5, -0.5], 1e-12);
+    assert!((result.residual_norm - 1.0).abs() < 1e-12);
+    assert_eq!(result.rank, 2);
+}
+
+#[test]
+fn test_lstsq_rank_deficient() {
+    // Two identical columns, any split of the weight between them fits
+    let a = linear_algebra::Matrix::from_vec(3, 2, vec![1.0, 1.0, 2.0, 2.0, 3.0, 3.0]);
+
+    let mut b = linear_algebra::Vector::new(3);
+    b.vector_set(vec![2.0, 4.0, 6.0]);
+
+    let result = decomposition::lstsq(&a, &b).unwrap();
+
+    assert_eq!(result.rank, 1);
+    assert!(result.residual_norm < 1e-12);
+    assert!((result.solution[0] + result.solution[1] - 2.0).abs() < 1e-12);
+    assert!(result.solution.data.iter().all(|x| x.is_finite()));
+}
+
+#[test]
+fn test_lstsq_dimension_mismatch() {
+    let a = linear_algebra::Matrix::new(3, 2);
+    let b = linear_algebra::Vector::new(2);
+
+    assert!(decomposition::lstsq(&a, &b).is_err());
+}
+
+#[test]
+fn test_cholesky_factors() {
+    let a = linear_algebra::Matrix::from_vec(
+        3,
+        3,
+        vec![4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0],
+    );
+
+    let cholesky = decomposition::Cholesky::new(&a).unwrap();
+
+    assert_close(
+        &cholesky.l.data,
+        &[2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0],
+        1e-12,
+    );
+
+    let product = &cholesky.l * &transpose(&cholesky.l);
+    assert_close(&product.data, &a.data, 1e-12);
+
+    // det A = (2 * 1 * 3)^2
+    assert!((cholesky.log_det() - 36.0f64.ln()).abs() < 1e-12);
+    assert!((cholesky.determinant() - 36.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_cholesky_solve() {
+    let a =
+        linear_algebra::Matrix::from_vec(3, 3, vec![4.0, 1.0, 2.0, 1.0, 3.0, 0.0, 2.0, 0.0, 5.0]);
+    let mut b = linear_algebra::Vector::new(3);
+    b.vector_set(vec![1.0, 2.0, 3.0]);
+
+    let cholesky = decomposition::Cholesky::new(&a).unwrap();
+    let x = cholesky.solve(&b).unwrap();
+
+    assert_close(&(&a * &x).data, &b.data, 1e-12);
+
+    let inverse = cholesky.inverse().unwrap();
+    assert_close(&(&a * &inverse).data, &identity(3).data, 1e-12);
+}
+
+#[test]
+fn test_cholesky_not_positive_definite() {
+    let a = linear_algebra::Matrix::from_vec(2, 2, vec![1.0, 2.0, 2.0, 1.0]);
+
+    let error = decomposition::Cholesky::new(&a).unwrap_err();
+
+    assert_eq!(
+        error,
+        linear_algebra::LinAlgError::NotPositiveDefinite { pivot: 1 }
+    );
+    assert_eq!(
+        error.to_string(),
+        "Matrix is not positive definite (failed at pivot 1)!"
+    );
+}
+
+#[test]
+fn test_cholesky_sample() {
+    let a = linear_algebra::Matrix::from_vec(2, 2, vec![4.0, 2.0, 2.0, 5.0]);
+    let cholesky = decomposition::Cholesky::new(&a).unwrap();
+
+    // L = [[2, 0], [1, 2]]
+    let mut z = linear_algebra::Vector::new(2);
+    z.vector_set(vec![1.0, -1.0]);
+    assert_close(&cholesky.sample(&z).unwrap().data, &[2.0, -1.0], 1e-12);
+
+    assert!(cholesky.sample(&linear_algebra::Vector::new(3)).is_err());
+}
+
+#[test]
+fn test_ldlt_indefinite() {
+    let a = linear_algebra::Matrix::from_vec(
+        4,
+        4,
+        vec![
+            1.0, 2.0, 3.0, 4.0, 2.0, -1.0, 0.5, 1.0, 3.0, 0.5, 0.0, -2.0, 4.0, 1.0, -2.0, 3.0,
+        ],
+    );
+
+    let ldlt = decomposition::LDLT::new(&a).unwrap();
+
+    // P A P^T = L D L^T
+    let p = ldlt.p();
+    let pap = &(&p * &a) * &transpose(&p);
+    let product = &(&ldlt.l * &ldlt.d_matrix()) * &transpose(&ldlt.l);
+
+    assert_close(&pap.data, &product.data, 1e-12);
+
+    let lu = decomposition::LU::new(&a).unwrap();
+    let (sign, log) = ldlt.log_det();
+
+    assert!((ldlt.determinant() - lu.determinant()).abs() < 1e-9);
+    assert!((sign * log.exp() - lu.determinant()).abs() < 1e-9);
+
+    let mut b = linear_algebra::Vector::new(4);
+    b.vector_set(vec![1.0, 2.0, 3.0, 4.0]);
+    let x = ldlt.solve(&b).unwrap();
+
+    assert_close(&(&a * &x).data, &b.data, 1e-12);
+}
+
+#[test]
+fn test_ldlt_two_by_two_pivot() {
+    // No usable 1x1 pivot exists, so a 2x2 block is required
+    let a =
+        linear_algebra::Matrix::from_vec(3, 3, vec![0.0, 1.0, 0.0, 1.0, 0.0, 2.0, 0.0, 2.0, 0.0]);
+
+    let ldlt = decomposition::LDLT::new(&a).unwrap();
+
+    assert!(ldlt.block_sizes.contains(&2));
+
+    let p = ldlt.p();
+    let pap = &(&p * &a) * &transpose(&p);
+    let product = &(&ldlt.l * &ldlt.d_matrix()) * &transpose(&ldlt.l);
+
+    assert_close(&pap.data, &product.data, 1e-12);
+
+    // Eigenvalues are 0 and +-sqrt(5)
+    assert_eq!(ldlt.inertia(), (1, 1, 1));
+    assert!(ldlt.is_singular());
+    assert_eq!(
+        ldlt.solve(&linear_algebra::Vector::new(3)).unwrap_err(),
+        linear_algebra::LinAlgError::SingularMatrix
+    );
+}
+
+#[test]
+fn test_ldlt_near_singular() {
+    // B B^T has rank 2, but rounding leaves a last pivot around 1e-17
+    // instead of zero
+    let b = linear_algebra::Matrix::from_vec(
+        3,
+        2,
+        vec![1.0, 1.0 / 3.0, 1.0 / 7.0, 2.0 / 9.0, 0.3, 0.11],
+    );
+    let a = &b * &transpose(&b);
+
+    let ldlt = decomposition::LDLT::new(&a).unwrap();
+
+    assert!(ldlt.d.iter().all(|&d| d != 0.0));
+    assert!(ldlt.is_singular());
+    assert_eq!(
+        ldlt.is_singular(),
+        decomposition::LU::new(&a).unwrap().is_singular()
+    );
+    assert_eq!(ldlt.log_det(), (0.0, f64::NEG_INFINITY));
+    assert_eq!(
+        ldlt.solve(&linear_algebra::Vector::new(3)).unwrap_err(),
+        linear_algebra::LinAlgError::SingularMatrix
+    );
+}
+
+#[test]
+fn test_ldlt_inertia() {
+    let a =
+        linear_algebra::Matrix::from_vec(3, 3, vec![2.0, 0.0, 0.0, 0.0, -3.0, 0.0, 0.0, 0.0, 5.0]);
+
+    let ldlt = decomposition::LDLT::new(&a).unwrap();
+
+    assert_eq!(ldlt.inertia(), (2, 1, 0));
+    assert_eq!(ldlt.log_det().0, -1.0);
+}
@@ -0,0 +1,310 @@
+// tests/eigen_tests.rs
+
+mod common;
+
+use common::{assert_close, assert_orthonormal_columns};
+use LAMS_rs::eigen::*;
+use LAMS_rs::linear_algebra::*;
+
+#[test]
+fn test_symmetric_eigen_small() {
+    let a = linear_algebra::Matrix::from_vec(2, 2, vec![2.0, 1.0, 1.0, 2.0]);
+
+    let eigen = eigen::SymmetricEigen::new(&a).unwrap();
+
+    assert_close(&eigen.eigenvalues.data, &[1.0, 3.0], 1e-12);
+    assert_orthonormal_columns(&eigen.eigenvectors);
+
+    // A v = lambda v for every column
+    for j in 0..2 {
+        for i in 0..2 {
+            let av =
+                a[(i, 0)] * eigen.eigenvectors[(0, j)] + a[(i, 1)] * eigen.eigenvectors[(1, j)];
+            assert!((av - eigen.eigenvalues[j] * eigen.eigenvectors[(i, j)]).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_symmetric_eigen_reconstruct() {
+    let a = linear_algebra::Matrix::from_vec(
+        4,
+        4,
+        vec![
+            4.0, 1.0, -2.0, 2.0, 1.0, 2.0, 0.0, 1.0, -2.0, 0.0, 3.0, -2.0, 2.0, 1.0, -2.0, -1.0,
+        ],
+    );
+
+    let eigen = eigen::SymmetricEigen::new(&a).unwrap();
+
+    assert_orthonormal_columns(&eigen.eigenvectors);
+    assert_close(&eigen.reconstruct().data, &a.data, 1e-12);
+
+    for i in 1..4 {
+        assert!(eigen.eigenvalues[i - 1] <= eigen.eigenvalues[i]);
+    }
+
+    // The trace is the sum of the eigenvalues
+    let sum: f64 = eigen.eigenvalues.data.iter().sum();
+    assert!((sum - 8.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_symmetric_eigenvalues_only() {
+    let a = linear_algebra::Matrix::from_vec(
+        3,
+        3,
+        vec![2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0],
+    );
+
+    let values = eigen::SymmetricEigen::eigenvalues(&a).unwrap();
+    let full = eigen::SymmetricEigen::new(&a).unwrap();
+
+    let sqrt2 = 2.0f64.sqrt();
+    assert_close(&values.data, &[2.0 - sqrt2, 2.0, 2.0 + sqrt2], 1e-12);
+    assert_close(&values.data, &full.eigenvalues.data, 1e-12);
+}
+
+#[test]
+fn test_symmetric_eigen_diagonal() {
+    let a =
+        linear_algebra::Matrix::from_vec(3, 3, vec![3.0, 0.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 2.0]);
+
+    let eigen = eigen::SymmetricEigen::new(&a).unwrap();
+
+    assert_close(&eigen.eigenvalues.data, &[-1.0, 2.0, 3.0], 1e-15);
+    assert_close(&eigen.reconstruct().data, &a.data, 1e-15);
+}
+
+#[test]
+fn test_symmetric_eigen_not_symmetric() {
+    let a = linear_algebra::Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+
+    let error = eigen::SymmetricEigen::new(&a).unwrap_err();
+
+    assert_eq!(
+        error,
+        linear_algebra::LinAlgError::NotSymmetric { row: 1, col: 0 }
+    );
+    assert_eq!(error.to_string(), "Matrix is not symmetric at (1, 0)!");
+
+    assert!(eigen::SymmetricEigen::eigenvalues(&linear_algebra::Matrix::new(2, 3)).is_err());
+}
+
+// Checks A x = lambda x for every column of the (complex) eigenvectors
+fn assert_right_eigenpairs(a: &linear_algebra::Matrix, eigen: &eigen::GeneralEigen) {
+    let n = a.rows;
+    let vectors = eigen.right.as_ref().unwrap();
+
+    for j in 0..n {
+        let (lr, li) = (eigen.real[j], eigen.imag[j]);
+
+        for i in 0..n {
+            let (mut re, mut im) = (0.0, 0.0);
+
+            for k in 0..n {
+                re += a[(i, k)] * vectors.real[(k, j)];
+                im += a[(i, k)] * vectors.imag[(k, j)];
+            }
+
+            re -= lr * vectors.real[(i, j)] - li * vectors.imag[(i, j)];
+            im -= lr * vectors.imag[(i, j)] + li * vectors.real[(i, j)];
+
+            assert!(re.hypot(im) < 1e-10, "column {} row {}", j, i);
+        }
+    }
+}
+
+fn sorted_pairs(eigen: &eigen::GeneralEigen) -> Vec<(f64, f64)> {
+    let mut pairs: Vec<(f64, f64)> = eigen
+        .real
+        .data
+        .iter()
+        .copied()
+        .zip(eigen.imag.data.iter().copied())
+        .collect();
+
+    pairs.sort_by(|x, y| x.0.total_cmp(&y.0).then(x.1.total_cmp(&y.1)));
+
+    pairs
+}
+
+#[test]
+fn test_general_eigen_rotation() {
+    // Rotation by 90 degrees scaled by 2, eigenvalues +-2i
+    let a = linear_algebra::Matrix::from_vec(2, 2, vec![0.0, -2.0, 2.0, 0.0]);
+
+    let eigen = eigen::GeneralEigen::new(&a).unwrap();
+
+    assert_close(&eigen.real.data, &[0.0, 0.0], 1e-12);
+    assert_close(&eigen.imag.data, &[2.0, -2.0], 1e-12);
+    assert!(!eigen.is_real());
+    assert!((eigen.spectral_radius() - 2.0).abs() < 1e-12);
+    assert_right_eigenpairs(&a, &eigen);
+}
+
+#[test]
+fn test_general_eigen_companion() {
+    // Companion matrix of (x - 1)(x - 2)(x^2 + 2x + 5), roots 1, 2, -1 +- 2i
+    let a = linear_algebra::Matrix::from_vec(
+        4,
+        4,
+        vec![
+            0.0, 0.0, 0.0, -10.0, 1.0, 0.0, 0.0, 11.0, 0.0, 1.0, 0.0, -1.0, 0.0, 0.0, 1.0, 1.0,
+        ],
+    );
+
+    let eigen = eigen::GeneralEigen::new(&a).unwrap();
+    let pairs = sorted_pairs(&eigen);
+
+    let expected = [(-1.0, -2.0), (-1.0, 2.0), (1.0, 0.0), (2.0, 0.0)];
+
+    for (actual, expected) in pairs.iter().zip(expected.iter()) {
+        assert!((actual.0 - expected.0).abs() < 1e-10, "{:?}", pairs);
+        assert!((actual.1 - expected.1).abs() < 1e-10, "{:?}", pairs);
+    }
+
+    assert_right_eigenpairs(&a, &eigen);
+
+    // Eigenvalues only mode agrees
+    let values = eigen::GeneralEigen::eigenvalues(&a).unwrap();
+
+    assert!(values.right.is_none());
+    assert_close(&values.real.data, &eigen.real.data, 1e-12);
+    assert_close(&values.imag.data, &eigen.imag.data, 1e-12);
+}
+
+#[test]
+fn test_general_eigen_markov() {
+    // Column stochastic transition matrix, so 1 is an eigenvalue whose left
+    // eigenvector is constant
+    let a =
+        linear_algebra::Matrix::from_vec(3, 3, vec![0.9, 0.2, 0.1, 0.05, 0.7, 0.3, 0.05, 0.1, 0.6]);
+
+    let eigen = eigen::GeneralEigen::new_with_left(&a).unwrap();
+
+    assert!(eigen.is_real());
+    assert!((eigen.spectral_radius() - 1.0).abs() < 1e-12);
+    assert_right_eigenpairs(&a, &eigen);
+
+    let left = eigen.left.as_ref().unwrap();
+
+    // y^H A = lambda y^H, with A and lambda real here
+    for j in 0..3 {
+        for k in 0..3 {
+            let mut sum = 0.0;
+
+            for i in 0..3 {
+                sum += left.real[(i, j)] * a[(i, k)];
+            }
+
+            assert!((sum - eigen.real[j] * left.real[(k, j)]).abs() < 1e-10);
+        }
+    }
+
+    let j = (0..3)
+        .find(|&j| (eigen.real[j] - 1.0).abs() < 1e-10)
+        .unwrap();
+    assert!((left.real[(0, j)] - left.real[(1, j)]).abs() < 1e-10);
+    assert!((left.real[(1, j)] - left.real[(2, j)]).abs() < 1e-10);
+}
+
+#[test]
+fn test_general_eigen_left_complex() {
+    let a =
+        linear_algebra::Matrix::from_vec(3, 3, vec![1.0, -3.0, 2.0, 2.0, 1.0, 0.0, 0.5, 1.0, -1.0]);
+
+    let eigen = eigen::GeneralEigen::new_with_left(&a).unwrap();
+    let left = eigen.left.as_ref().unwrap();
+
+    assert!(!eigen.is_real());
+    assert_right_eigenpairs(&a, &eigen);
+
+    // conj(y)^T A = lambda conj(y)^T
+    for j in 0..3 {
+        let (lr, li) = (eigen.real[j], eigen.imag[j]);
+
+        for k in 0..3 {
+            let (mut re, mut im) = (0.0, 0.0);
+
+            for i in 0..3 {
+                re += left.real[(i, j)] * a[(i, k)];
+                im -= left.imag[(i, j)] * a[(i, k)];
+            }
+
+            let (yr, yi) = (left.real[(k, j)], -left.imag[(k, j)]);
+
+            re -= lr * yr - li * yi;
+            im -= lr * yi + li * yr;
+
+            assert!(re.hypot(im) < 1e-10);
+        }
+    }
+}
+
+#[test]
+fn test_general_eigen_triangular() {
+    let a =
+        linear_algebra::Matrix::from_vec(3, 3, vec![1.0, 2.0, 3.0, 0.0, 4.0, 5.0, 0.0, 0.0, 6.0]);
+
+    let eigen = eigen::GeneralEigen::new(&a).unwrap();
+    let pairs = sorted_pairs(&eigen);
+
+    assert_eq!(pairs.iter().map(|x| x.0).collect::<Vec<f64>>().len(), 3);
+    assert_close(
+        &pairs.iter().map(|x| x.0).collect::<Vec<f64>>(),
+        &[1.0, 4.0, 6.0],
+        1e-12,
+    );
+    assert_right_eigenpairs(&a, &eigen);
+
+    assert!(eigen::GeneralEigen::new(&linear_algebra::Matrix::new(2, 3)).is_err());
+}
+
+#[test]
+fn test_hessenberg() {
+    let a = linear_algebra::Matrix::from_vec(
+        4,
+        4,
+        vec![
+            4.0, 1.0, -2.0, 2.0, 1.0, 2.0, 0.0, 1.0, -2.0, 3.0, 3.0, -2.0, 2.0, 1.0, -2.0, -1.0,
+        ],
+    );
+
+    let (h, q) = eigen::hessenberg(&a).unwrap();
+
+    assert_orthonormal_columns(&q);
+
+    for i in 2..4 {
+        for j in 0..i - 1 {
+            assert_eq!(h[(i, j)], 0.0);
+        }
+    }
+
+    let qhqt = &(&q * &h) * &q.view().transpose().to_matrix();
+    assert_close(&qhqt.data, &a.data, 1e-12);
+}
+
+#[test]
+fn test_general_eigen_larger() {
+    // Deterministic pseudo-random entries in [-1, 1)
+    let mut state: u64 = 12345;
+    let data: Vec<f64> = (0..100)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+        })
+        .collect();
+
+    let a = linear_algebra::Matrix::from_vec(10, 10, data);
+
+    let eigen = eigen::GeneralEigen::new(&a).unwrap();
+    assert_right_eigenpairs(&a, &eigen);
+
+    // The trace is the sum of the eigenvalues
+    let trace: f64 = (0..10).map(|i| a[(i, i)]).sum();
+    let sum: f64 = eigen.real.data.iter().sum();
+    assert!((trace - sum).abs() < 1e-10);
+}
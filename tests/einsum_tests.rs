@@ -0,0 +1,223 @@
+// tests/einsum_tests.rs
+
+mod common;
+
+use common::assert_close;
+use LAMS_rs::einsum::*;
+use LAMS_rs::linear_algebra::*;
+
+fn counting_tensor(shape: &[usize]) -> linear_algebra::Tensor {
+    let size = shape.iter().product::<usize>();
+
+    linear_algebra::Tensor::from_vec(shape, (0..size).map(|x| x as f64 - 3.0).collect())
+}
+
+#[test]
+fn test_einsum_matrix_product() {
+    let a = counting_tensor(&[2, 3]);
+    let b = counting_tensor(&[3, 4]);
+
+    let expected = linear_algebra::Matrix::matrix_mul(&a.to_matrix(), &b.to_matrix());
+
+    let c = einsum::einsum("ij,jk->ik", &[&a, &b]);
+
+    assert_eq!(c.shape, vec![2, 4]);
+    assert_close(c.as_slice(), &expected.data, 1e-12);
+
+    // Implicit output and whitespace
+    assert_eq!(einsum::einsum("ij, jk", &[&a, &b]), c);
+
+    // Output order is respected
+    let ct = einsum::einsum("ij,jk->ki", &[&a, &b]);
+
+    assert_eq!(
+        ct.to_matrix(),
+        linear_algebra::Matrix::matrix_transpose(&expected)
+    );
+}
+
+#[test]
+fn test_einsum_batched_product() {
+    let a = counting_tensor(&[3, 2, 4]);
+    let b = counting_tensor(&[3, 4, 5]);
+
+    let c = einsum::einsum("bij,bjk->bik", &[&a, &b]);
+
+    assert_eq!(c.shape, vec![3, 2, 5]);
+
+    for p in 0..3 {
+        let expected = linear_algebra::Matrix::matrix_mul(
+            &a.tensor_view(p).to_matrix(),
+            &b.tensor_view(p).to_matrix(),
+        );
+
+        assert_close(&c.tensor_view(p).to_matrix().data, &expected.data, 1e-12);
+    }
+}
+
+#[test]
+fn test_einsum_single_operand() {
+    let a = counting_tensor(&[3, 3]);
+
+    // Trace, diagonal, transpose and full sum
+    assert_eq!(einsum::einsum("ii->", &[&a]).as_slice(), vec![3.0]);
+    assert_eq!(
+        einsum::einsum("ii->i", &[&a]).as_slice(),
+        vec![-3.0, 1.0, 5.0]
+    );
+    assert_eq!(einsum::einsum("ij->ji", &[&a]), a.clone().permute(&[1, 0]));
+    assert_eq!(einsum::einsum("ij->", &[&a]).as_slice(), vec![9.0]);
+    assert_eq!(
+        einsum::einsum("ij->j", &[&a]).as_slice(),
+        vec![0.0, 3.0, 6.0]
+    );
+
+    // Trace over two axes of a 3-D tensor
+    let t = counting_tensor(&[2, 3, 2]);
+    let traced = einsum::einsum("iji->j", &[&t]);
+
+    let expected: Vec<f64> = (0..3).map(|j| t[[0, j, 0]] + t[[1, j, 1]]).collect();
+
+    assert_eq!(traced.as_slice(), expected);
+}
+
+#[test]
+fn test_einsum_outer_and_inner() {
+    let u = linear_algebra::Tensor::from_vec(&[2], vec![1.0, 2.0]);
+    let v = linear_algebra::Tensor::from_vec(&[3], vec![3.0, 4.0, 5.0]);
+
+    let outer = einsum::einsum("i,j->ij", &[&u, &v]);
+
+    assert_eq!(outer.shape, vec![2, 3]);
+    assert_eq!(outer.as_slice(), vec![3.0, 4.0, 5.0, 6.0, 8.0, 10.0]);
+
+    let dot = einsum::einsum("i,i->", &[&v, &v]);
+
+    assert_eq!(dot.shape, Vec::<usize>::new());
+    assert_eq!(dot.as_slice(), vec![50.0]);
+
+    // Elementwise product keeps both labels
+    assert_eq!(
+        einsum::einsum("i,i->i", &[&v, &v]).as_slice(),
+        vec![9.0, 16.0, 25.0]
+    );
+
+    // Batched inner products of rows
+    let a = counting_tensor(&[2, 3]);
+
+    assert_eq!(
+        einsum::einsum("bi,bi->b", &[&a, &a]).as_slice(),
+        vec![9.0 + 4.0 + 1.0, 0.0 + 1.0 + 4.0]
+    );
+}
+
+#[test]
+fn test_einsum_many_operands() {
+    let a = counting_tensor(&[2, 3]);
+    let b = counting_tensor(&[3, 4]);
+    let c = counting_tensor(&[4, 2]);
+    let d = counting_tensor(&[2]);
+
+    let abc = linear_algebra::Matrix::matrix_mul(
+        &linear_algebra::Matrix::matrix_mul(&a.to_matrix(), &b.to_matrix()),
+        &c.to_matrix(),
+    );
+
+    let chain = einsum::einsum("ij,jk,kl->il", &[&a, &b, &c]);
+
+    assert_close(chain.as_slice(), &abc.data, 1e-10);
+
+    // A scalar from four operands: d^T (A B C) d
+    let quadratic = einsum::einsum("i,ij,jk,kl,l->", &[&d, &a, &b, &c, &d]);
+
+    let mut expected = 0.0;
+
+    for i in 0..2 {
+        for l in 0..2 {
+            expected += d[[i]] * abc[(i, l)] * d[[l]];
+        }
+    }
+
+    assert_close(quadratic.as_slice(), &[expected], 1e-9);
+}
+
+#[test]
+fn test_einsum_generic() {
+    let a = linear_algebra::Tensor::<i64>::from_vec(&[2, 2], vec![1, 2, 3, 4]);
+
+    assert_eq!(
+        einsum::einsum("ij,jk->ik", &[&a, &a]).as_slice(),
+        vec![7, 10, 15, 22]
+    );
+    assert_eq!(einsum::einsum("ii", &[&a]).as_slice(), vec![5]);
+}
+
+#[test]
+fn test_try_einsum_errors() {
+    let a = counting_tensor(&[2, 3]);
+    let b = counting_tensor(&[4, 3]);
+
+    assert_eq!(
+        einsum::try_einsum("ij,jk->ik", &[&a, &b]),
+        Err(linear_algebra::LinAlgError::DimensionMismatch {
+            left: vec![2, 3],
+            right: vec![4, 3],
+        })
+    );
+    assert_eq!(
+        einsum::try_einsum("ij,jk->il", &[&a, &a]),
+        Err(linear_algebra::LinAlgError::InvalidSubscripts {
+            subscripts: "ij,jk->il".to_string(),
+            reason: "output label missing from the inputs",
+        })
+    );
+    assert!(einsum::try_einsum("ij->ii", &[&a]).is_err());
+    assert!(einsum::try_einsum("ijk->i", &[&a]).is_err());
+    assert!(einsum::try_einsum("ij,jk->ik", &[&a]).is_err());
+    assert!(einsum::try_einsum("i1->i", &[&a]).is_err());
+    assert!(einsum::try_einsum::<f64>("->", &[]).is_err());
+
+    assert_eq!(
+        linear_algebra::LinAlgError::InvalidSubscripts {
+            subscripts: "ij->k".to_string(),
+            reason: "output label missing from the inputs",
+        }
+        .to_string(),
+        "Invalid subscripts \"ij->k\": output label missing from the inputs!"
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_einsum_panic() {
+    let a = counting_tensor(&[2, 3]);
+
+    einsum::einsum("ij,jk->ik", &[&a, &a]);
+}
+
+#[test]
+fn test_tensordot() {
+    let a = counting_tensor(&[2, 3, 4]);
+    let b = counting_tensor(&[4, 3, 5]);
+
+    let c = einsum::tensordot(&a, &b, &[1, 2], &[1, 0]);
+
+    assert_eq!(c.shape, vec![2, 5]);
+    assert_close(
+        c.as_slice(),
+        einsum::einsum("ijk,kjl->il", &[&a, &b]).as_slice(),
+        1e-10,
+    );
+
+    // No contracted axes is the outer product
+    let u = linear_algebra::Tensor::from_vec(&[2], vec![1.0, 2.0]);
+    let outer = einsum::tensordot(&u, &a, &[], &[]);
+
+    assert_eq!(outer.shape, vec![2, 2, 3, 4]);
+    assert_eq!(outer[[1, 1, 2, 3]], 2.0 * a[[1, 2, 3]]);
+
+    assert!(einsum::try_tensordot(&a, &b, &[1], &[0]).is_err());
+    assert!(einsum::try_tensordot(&a, &b, &[1, 1], &[1, 0]).is_err());
+    assert!(einsum::try_tensordot(&a, &b, &[3], &[0]).is_err());
+    assert!(einsum::try_tensordot(&a, &b, &[1], &[1, 2]).is_err());
+}
@@ -0,0 +1,287 @@
+// tests/fft_tests.rs
+
+mod common;
+
+use std::f64::consts::PI;
+
+use common::assert_close;
+use LAMS_rs::complex::*;
+use LAMS_rs::fft::*;
+use LAMS_rs::linear_algebra::*;
+
+fn assert_complex_close(
+    actual: &[complex::Complex64],
+    expected: &[complex::Complex64],
+    tolerance: f64,
+) {
+    assert_eq!(actual.len(), expected.len());
+
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!(
+            (*a - *e).norm() <= tolerance,
+            "{} != {} (tolerance {})",
+            a,
+            e,
+            tolerance
+        );
+    }
+}
+
+// Deterministic, irregular test data
+fn signal(n: usize) -> linear_algebra::Vector {
+    linear_algebra::Vector {
+        size: n,
+        data: (0..n)
+            .map(|j| ((j * j + 3 * j) % 17) as f64 / 4.0 - 1.5)
+            .collect(),
+    }
+}
+
+fn complex_signal(n: usize) -> linear_algebra::Vector<complex::Complex64> {
+    linear_algebra::Vector {
+        size: n,
+        data: (0..n)
+            .map(|j| complex::Complex64::new((j as f64 * 0.7).sin(), ((j * 5) % 7) as f64 - 3.0))
+            .collect(),
+    }
+}
+
+// O(n^2) reference transform
+fn naive_dft(x: &[complex::Complex64]) -> Vec<complex::Complex64> {
+    let n = x.len();
+
+    (0..n)
+        .map(|k| {
+            x.iter()
+                .enumerate()
+                .fold(complex::Complex64::new(0.0, 0.0), |acc, (j, &v)| {
+                    let angle = -2.0 * PI * ((j * k) % n) as f64 / n as f64;
+                    acc + v * complex::Complex64::from_polar(1.0, angle)
+                })
+        })
+        .collect()
+}
+
+fn naive_convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut result = vec![0.0; a.len() + b.len() - 1];
+
+    for i in 0..a.len() {
+        for j in 0..b.len() {
+            result[i + j] += a[i] * b[j];
+        }
+    }
+
+    result
+}
+
+#[test]
+fn test_fft_matches_dft() {
+    // Radix-2, mixed radix, and Bluestein for the primes above 31 (37, 97)
+    // and for 2 * 3 * 37
+    let sizes = (1..=40).chain([64, 97, 210, 222, 256, 1000]);
+
+    for n in sizes {
+        let x = complex_signal(n);
+        let expected = naive_dft(&x.data);
+        let tolerance = 1e-12 * n as f64;
+
+        assert_complex_close(&fft::fft(&x).data, &expected, tolerance);
+        assert_complex_close(&fft::ifft(&fft::fft(&x)).data, &x.data, 1e-14 * n as f64);
+    }
+
+    assert_eq!(
+        fft::fft(&linear_algebra::Vector {
+            size: 0,
+            data: vec![],
+        })
+        .size,
+        0
+    );
+}
+
+#[test]
+fn test_fft_properties() {
+    // An impulse transforms to a constant, a constant to an impulse
+    let mut impulse = linear_algebra::Vector::zeros(12);
+    impulse.data[0] = complex::Complex64::new(1.0, 0.0);
+
+    assert!(fft::fft(&impulse)
+        .data
+        .iter()
+        .all(|&z| z == complex::Complex64::new(1.0, 0.0)));
+
+    let spectrum = fft::fft(&linear_algebra::Vector {
+        size: 8,
+        data: vec![complex::Complex64::new(2.0, 0.0); 8],
+    });
+
+    assert_complex_close(
+        &spectrum.data,
+        &[
+            vec![complex::Complex64::new(16.0, 0.0)],
+            vec![complex::Complex64::new(0.0, 0.0); 7],
+        ]
+        .concat(),
+        1e-14,
+    );
+
+    // Parseval: sum |x|^2 = sum |X|^2 / n
+    let x = complex_signal(101);
+    let energy: f64 = x.data.iter().map(|z| z.norm_sqr()).sum();
+    let spectral: f64 = fft::fft(&x).data.iter().map(|z| z.norm_sqr()).sum();
+
+    assert!((energy - spectral / 101.0).abs() < 1e-11);
+}
+
+#[test]
+fn test_rfft() {
+    for n in [1, 2, 7, 8, 15, 30, 74, 97, 128] {
+        let x = signal(n);
+        let full = fft::fft(&linear_algebra::Vector::from_real(&x));
+        let half = fft::rfft(&x);
+
+        assert_eq!(half.size, n / 2 + 1);
+        assert_complex_close(&half.data, &full.data[..n / 2 + 1], 1e-12 * n as f64);
+        assert_close(&fft::irfft(&half, n).data, &x.data, 1e-13);
+    }
+
+    assert_eq!(
+        fft::try_irfft(&fft::rfft(&signal(8)), 10),
+        Err(linear_algebra::LinAlgError::DimensionMismatch {
+            left: vec![5],
+            right: vec![6],
+        })
+    );
+}
+
+#[test]
+fn test_fft2() {
+    let (rows, cols) = (6, 5);
+    let m = linear_algebra::Matrix::from_vec(rows, cols, complex_signal(rows * cols).data);
+    let transformed = fft::fft2(&m);
+
+    // Direct 2-D sum for comparison
+    for u in 0..rows {
+        for v in 0..cols {
+            let mut expected = complex::Complex64::new(0.0, 0.0);
+
+            for i in 0..rows {
+                for j in 0..cols {
+                    let angle =
+                        -2.0 * PI * ((u * i) as f64 / rows as f64 + (v * j) as f64 / cols as f64);
+                    expected += m[(i, j)] * complex::Complex64::from_polar(1.0, angle);
+                }
+            }
+
+            assert!((transformed[(u, v)] - expected).norm() < 1e-12);
+        }
+    }
+
+    assert_complex_close(&fft::ifft2(&transformed).data, &m.data, 1e-14);
+}
+
+#[test]
+fn test_convolve() {
+    let a = signal(5);
+    let b = signal(3);
+
+    assert_eq!(fft::convolve(&a, &b).data, naive_convolve(&a.data, &b.data));
+
+    // Long enough for the FFT path, including a non power of two result
+    for (n, m) in [(40, 33), (100, 64), (257, 50)] {
+        let a = signal(n);
+        let b = signal(m);
+        let result = fft::convolve(&a, &b);
+
+        assert_eq!(result.size, n + m - 1);
+        assert_close(&result.data, &naive_convolve(&a.data, &b.data), 1e-11);
+    }
+
+    // Convolving distributions: two fair dice
+    let die = linear_algebra::Vector {
+        size: 6,
+        data: vec![1.0 / 6.0; 6],
+    };
+    let sum = fft::convolve(&die, &die);
+
+    assert!((sum.data[5] - 6.0 / 36.0).abs() < 1e-15);
+    assert!((sum.data.iter().sum::<f64>() - 1.0).abs() < 1e-15);
+    assert_eq!(fft::convolve(&die, &linear_algebra::Vector::new(0)).size, 0);
+}
+
+#[test]
+fn test_correlate() {
+    let a = linear_algebra::Vector {
+        size: 3,
+        data: vec![1.0, 2.0, 3.0],
+    };
+    let b = linear_algebra::Vector {
+        size: 2,
+        data: vec![0.0, 1.0],
+    };
+
+    // Lags -1, 0, 1, 2
+    assert_eq!(fft::correlate(&a, &b).data, vec![1.0, 2.0, 3.0, 0.0]);
+
+    // The autocorrelation peaks at zero lag, with the signal energy
+    let x = signal(50);
+    let auto = fft::correlate(&x, &x);
+    let energy: f64 = x.data.iter().map(|v| v * v).sum();
+
+    assert_eq!(auto.size, 99);
+    assert!((auto.data[49] - energy).abs() < 1e-11);
+    assert!(auto.data.iter().all(|&v| v <= auto.data[49] + 1e-11));
+
+    for k in 0..99 {
+        assert!((auto.data[k] - auto.data[98 - k]).abs() < 1e-11);
+    }
+}
+
+#[test]
+fn test_circular() {
+    let a = linear_algebra::Vector {
+        size: 4,
+        data: vec![1.0, 2.0, 3.0, 4.0],
+    };
+    let shift = linear_algebra::Vector {
+        size: 4,
+        data: vec![0.0, 1.0, 0.0, 0.0],
+    };
+
+    // Convolving with a shifted impulse rotates the signal
+    assert_close(
+        &fft::circular_convolve(&a, &shift).data,
+        &[4.0, 1.0, 2.0, 3.0],
+        1e-15,
+    );
+    assert_close(
+        &fft::circular_correlate(&a, &shift).data,
+        &[2.0, 3.0, 4.0, 1.0],
+        1e-15,
+    );
+
+    // Wrapping the linear convolution around gives the circular one
+    for n in [7, 12, 37] {
+        let x = signal(n);
+        let y = signal(n + 1).data[1..].to_vec();
+        let linear = naive_convolve(&x.data, &y);
+        let mut wrapped = vec![0.0; n];
+
+        for (k, v) in linear.iter().enumerate() {
+            wrapped[k % n] += v;
+        }
+
+        let y = linear_algebra::Vector { size: n, data: y };
+
+        assert_close(&fft::circular_convolve(&x, &y).data, &wrapped, 1e-12);
+    }
+
+    assert_eq!(
+        fft::try_circular_convolve(&a, &signal(3)),
+        Err(linear_algebra::LinAlgError::DimensionMismatch {
+            left: vec![4],
+            right: vec![3],
+        })
+    );
+    assert!(fft::try_circular_correlate(&a, &signal(5)).is_err());
+}
@@ -0,0 +1,310 @@
+// tests/geometry_tests.rs
+
+mod common;
+
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+use common::assert_close;
+use LAMS_rs::geometry::*;
+use LAMS_rs::linear_algebra::*;
+
+fn components(v: geometry::Vec3) -> [f64; 3] {
+    [v.x, v.y, v.z]
+}
+
+fn quaternion_components(q: geometry::Quaternion) -> [f64; 4] {
+    [q.w, q.x, q.y, q.z]
+}
+
+#[test]
+fn test_vec3() {
+    let a = geometry::Vec3::new(1.0, 2.0, 3.0);
+    let b = geometry::Vec3::new(3.0, 4.0, 5.0);
+
+    assert_eq!(a.cross(b), geometry::Vec3::new(-2.0, 4.0, -2.0));
+    assert_eq!(
+        geometry::Vec3::unit_x().cross(geometry::Vec3::unit_y()),
+        geometry::Vec3::unit_z()
+    );
+    assert_eq!(a.dot(a.cross(b)), 0.0);
+    assert_eq!(a.dot(b), 26.0);
+    assert_eq!(geometry::Vec3::new(2.0, 3.0, 6.0).length(), 7.0);
+    assert_eq!(a.distance(a), 0.0);
+    assert_eq!(a.lerp(b, 0.5), geometry::Vec3::new(2.0, 3.0, 4.0));
+    assert_eq!(-a + 2.0 * a - a * 1.0, geometry::Vec3::zero());
+
+    let mut c = a;
+    c += b;
+    c -= a;
+    c *= 0.5;
+
+    assert_eq!(c, geometry::Vec3::new(1.5, 2.0, 2.5));
+    assert_close(
+        &components(b.normalize()),
+        &components(b / 50f64.sqrt()),
+        1e-15,
+    );
+
+    // Conversions to and from the dynamic types
+    let v = a.to_vector();
+
+    assert_eq!(v.data, vec![1.0, 2.0, 3.0]);
+    assert_eq!(geometry::Vec3::from_vector(&v), a);
+    assert_eq!(
+        geometry::Vec3::try_from_vector(&linear_algebra::Vector::new(4)),
+        Err(linear_algebra::LinAlgError::DimensionMismatch {
+            left: vec![4],
+            right: vec![3],
+        })
+    );
+
+    let h = a.extend(1.0);
+
+    assert_eq!(h, geometry::Vec4::new(1.0, 2.0, 3.0, 1.0));
+    assert_eq!(h.truncate(), a);
+    assert_eq!(h.dot(h), 15.0);
+    assert_eq!(geometry::Vec4::from_vector(&h.to_vector()), h);
+}
+
+#[test]
+fn test_quaternion_rotation() {
+    let q = geometry::Quaternion::from_axis_angle(geometry::Vec3::unit_z(), FRAC_PI_2);
+
+    // A quarter turn about z takes x to y
+    assert_close(
+        &components(q.rotate(geometry::Vec3::unit_x())),
+        &[0.0, 1.0, 0.0],
+        1e-15,
+    );
+
+    // Composition applies the right factor first
+    let r = geometry::Quaternion::from_axis_angle(geometry::Vec3::unit_x(), FRAC_PI_2);
+    let v = geometry::Vec3::new(0.3, -1.2, 2.0);
+
+    assert_close(
+        &components((q * r).rotate(v)),
+        &components(q.rotate(r.rotate(v))),
+        1e-14,
+    );
+
+    assert_close(
+        &quaternion_components(q * q.inverse()),
+        &quaternion_components(geometry::Quaternion::identity()),
+        1e-15,
+    );
+    assert_eq!(q.conjugate(), q.inverse());
+    assert!((q.norm() - 1.0).abs() < 1e-15);
+
+    let (axis, angle) =
+        geometry::Quaternion::from_axis_angle(geometry::Vec3::new(1.0, 1.0, 0.0), 2.5)
+            .to_axis_angle();
+
+    assert_close(
+        &components(axis),
+        &[0.5f64.sqrt(), 0.5f64.sqrt(), 0.0],
+        1e-14,
+    );
+    assert!((angle - 2.5).abs() < 1e-14);
+
+    // -q is the same rotation, reported with the same axis and angle
+    let (_, angle) = (-q).to_axis_angle();
+
+    assert!((angle - FRAC_PI_2).abs() < 1e-14);
+    assert_eq!(geometry::Quaternion::identity().to_axis_angle().1, 0.0);
+}
+
+#[test]
+fn test_quaternion_matrices() {
+    let q = geometry::Quaternion::from_axis_angle(geometry::Vec3::new(1.0, -2.0, 0.5), 1.1);
+    let m = q.to_rotation_matrix();
+
+    // Orthogonal with determinant one, and agrees with `rotate`
+    let mtm = &linear_algebra::Matrix::matrix_transpose(&m) * &m;
+
+    assert_close(
+        &mtm.data,
+        &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+        1e-15,
+    );
+
+    let v = geometry::Vec3::new(0.7, 0.1, -0.4);
+    let mv = linear_algebra::Matrix::matrix_vector_mul(&m, &v.to_vector());
+
+    assert_close(&mv.data, &components(q.rotate(v)), 1e-15);
+
+    assert_close(
+        &quaternion_components(geometry::Quaternion::from_rotation_matrix(&m)),
+        &quaternion_components(q),
+        1e-15,
+    );
+
+    // Half turns exercise every branch of the matrix conversion
+    for axis in [
+        geometry::Vec3::unit_x(),
+        geometry::Vec3::unit_y(),
+        geometry::Vec3::unit_z(),
+    ] {
+        let half_turn = geometry::Quaternion::from_axis_angle(axis, PI);
+        let recovered = geometry::Quaternion::from_rotation_matrix(&half_turn.to_rotation_matrix());
+
+        assert!((recovered.dot(half_turn).abs() - 1.0).abs() < 1e-15);
+    }
+
+    // The rotation block of a homogeneous transform
+    let recovered = geometry::Quaternion::from_rotation_matrix(&geometry::rotation(q));
+
+    assert_close(
+        &quaternion_components(recovered),
+        &quaternion_components(q),
+        1e-15,
+    );
+    assert!(
+        geometry::Quaternion::try_from_rotation_matrix(&linear_algebra::Matrix::new(2, 2)).is_err()
+    );
+}
+
+#[test]
+fn test_euler_angles() {
+    let (roll, pitch, yaw) = (0.3, -0.7, 2.0);
+    let q = geometry::Quaternion::from_euler(roll, pitch, yaw);
+
+    let expected = geometry::Quaternion::from_axis_angle(geometry::Vec3::unit_z(), yaw)
+        * geometry::Quaternion::from_axis_angle(geometry::Vec3::unit_y(), pitch)
+        * geometry::Quaternion::from_axis_angle(geometry::Vec3::unit_x(), roll);
+
+    assert_close(
+        &quaternion_components(q),
+        &quaternion_components(expected),
+        1e-15,
+    );
+
+    let (r, p, y) = q.to_euler();
+
+    assert_close(&[r, p, y], &[roll, pitch, yaw], 1e-14);
+
+    // Gimbal lock keeps pitch in range
+    let (_, p, _) = geometry::Quaternion::from_euler(0.0, FRAC_PI_2, 0.0).to_euler();
+
+    assert!((p - FRAC_PI_2).abs() < 1e-7);
+}
+
+#[test]
+fn test_slerp() {
+    let a = geometry::Quaternion::identity();
+    let b = geometry::Quaternion::from_axis_angle(geometry::Vec3::unit_z(), FRAC_PI_2);
+
+    assert_eq!(a.slerp(b, 0.0), a);
+    assert_close(
+        &quaternion_components(a.slerp(b, 1.0)),
+        &quaternion_components(b),
+        1e-15,
+    );
+
+    // Constant angular speed: halfway is an eighth turn
+    assert_close(
+        &quaternion_components(a.slerp(b, 0.5)),
+        &quaternion_components(geometry::Quaternion::from_axis_angle(
+            geometry::Vec3::unit_z(),
+            FRAC_PI_4,
+        )),
+        1e-15,
+    );
+
+    // The shorter arc is taken when the signs disagree
+    let halfway = a.slerp(-b, 0.5);
+
+    assert!((halfway.dot(a.slerp(b, 0.5)).abs() - 1.0).abs() < 1e-15);
+
+    // Nearly equal rotations stay normalized
+    let c = geometry::Quaternion::from_axis_angle(geometry::Vec3::unit_z(), 1e-6);
+
+    assert!((a.slerp(c, 0.3).norm() - 1.0).abs() < 1e-15);
+}
+
+#[test]
+fn test_homogeneous_transforms() {
+    let p = geometry::Vec3::new(1.0, 2.0, 3.0);
+
+    let moved = geometry::transform_point(
+        &geometry::translation(geometry::Vec3::new(1.0, -1.0, 0.5)),
+        p,
+    );
+
+    assert_eq!(moved, geometry::Vec3::new(2.0, 1.0, 3.5));
+
+    // Directions ignore translation
+    assert_eq!(
+        geometry::transform_direction(
+            &geometry::translation(geometry::Vec3::new(1.0, -1.0, 0.5)),
+            p
+        ),
+        p
+    );
+
+    // Scale, then rotate a quarter turn about z, then translate
+    let q = geometry::Quaternion::from_axis_angle(geometry::Vec3::unit_z(), FRAC_PI_2);
+    let m = &(&geometry::translation(geometry::Vec3::new(0.0, 0.0, 10.0)) * &geometry::rotation(q))
+        * &geometry::scaling(geometry::Vec3::new(2.0, 1.0, 1.0));
+
+    assert_close(
+        &components(geometry::transform_point(&m, geometry::Vec3::unit_x())),
+        &[0.0, 2.0, 10.0],
+        1e-15,
+    );
+
+    assert_eq!(
+        geometry::try_transform_point(&linear_algebra::Matrix::new(3, 3), p),
+        Err(linear_algebra::LinAlgError::DimensionMismatch {
+            left: vec![3, 3],
+            right: vec![4, 4],
+        })
+    );
+}
+
+#[test]
+fn test_camera_transforms() {
+    let eye = geometry::Vec3::new(0.0, 0.0, 5.0);
+    let view = geometry::look_at(eye, geometry::Vec3::zero(), geometry::Vec3::unit_y());
+
+    // The camera sits at the origin looking down -z
+    assert_close(
+        &components(geometry::transform_point(&view, eye)),
+        &[0.0, 0.0, 0.0],
+        1e-15,
+    );
+    assert_close(
+        &components(geometry::transform_point(&view, geometry::Vec3::zero())),
+        &[0.0, 0.0, -5.0],
+        1e-15,
+    );
+
+    let side_view = geometry::look_at(
+        geometry::Vec3::new(3.0, 0.0, 0.0),
+        geometry::Vec3::zero(),
+        geometry::Vec3::unit_y(),
+    );
+
+    assert_close(
+        &components(geometry::transform_point(
+            &side_view,
+            geometry::Vec3::new(3.0, 1.0, 0.0),
+        )),
+        &[0.0, 1.0, 0.0],
+        1e-15,
+    );
+
+    assert_eq!(
+        geometry::try_look_at(eye, geometry::Vec3::zero(), geometry::Vec3::unit_z()),
+        Err(linear_algebra::LinAlgError::SingularMatrix)
+    );
+    assert!(geometry::try_look_at(eye, eye, geometry::Vec3::unit_y()).is_err());
+
+    // Near and far planes land on z = -1 and z = 1
+    let projection = geometry::perspective(FRAC_PI_2, 2.0, 1.0, 100.0);
+
+    let near = geometry::transform_point(&projection, geometry::Vec3::new(2.0, 1.0, -1.0));
+    let far = geometry::transform_point(&projection, geometry::Vec3::new(0.0, 0.0, -100.0));
+
+    assert_close(&components(near), &[1.0, 1.0, -1.0], 1e-14);
+    assert_close(&components(far), &[0.0, 0.0, 1.0], 1e-14);
+}
@@ -0,0 +1,360 @@
+// tests/iterative_tests.rs
+
+use LAMS_rs::iterative::*;
+use LAMS_rs::linear_algebra::*;
+use LAMS_rs::sparse::*;
+
+// 5-point finite difference Laplacian on a k x k grid
+fn poisson_2d(k: usize) -> sparse::CsrMatrix {
+    let n = k * k;
+    let mut coo = sparse::CooMatrix::new(n, n);
+
+    for i in 0..k {
+        for j in 0..k {
+            let row = i * k + j;
+            coo.push(row, row, 4.0);
+
+            if i > 0 {
+                coo.push(row, row - k, -1.0);
+            }
+
+            if i + 1 < k {
+                coo.push(row, row + k, -1.0);
+            }
+
+            if j > 0 {
+                coo.push(row, row - 1, -1.0);
+            }
+
+            if j + 1 < k {
+                coo.push(row, row + 1, -1.0);
+            }
+        }
+    }
+
+    coo.to_csr()
+}
+
+// Tridiagonal [lower, diagonal, upper] with a varying diagonal
+fn tridiagonal(n: usize, lower: f64, diagonal: f64, upper: f64) -> sparse::CsrMatrix {
+    let mut coo = sparse::CooMatrix::new(n, n);
+
+    for i in 0..n {
+        coo.push(i, i, diagonal + (i % 3) as f64);
+
+        if i > 0 {
+            coo.push(i, i - 1, lower);
+        }
+
+        if i + 1 < n {
+            coo.push(i, i + 1, upper);
+        }
+    }
+
+    coo.to_csr()
+}
+
+fn ones(n: usize) -> linear_algebra::Vector {
+    let mut v = linear_algebra::Vector::new(n);
+    v.vector_set(vec![1.0; n]);
+    v
+}
+
+// ||b - Ax|| / ||b||
+fn relative_residual(
+    a: &sparse::CsrMatrix,
+    x: &linear_algebra::Vector,
+    b: &linear_algebra::Vector,
+) -> f64 {
+    let r = linear_algebra::Vector::vector_sub(b, &(a * x));
+
+    linear_algebra::Vector::vector_norm(&r) / linear_algebra::Vector::vector_norm(b)
+}
+
+fn options(tolerance: f64) -> iterative::SolverOptions {
+    iterative::SolverOptions {
+        tolerance,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_cg_poisson() {
+    let a = poisson_2d(12);
+    let b = ones(144);
+
+    let plain = iterative::cg(&a, &b, None, &iterative::Identity, &options(1e-10)).unwrap();
+
+    assert!(plain.converged);
+    assert!(relative_residual(&a, &plain.solution, &b) < 1e-9);
+    assert_eq!(plain.residual_history.len(), plain.iterations + 1);
+
+    let jacobi = iterative::Jacobi::new(&a).unwrap();
+    let with_jacobi = iterative::cg(&a, &b, None, &jacobi, &options(1e-10)).unwrap();
+
+    assert!(with_jacobi.converged);
+    assert!(relative_residual(&a, &with_jacobi.solution, &b) < 1e-9);
+
+    let ic = iterative::IncompleteCholesky::new(&a).unwrap();
+    let with_ic = iterative::cg(&a, &b, None, &ic, &options(1e-10)).unwrap();
+
+    assert!(with_ic.converged);
+    assert!(relative_residual(&a, &with_ic.solution, &b) < 1e-9);
+    assert!(with_ic.iterations < plain.iterations);
+}
+
+#[test]
+fn test_incomplete_factorizations_exact_for_tridiagonal() {
+    // No fill-in happens for a tridiagonal matrix, so IC(0) and ILU(0) are
+    // the exact factorizations and one iteration suffices
+    let spd = tridiagonal(50, -1.0, 3.0, -1.0);
+    let b = ones(50);
+
+    let ic = iterative::IncompleteCholesky::new(&spd).unwrap();
+    let result = iterative::cg(&spd, &b, None, &ic, &options(1e-12)).unwrap();
+
+    assert!(result.converged);
+    assert_eq!(result.iterations, 1);
+
+    let nonsymmetric = tridiagonal(50, -1.5, 3.0, -0.5);
+    let ilu = iterative::IncompleteLU::new(&nonsymmetric).unwrap();
+    let result = iterative::gmres(&nonsymmetric, &b, None, &ilu, &options(1e-12)).unwrap();
+
+    assert!(result.converged);
+    assert_eq!(result.iterations, 1);
+    assert!(relative_residual(&nonsymmetric, &result.solution, &b) < 1e-12);
+}
+
+#[test]
+fn test_minres_indefinite() {
+    // Shifting the Laplacian makes it indefinite, which breaks CG but not
+    // MINRES
+    let mut shifted = sparse::CooMatrix::new(100, 100);
+
+    for i in 0..100 {
+        shifted.push(i, i, -1.0);
+    }
+
+    let a = &poisson_2d(10) + &shifted.to_csr();
+    let b = ones(100);
+
+    let result = iterative::minres(&a, &b, None, &iterative::Identity, &options(1e-10)).unwrap();
+
+    assert!(result.converged);
+    assert!(relative_residual(&a, &result.solution, &b) < 1e-8);
+
+    // The MINRES residual never increases
+    for pair in result.residual_history.windows(2) {
+        assert!(pair[1] <= pair[0] * (1.0 + 1e-12));
+    }
+
+    let jacobi = iterative::Jacobi::new(&poisson_2d(10)).unwrap();
+    let preconditioned =
+        iterative::minres(&poisson_2d(10), &b, None, &jacobi, &options(1e-10)).unwrap();
+
+    assert!(preconditioned.converged);
+    assert!(relative_residual(&poisson_2d(10), &preconditioned.solution, &b) < 1e-8);
+}
+
+#[test]
+fn test_gmres_nonsymmetric() {
+    let a = tridiagonal(200, -1.6, 2.5, -0.4);
+    let b = ones(200);
+
+    let restarted = iterative::SolverOptions {
+        tolerance: 1e-10,
+        max_iterations: 2000,
+        restart: 10,
+    };
+
+    let result = iterative::gmres(&a, &b, None, &iterative::Identity, &restarted).unwrap();
+
+    assert!(result.converged);
+    assert!(relative_residual(&a, &result.solution, &b) < 1e-9);
+    assert_eq!(result.residual_history.len(), result.iterations + 1);
+
+    let jacobi = iterative::Jacobi::new(&a).unwrap();
+    let result = iterative::gmres(&a, &b, None, &jacobi, &restarted).unwrap();
+
+    assert!(result.converged);
+    assert!(relative_residual(&a, &result.solution, &b) < 1e-9);
+}
+
+#[test]
+fn test_bicgstab_nonsymmetric() {
+    let a = tridiagonal(200, -1.6, 2.5, -0.4);
+    let b = ones(200);
+
+    let result = iterative::bicgstab(&a, &b, None, &iterative::Identity, &options(1e-10)).unwrap();
+
+    assert!(result.converged);
+    assert!(relative_residual(&a, &result.solution, &b) < 1e-9);
+
+    let ilu = iterative::IncompleteLU::new(&poisson_2d(10)).unwrap();
+    let result =
+        iterative::bicgstab(&poisson_2d(10), &ones(100), None, &ilu, &options(1e-10)).unwrap();
+
+    assert!(result.converged);
+    assert!(relative_residual(&poisson_2d(10), &result.solution, &ones(100)) < 1e-9);
+}
+
+#[test]
+fn test_solver_breakdown() {
+    let mut e1 = linear_algebra::Vector::new(2);
+    e1.vector_set(vec![1.0, 0.0]);
+
+    // p^T A p = 0 on the first step, and < 0 for a negative definite A
+    let swap = linear_algebra::Matrix::from_vec(2, 2, vec![0.0, 1.0, 1.0, 0.0]);
+    let negative = linear_algebra::Matrix::from_vec(2, 2, vec![-2.0, 0.0, 0.0, -1.0]);
+
+    for a in [swap, negative] {
+        assert_eq!(
+            iterative::cg(&a, &e1, None, &iterative::Identity, &Default::default()).unwrap_err(),
+            linear_algebra::LinAlgError::NotPositiveDefinite { pivot: 1 }
+        );
+    }
+
+    // r_hat^T A r_hat = 0 for a skew-symmetric A
+    let skew = linear_algebra::Matrix::from_vec(2, 2, vec![0.0, 1.0, -1.0, 0.0]);
+    let result =
+        iterative::bicgstab(&skew, &e1, None, &iterative::Identity, &Default::default()).unwrap();
+
+    assert!(!result.converged);
+    assert_eq!(result.iterations, 0);
+    assert_eq!(result.solution.data, vec![0.0, 0.0]);
+
+    // The half step lands the residual in the null space of A, so t = 0
+    let singular = linear_algebra::Matrix::from_vec(2, 2, vec![1.0, 1.0, 0.0, 0.0]);
+    let result = iterative::bicgstab(
+        &singular,
+        &ones(2),
+        None,
+        &iterative::Identity,
+        &Default::default(),
+    )
+    .unwrap();
+
+    assert!(!result.converged);
+    assert_eq!(result.iterations, 1);
+    assert_eq!(result.solution.data, vec![1.0, 1.0]);
+    assert_eq!(result.residual_history, vec![2.0f64.sqrt(), 2.0f64.sqrt()]);
+}
+
+#[test]
+fn test_dense_and_closure_operators() {
+    let a = poisson_2d(6);
+    let dense = a.to_dense();
+    let csc = a.to_csc();
+    let b = ones(36);
+
+    let from_dense =
+        iterative::cg(&dense, &b, None, &iterative::Identity, &options(1e-12)).unwrap();
+    let from_csc = iterative::cg(&csc, &b, None, &iterative::Identity, &options(1e-12)).unwrap();
+
+    let operator = iterative::FnOperator::new(36, |x: &[f64], y: &mut [f64]| {
+        let result = sparse::CsrMatrix::csr_vector_mul(&a, &{
+            let mut v = linear_algebra::Vector::new(36);
+            v.vector_set(x.to_vec());
+            v
+        });
+        y.copy_from_slice(&result.data);
+    });
+
+    let from_closure =
+        iterative::gmres(&operator, &b, None, &iterative::Identity, &options(1e-12)).unwrap();
+
+    assert!(from_dense.converged && from_csc.converged && from_closure.converged);
+
+    for i in 0..36 {
+        assert!((from_dense.solution[i] - from_csc.solution[i]).abs() < 1e-10);
+        assert!((from_dense.solution[i] - from_closure.solution[i]).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn test_initial_guess_and_iteration_cap() {
+    let a = poisson_2d(8);
+    let b = ones(64);
+
+    let exact = iterative::cg(&a, &b, None, &iterative::Identity, &options(1e-12)).unwrap();
+
+    // Starting from the solution converges immediately
+    let restart = iterative::cg(
+        &a,
+        &b,
+        Some(&exact.solution),
+        &iterative::Identity,
+        &options(1e-8),
+    )
+    .unwrap();
+
+    assert!(restart.converged);
+    assert_eq!(restart.iterations, 0);
+
+    let capped = iterative::SolverOptions {
+        max_iterations: 3,
+        ..Default::default()
+    };
+
+    for result in [
+        iterative::cg(&a, &b, None, &iterative::Identity, &capped).unwrap(),
+        iterative::minres(&a, &b, None, &iterative::Identity, &capped).unwrap(),
+        iterative::gmres(&a, &b, None, &iterative::Identity, &capped).unwrap(),
+        iterative::bicgstab(&a, &b, None, &iterative::Identity, &capped).unwrap(),
+    ] {
+        assert!(!result.converged);
+        assert_eq!(result.iterations, 3);
+        assert_eq!(result.residual_history.len(), 4);
+    }
+}
+
+#[test]
+fn test_solver_errors() {
+    let a = poisson_2d(4);
+
+    assert_eq!(
+        iterative::cg(
+            &a,
+            &ones(3),
+            None,
+            &iterative::Identity,
+            &Default::default()
+        )
+        .unwrap_err(),
+        linear_algebra::LinAlgError::DimensionMismatch {
+            left: vec![16, 16],
+            right: vec![3],
+        }
+    );
+
+    let rectangular = linear_algebra::Matrix::new(3, 4);
+    assert!(iterative::gmres(
+        &rectangular,
+        &ones(3),
+        None,
+        &iterative::Identity,
+        &Default::default()
+    )
+    .is_err());
+
+    // Indefinite matrices have no incomplete Cholesky factorization
+    let indefinite = sparse::CsrMatrix::from_dense(&linear_algebra::Matrix::from_vec(
+        2,
+        2,
+        vec![1.0, 2.0, 2.0, 1.0],
+    ));
+
+    assert!(matches!(
+        iterative::IncompleteCholesky::new(&indefinite),
+        Err(linear_algebra::LinAlgError::NotPositiveDefinite { pivot: 1 })
+    ));
+
+    let zero_diagonal = sparse::CsrMatrix::from_dense(&linear_algebra::Matrix::from_vec(
+        2,
+        2,
+        vec![0.0, 1.0, 1.0, 0.0],
+    ));
+
+    assert!(iterative::Jacobi::new(&zero_diagonal).is_err());
+    assert!(iterative::IncompleteLU::new(&zero_diagonal).is_err());
+}
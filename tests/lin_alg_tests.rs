@@ -1,5 +1,8 @@
 // tests/lin_alg_tests.rs
 
+#![allow(clippy::needless_range_loop)]
+
+use std::ops::Bound;
 use LAMS_rs::linear_algebra::*;
 
 #[test]
@@ -147,14 +150,26 @@ fn test_cross_vectors_panic() {
     let _v3 = linear_algebra::Vector::vector_cross(&v1, &v2);
 }
 
+#[test]
+fn test_cross_vectors_not_3d() {
+    let v1 = linear_algebra::Vector::new(4);
+
+    assert_eq!(
+        linear_algebra::Vector::try_vector_cross(&v1, &v1),
+        Err(linear_algebra::LinAlgError::DimensionMismatch {
+            left: vec![4],
+            right: vec![3],
+        })
+    );
+}
+
 #[test]
 fn test_matrix_new() {
     let m = linear_algebra::Matrix::new(3, 3);
     assert_eq!(m.rows, 3);
     assert_eq!(m.cols, 3);
-    assert_eq!(m.data, vec![vec![0.0; 3]; 3]);
-    assert_eq!(m.data.len(), 3);
-    assert_eq!(m.data[0].len(), 3);
+    assert_eq!(m.data, vec![0.0; 9]);
+    assert_eq!(m.data.len(), 9);
 }
 
 #[test]
@@ -165,16 +180,8 @@ fn test_set_matrix() {
 
     assert_eq!(m.rows, 3);
     assert_eq!(m.cols, 3);
-    assert_eq!(
-        m.data,
-        vec![
-            vec![1.0, 2.0, 3.0],
-            vec![4.0, 5.0, 6.0],
-            vec![7.0, 8.0, 9.0]
-        ]
-    );
-    assert_eq!(m.data.len(), 3);
-    assert_eq!(m.data[0].len(), 3);
+    assert_eq!(m.data, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+    assert_eq!(m.data.len(), 9);
 }
 
 #[test]
@@ -191,14 +198,10 @@ fn test_add_matrices() {
 
     assert_eq!(
         m3.data,
-        vec![
-            vec![2.0, 4.0, 6.0],
-            vec![8.0, 10.0, 12.0],
-            vec![14.0, 16.0, 18.0]
-        ]
+        vec![2.0, 4.0, 6.0, 8.0, 10.0, 12.0, 14.0, 16.0, 18.0]
     );
 
-    assert_eq!(m3.data.len(), 3);
+    assert_eq!(m3.data.len(), 9);
 }
 
 #[test]
@@ -222,16 +225,9 @@ fn test_sub_matrices() {
     assert_eq!(m3.rows, 3);
     assert_eq!(m3.cols, 3);
 
-    assert_eq!(
-        m3.data,
-        vec![
-            vec![0.0, 0.0, 0.0],
-            vec![0.0, 0.0, 0.0],
-            vec![0.0, 0.0, 0.0]
-        ]
-    );
+    assert_eq!(m3.data, vec![0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
 
-    assert_eq!(m3.data.len(), 3);
+    assert_eq!(m3.data.len(), 9);
 }
 
 #[test]
@@ -254,13 +250,9 @@ fn test_scale_matrix() {
     assert_eq!(m2.cols, 3);
     assert_eq!(
         m2.data,
-        vec![
-            vec![2.0, 4.0, 6.0],
-            vec![8.0, 10.0, 12.0],
-            vec![14.0, 16.0, 18.0]
-        ]
+        vec![2.0, 4.0, 6.0, 8.0, 10.0, 12.0, 14.0, 16.0, 18.0]
     );
-    assert_eq!(m2.data.len(), 3);
+    assert_eq!(m2.data.len(), 9);
 }
 
 #[test]
@@ -277,11 +269,7 @@ fn test_mul_matrices() {
     assert_eq!(m3.cols, 3);
     assert_eq!(
         m3.data,
-        vec![
-            vec![-14.0, 16.0, -18.0],
-            vec![-26.0, 31.0, -36.0],
-            vec![-38.0, 46.0, -54.0]
-        ]
+        vec![-14.0, 16.0, -18.0, -26.0, 31.0, -36.0, -38.0, 46.0, -54.0]
     );
 }
 
@@ -306,7 +294,7 @@ fn test_matrix_vector_mul() {
 
     assert_eq!(m2.rows, 3);
     assert_eq!(m2.cols, 1);
-    assert_eq!(m2.data, vec![vec![14.0], vec![32.0], vec![50.0]]);
+    assert_eq!(m2.data, vec![14.0, 32.0, 50.0]);
 }
 
 #[test]
@@ -327,14 +315,7 @@ fn test_matrix_transpose() {
 
     assert_eq!(m2.rows, 3);
     assert_eq!(m2.cols, 3);
-    assert_eq!(
-        m2.data,
-        vec![
-            vec![1.0, 4.0, 7.0],
-            vec![2.0, 5.0, 8.0],
-            vec![3.0, 6.0, 9.0]
-        ]
-    );
+    assert_eq!(m2.data, vec![1.0, 4.0, 7.0, 2.0, 5.0, 8.0, 3.0, 6.0, 9.0]);
 
     // Test a non-square matrix
     let mut m3 = linear_algebra::Matrix::new(3, 2);
@@ -344,7 +325,7 @@ fn test_matrix_transpose() {
 
     assert_eq!(m4.rows, 2);
     assert_eq!(m4.cols, 3);
-    assert_eq!(m4.data, vec![vec![1.0, 3.0, 5.0], vec![2.0, 4.0, 6.0]]);
+    assert_eq!(m4.data, vec![1.0, 3.0, 5.0, 2.0, 4.0, 6.0]);
 }
 
 #[test]
@@ -355,40 +336,28 @@ pub fn test_matrix_fill() {
 
     assert_eq!(m1.rows, 3);
     assert_eq!(m1.cols, 3);
-    assert_eq!(
-        m1.data,
-        vec![
-            vec![1.0, 1.0, 1.0],
-            vec![1.0, 1.0, 1.0],
-            vec![1.0, 1.0, 1.0]
-        ]
-    );
+    assert_eq!(m1.data, vec![1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0]);
 }
 
 #[test]
 pub fn test_tensor_new() {
-    let t1 = linear_algebra::Tensor::tensor_new(3, 3, 3);
+    let t1 = linear_algebra::Tensor::tensor_new(&[3, 3, 3]);
 
-    assert_eq!(t1.rank, 3);
-    assert_eq!(t1.rows, 3);
-    assert_eq!(t1.cols, 3);
+    assert_eq!(t1.shape, vec![3, 3, 3]);
+    assert_eq!(t1.strides(), vec![9, 3, 1]);
 
     // Check that each 3 x 3 matrix is filled with 0.0
-    for i in 0..t1.rank {
+    for i in 0..t1.matrix_count() {
         assert_eq!(
-            t1.data[i].data,
-            vec![
-                vec![0.0, 0.0, 0.0],
-                vec![0.0, 0.0, 0.0],
-                vec![0.0, 0.0, 0.0]
-            ]
+            t1.tensor_view(i).to_matrix().data,
+            vec![0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]
         );
     }
 }
 
 #[test]
 pub fn test_tensor_set() {
-    let mut t1 = linear_algebra::Tensor::tensor_new(3, 3, 3);
+    let mut t1 = linear_algebra::Tensor::tensor_new(&[3, 3, 3]);
 
     let mut m1 = linear_algebra::Matrix::new(3, 3);
     m1.matrix_set(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
@@ -403,21 +372,19 @@ pub fn test_tensor_set() {
 
     t1.tensor_set(&mv);
 
-    assert_eq!(t1.rank, 3);
-    assert_eq!(t1.rows, 3);
-    assert_eq!(t1.cols, 3);
+    assert_eq!(t1.shape, vec![3, 3, 3]);
 
     // Check that each 3 x 3 corresponds to the correct matrix
 
-    for i in 0..t1.rank {
-        assert_eq!(t1.data[i].data, mv[i].data);
+    for i in 0..t1.matrix_count() {
+        assert_eq!(t1.tensor_view(i).to_matrix().data, mv[i].data);
     }
 }
 
 #[test]
 #[should_panic]
 pub fn test_tensor_set_panic() {
-    let mut t1 = linear_algebra::Tensor::tensor_new(3, 3, 3);
+    let mut t1 = linear_algebra::Tensor::tensor_new(&[3, 3, 3]);
 
     let m1 = linear_algebra::Matrix::new(3, 3);
     let m2 = linear_algebra::Matrix::new(3, 3);
@@ -431,7 +398,7 @@ pub fn test_tensor_set_panic() {
 
 #[test]
 pub fn test_tensor_insert() {
-    let mut t1 = linear_algebra::Tensor::tensor_new(3, 3, 3);
+    let mut t1 = linear_algebra::Tensor::tensor_new(&[3, 3, 3]);
 
     let mut m1 = linear_algebra::Matrix::new(3, 3);
     m1.matrix_set(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
@@ -446,25 +413,23 @@ pub fn test_tensor_insert() {
 
     let clone = mv.clone();
 
-    for i in 0..t1.rank {
+    for i in 0..t1.matrix_count() {
         t1.tensor_insert(clone[i].clone(), i);
     }
 
-    assert_eq!(t1.rank, 3);
-    assert_eq!(t1.rows, 3);
-    assert_eq!(t1.cols, 3);
+    assert_eq!(t1.shape, vec![3, 3, 3]);
 
     // Check that each 3 x 3 corresponds to the correct matrix
 
-    for i in 0..t1.rank {
-        assert_eq!(t1.data[i].data, mv[i].data);
+    for i in 0..t1.matrix_count() {
+        assert_eq!(t1.tensor_view(i).to_matrix().data, mv[i].data);
     }
 }
 
 #[test]
 #[should_panic]
 pub fn test_tensor_insert_panic() {
-    let mut t1 = linear_algebra::Tensor::tensor_new(3, 3, 3);
+    let mut t1 = linear_algebra::Tensor::tensor_new(&[3, 3, 3]);
 
     let m1 = linear_algebra::Matrix::new(3, 3);
 
@@ -473,7 +438,7 @@ pub fn test_tensor_insert_panic() {
 
 #[test]
 pub fn test_tensor_add() {
-    let mut t1 = linear_algebra::Tensor::tensor_new(3, 3, 3);
+    let mut t1 = linear_algebra::Tensor::tensor_new(&[3, 3, 3]);
 
     let mut m1 = linear_algebra::Matrix::new(3, 3);
     m1.matrix_set(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
@@ -488,7 +453,7 @@ pub fn test_tensor_add() {
 
     t1.tensor_set(&mv1);
 
-    let mut t2 = linear_algebra::Tensor::tensor_new(3, 3, 3);
+    let mut t2 = linear_algebra::Tensor::tensor_new(&[3, 3, 3]);
 
     let mut m4 = linear_algebra::Matrix::new(3, 3);
     m4.matrix_set(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
@@ -511,28 +476,26 @@ pub fn test_tensor_add() {
 
     let t3 = linear_algebra::Tensor::tensor_add(&t1, &t2);
 
-    assert_eq!(t3.rank, 3);
-    assert_eq!(t3.rows, 3);
-    assert_eq!(t3.cols, 3);
+    assert_eq!(t3.shape, vec![3, 3, 3]);
 
     // Check that each 3 x 3 corresponds to the correct matrix
-    for i in 0..t3.rank {
-        assert_eq!(t3.data[i].data, expected[i].data);
+    for i in 0..t3.matrix_count() {
+        assert_eq!(t3.tensor_view(i).to_matrix().data, expected[i].data);
     }
 }
 
 #[test]
 #[should_panic]
 pub fn test_tensor_add_panic() {
-    let t1 = linear_algebra::Tensor::tensor_new(3, 3, 3);
-    let t2 = linear_algebra::Tensor::tensor_new(4, 3, 3);
+    let t1 = linear_algebra::Tensor::tensor_new(&[3, 3, 3]);
+    let t2 = linear_algebra::Tensor::tensor_new(&[4, 3, 3]);
 
     linear_algebra::Tensor::tensor_add(&t1, &t2);
 }
 
 #[test]
 pub fn test_tensor_sub() {
-    let mut t1 = linear_algebra::Tensor::tensor_new(3, 3, 3);
+    let mut t1 = linear_algebra::Tensor::tensor_new(&[3, 3, 3]);
 
     let mut m1 = linear_algebra::Matrix::new(3, 3);
     m1.matrix_set(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
@@ -547,7 +510,7 @@ pub fn test_tensor_sub() {
 
     t1.tensor_set(&mv1);
 
-    let mut t2 = linear_algebra::Tensor::tensor_new(3, 3, 3);
+    let mut t2 = linear_algebra::Tensor::tensor_new(&[3, 3, 3]);
 
     let mut m4 = linear_algebra::Matrix::new(3, 3);
     m4.matrix_set(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
@@ -570,28 +533,26 @@ pub fn test_tensor_sub() {
 
     let t3 = linear_algebra::Tensor::tensor_sub(&t1, &t2);
 
-    assert_eq!(t3.rank, 3);
-    assert_eq!(t3.rows, 3);
-    assert_eq!(t3.cols, 3);
+    assert_eq!(t3.shape, vec![3, 3, 3]);
 
     // Check that each 3 x 3 corresponds to the correct matrix
-    for i in 0..t3.rank {
-        assert_eq!(t3.data[i].data, expected[i].data);
+    for i in 0..t3.matrix_count() {
+        assert_eq!(t3.tensor_view(i).to_matrix().data, expected[i].data);
     }
 }
 
 #[test]
 #[should_panic]
 pub fn test_tensor_sub_panic() {
-    let t1 = linear_algebra::Tensor::tensor_new(3, 3, 3);
-    let t2 = linear_algebra::Tensor::tensor_new(4, 3, 3);
+    let t1 = linear_algebra::Tensor::tensor_new(&[3, 3, 3]);
+    let t2 = linear_algebra::Tensor::tensor_new(&[4, 3, 3]);
 
     linear_algebra::Tensor::tensor_sub(&t1, &t2);
 }
 
 #[test]
 pub fn test_tensor_scale() {
-    let mut t1 = linear_algebra::Tensor::tensor_new(3, 3, 3);
+    let mut t1 = linear_algebra::Tensor::tensor_new(&[3, 3, 3]);
 
     let mut m1 = linear_algebra::Matrix::new(3, 3);
 
@@ -616,13 +577,1322 @@ pub fn test_tensor_scale() {
 
     let t2 = linear_algebra::Tensor::tensor_scale(&t1, 2.0);
 
-    assert_eq!(t2.rank, 3);
-    assert_eq!(t2.rows, 3);
-    assert_eq!(t2.cols, 3);
+    assert_eq!(t2.shape, vec![3, 3, 3]);
 
     // Check that each 3 x 3 corresponds to the correct matrix_scaled
 
-    for i in 0..t2.rank {
-        assert_eq!(t2.data[i].data, expected[i].data);
+    for i in 0..t2.matrix_count() {
+        assert_eq!(t2.tensor_view(i).to_matrix().data, expected[i].data);
+    }
+}
+
+#[test]
+fn test_matrix_from_vec() {
+    let m = linear_algebra::Matrix::from_vec(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    assert_eq!(m.rows, 2);
+    assert_eq!(m.cols, 3);
+    assert_eq!(m.get(1, 0), 4.0);
+    assert_eq!(m.as_slice(), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!((m.row_stride(), m.col_stride()), (3, 1));
+}
+
+#[test]
+#[should_panic]
+fn test_matrix_from_vec_panic() {
+    let _m = linear_algebra::Matrix::from_vec(2, 3, vec![1.0, 2.0]);
+}
+
+#[test]
+#[should_panic]
+fn test_matrix_get_panic() {
+    let m = linear_algebra::Matrix::new(2, 2);
+
+    m.get(2, 0);
+}
+
+#[test]
+fn test_matrix_view_transpose() {
+    let m = linear_algebra::Matrix::from_vec(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    let t = m.view().transpose();
+
+    assert_eq!((t.rows(), t.cols()), (3, 2));
+    assert_eq!((t.row_stride(), t.col_stride()), (1, 3));
+    assert_eq!(t.get(2, 1), 6.0);
+    assert!(!t.is_contiguous());
+    assert_eq!(t.as_slice(), None);
+    assert_eq!(t.to_matrix().data, vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+}
+
+#[test]
+fn test_matrix_view_submatrix() {
+    let mut m = linear_algebra::Matrix::new(3, 3);
+    m.matrix_set(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+
+    let s = m.view().submatrix(1, 1, 2, 2);
+
+    assert_eq!((s.rows(), s.cols()), (2, 2));
+    assert_eq!(s.iter().collect::<Vec<_>>(), vec![5.0, 6.0, 8.0, 9.0]);
+
+    // A full-width band of rows is still contiguous
+    let band = m.view().submatrix(1, 0, 2, 3);
+
+    assert_eq!(band.as_slice(), Some(&[4.0, 5.0, 6.0, 7.0, 8.0, 9.0][..]));
+}
+
+#[test]
+#[should_panic]
+fn test_matrix_view_submatrix_panic() {
+    let m = linear_algebra::Matrix::new(3, 3);
+
+    let _s = m.view().submatrix(2, 2, 2, 2);
+}
+
+#[test]
+fn test_matrix_view_mut() {
+    let mut m = linear_algebra::Matrix::new(3, 3);
+
+    {
+        let mut s = m.view_mut().submatrix(0, 1, 2, 2);
+
+        assert_eq!((s.rows(), s.cols()), (2, 2));
+        assert_eq!((s.row_stride(), s.col_stride()), (3, 1));
+
+        s.fill(1.0);
+        *s.get_mut(1, 1) = 2.0;
+    }
+
+    assert_eq!(m.data, vec![0.0, 1.0, 1.0, 0.0, 1.0, 2.0, 0.0, 0.0, 0.0]);
+
+    // Writing through a transposed view lands in the transposed position
+    let source = linear_algebra::Matrix::from_vec(1, 3, vec![7.0, 8.0, 9.0]);
+
+    m.view_mut()
+        .transpose()
+        .submatrix(2, 0, 1, 3)
+        .copy_from(&source.view());
+
+    assert_eq!(m.data, vec![0.0, 1.0, 7.0, 0.0, 1.0, 8.0, 0.0, 0.0, 9.0]);
+}
+
+#[test]
+pub fn test_tensor_view() {
+    let mut t1 = linear_algebra::Tensor::tensor_new(&[2, 2, 2]);
+
+    t1.tensor_view_mut(1).fill(3.0);
+
+    assert_eq!(t1.as_slice(), vec![0.0, 0.0, 0.0, 0.0, 3.0, 3.0, 3.0, 3.0]);
+    assert_eq!(t1.tensor_view(1).as_slice(), Some(&[3.0; 4][..]));
+}
+
+#[test]
+fn test_vector_operators() {
+    let mut v1 = linear_algebra::Vector::new(3);
+    let mut v2 = linear_algebra::Vector::new(3);
+
+    v1.vector_set(vec![1.0, 2.0, 3.0]);
+    v2.vector_set(vec![3.0, 4.0, 5.0]);
+
+    assert_eq!((&v1 + &v2).data, vec![4.0, 6.0, 8.0]);
+    assert_eq!((&v2 - &v1).data, vec![2.0, 2.0, 2.0]);
+    assert_eq!((2.0 * &v1).data, vec![2.0, 4.0, 6.0]);
+    assert_eq!((&v1 * 2.0).data, vec![2.0, 4.0, 6.0]);
+    assert_eq!((-&v1).data, vec![-1.0, -2.0, -3.0]);
+    assert_eq!((v1.clone() + v2.clone()).data, vec![4.0, 6.0, 8.0]);
+
+    let mut v3 = v1.clone();
+    v3 += &v2;
+    v3 *= 0.5;
+    v3 -= v1;
+
+    assert_eq!(v3.data, vec![1.0, 1.0, 1.0]);
+
+    v3[1] = 5.0;
+
+    assert_eq!(v3[1], 5.0);
+}
+
+#[test]
+#[should_panic]
+fn test_vector_operators_panic() {
+    let v1 = linear_algebra::Vector::new(3);
+    let v2 = linear_algebra::Vector::new(4);
+
+    let _v3 = v1 + v2;
+}
+
+#[test]
+fn test_matrix_operators() {
+    let mut m1 = linear_algebra::Matrix::new(3, 3);
+    m1.matrix_set(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+
+    let mut m2 = linear_algebra::Matrix::new(3, 3);
+    m2.matrix_set(vec![-1.0, 2.0, -3.0, 4.0, -5.0, 6.0, -7.0, 8.0, -9.0]);
+
+    let mut v1 = linear_algebra::Vector::new(3);
+    v1.vector_set(vec![1.0, 2.0, 3.0]);
+
+    assert_eq!(&m1 * &m2, linear_algebra::Matrix::matrix_mul(&m1, &m2));
+    assert_eq!(&m1 + &m2, linear_algebra::Matrix::matrix_add(&m1, &m2));
+    assert_eq!(&m1 - &m2, linear_algebra::Matrix::matrix_sub(&m1, &m2));
+    assert_eq!(2.0 * &m1, linear_algebra::Matrix::matrix_scale(&m1, 2.0));
+    assert_eq!(-&m1, linear_algebra::Matrix::matrix_scale(&m1, -1.0));
+    assert_eq!((&m1 * &v1).data, vec![14.0, 32.0, 50.0]);
+
+    // The expression reads like the math: a * b + c
+    let m3 = &m1 * &m2 + &m1;
+    let expected =
+        linear_algebra::Matrix::matrix_add(&linear_algebra::Matrix::matrix_mul(&m1, &m2), &m1);
+
+    assert_eq!(m3, expected);
+
+    let mut m4 = m1.clone();
+    m4 *= &m2;
+    m4 += m1.clone();
+    m4 *= 1.0;
+
+    assert_eq!(m4, expected);
+
+    m4[(2, 1)] = 0.5;
+
+    assert_eq!(m4[(2, 1)], 0.5);
+    assert_eq!(m4.data[7], 0.5);
+}
+
+#[test]
+#[should_panic]
+fn test_matrix_index_panic() {
+    let m1 = linear_algebra::Matrix::new(2, 3);
+
+    let _x = m1[(0, 3)];
+}
+
+#[test]
+#[should_panic]
+fn test_matrix_operators_panic() {
+    let m1 = linear_algebra::Matrix::new(3, 3);
+    let m2 = linear_algebra::Matrix::new(4, 4);
+
+    let _m3 = m1 * m2;
+}
+
+#[test]
+pub fn test_tensor_operators() {
+    let t1 =
+        linear_algebra::Tensor::from_vec(&[2, 2, 2], vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+
+    let t2 = linear_algebra::Tensor::tensor_scale(&t1, 2.0);
+
+    assert_eq!(&t1 + &t1, t2);
+    assert_eq!(&t2 - &t1, t1);
+    assert_eq!(2.0 * &t1, t2);
+    assert_eq!(-(-&t1), t1);
+
+    let mut t3 = t1.clone();
+    t3 += &t1;
+    t3 *= 3.0;
+    t3 -= t2;
+
+    assert_eq!(t3, linear_algebra::Tensor::tensor_scale(&t1, 4.0));
+
+    t3[[1, 0, 1]] = -1.0;
+
+    assert_eq!(t3[[1, 0, 1]], -1.0);
+    assert_eq!(t3.as_slice()[5], -1.0);
+}
+
+#[test]
+fn test_try_vector_functions() {
+    let v1 = linear_algebra::Vector::new(3);
+    let v2 = linear_algebra::Vector::new(4);
+
+    let error = linear_algebra::LinAlgError::DimensionMismatch {
+        left: vec![3],
+        right: vec![4],
+    };
+
+    assert_eq!(
+        linear_algebra::Vector::try_vector_add(&v1, &v2),
+        Err(error.clone())
+    );
+    assert_eq!(
+        linear_algebra::Vector::try_vector_sub(&v1, &v2),
+        Err(error.clone())
+    );
+    assert_eq!(
+        linear_algebra::Vector::try_vector_dot(&v1, &v2),
+        Err(error.clone())
+    );
+    assert_eq!(
+        linear_algebra::Vector::try_vector_cross(&v1, &v2),
+        Err(error)
+    );
+    assert_eq!(linear_algebra::Vector::try_vector_dot(&v1, &v1), Ok(0.0));
+
+    let mut v3 = linear_algebra::Vector::new(2);
+
+    assert!(v3.try_vector_set(vec![1.0, 2.0, 3.0]).is_err());
+    assert!(v3.try_vector_set(vec![1.0, 2.0]).is_ok());
+    assert_eq!(v3.data, vec![1.0, 2.0]);
+}
+
+#[test]
+fn test_try_matrix_functions() {
+    let m1 = linear_algebra::Matrix::new(2, 3);
+    let m2 = linear_algebra::Matrix::new(2, 2);
+    let v1 = linear_algebra::Vector::new(2);
+
+    assert_eq!(
+        linear_algebra::Matrix::try_matrix_add(&m1, &m2),
+        Err(linear_algebra::LinAlgError::DimensionMismatch {
+            left: vec![2, 3],
+            right: vec![2, 2],
+        })
+    );
+    assert!(linear_algebra::Matrix::try_matrix_sub(&m1, &m2).is_err());
+    assert!(linear_algebra::Matrix::try_matrix_mul(&m1, &m2).is_err());
+    assert!(linear_algebra::Matrix::try_matrix_mul(&m2, &m1).is_ok());
+    assert!(linear_algebra::Matrix::try_matrix_vector_mul(&m1, &v1).is_err());
+    assert!(linear_algebra::Matrix::try_from_vec(2, 2, vec![1.0]).is_err());
+
+    assert_eq!(
+        m1.try_get(2, 0),
+        Err(linear_algebra::LinAlgError::IndexOutOfBounds {
+            index: vec![2, 0],
+            shape: vec![2, 3],
+        })
+    );
+    assert_eq!(m1.try_get(1, 2), Ok(0.0));
+    assert!(m1.view().try_submatrix(1, 1, 2, 2).is_err());
+
+    let mut m3 = m1.clone();
+
+    *m3.try_get_mut(1, 2).unwrap() = 5.0;
+
+    assert_eq!(m3.get(1, 2), 5.0);
+    assert!(m3.try_get_mut(0, 3).is_err());
+    assert_eq!(
+        m3.view_mut().transpose().try_get_mut(2, 1).map(|x| *x),
+        Ok(5.0)
+    );
+    assert_eq!(
+        m3.view_mut().try_get_mut(2, 0),
+        Err(linear_algebra::LinAlgError::IndexOutOfBounds {
+            index: vec![2, 0],
+            shape: vec![2, 3],
+        })
+    );
+}
+
+#[test]
+pub fn test_try_tensor_functions() {
+    let mut t1 = linear_algebra::Tensor::tensor_new(&[2, 2, 2]);
+    let t2 = linear_algebra::Tensor::tensor_new(&[3, 2, 2]);
+
+    assert_eq!(
+        linear_algebra::Tensor::try_tensor_add(&t1, &t2),
+        Err(linear_algebra::LinAlgError::DimensionMismatch {
+            left: vec![2, 2, 2],
+            right: vec![3, 2, 2],
+        })
+    );
+    assert!(linear_algebra::Tensor::try_tensor_sub(&t1, &t2).is_err());
+
+    assert_eq!(
+        t1.try_tensor_insert(linear_algebra::Matrix::new(2, 2), 2),
+        Err(linear_algebra::LinAlgError::IndexOutOfBounds {
+            index: vec![2],
+            shape: vec![2, 2, 2],
+        })
+    );
+    assert!(t1
+        .try_tensor_insert(linear_algebra::Matrix::new(3, 2), 0)
+        .is_err());
+
+    // A failed set leaves the tensor untouched
+    let mut m1 = linear_algebra::Matrix::new(2, 2);
+    m1.matrix_fill(1.0);
+
+    let result = t1.try_tensor_set(&[m1, linear_algebra::Matrix::new(1, 1)]);
+
+    assert!(result.is_err());
+    assert_eq!(t1.as_slice(), vec![0.0; 8]);
+}
+
+#[test]
+fn test_lin_alg_error_display() {
+    let error = linear_algebra::LinAlgError::DimensionMismatch {
+        left: vec![3, 3],
+        right: vec![4, 4],
+    };
+
+    assert_eq!(error.to_string(), "Dimension mismatch: [3, 3] and [4, 4]!");
+    assert_eq!(
+        linear_algebra::LinAlgError::NonConvergence { iterations: 10 }.to_string(),
+        "Failed to converge after 10 iterations!"
+    );
+}
+
+#[test]
+fn test_generic_f32() {
+    let mut v1 = linear_algebra::Vector::<f32>::zeros(3);
+    v1.vector_set(vec![1.0, 2.0, 2.0]);
+
+    assert_eq!(linear_algebra::Vector::vector_norm(&v1), 3.0f32);
+    assert_eq!((2.0f32 * &v1).data, vec![2.0f32, 4.0, 4.0]);
+
+    let m1 = linear_algebra::Matrix::from_vec(2, 2, vec![1.0f32, 2.0, 3.0, 4.0]);
+    let m2 = &m1 * &m1;
+
+    assert_eq!(m2.data, vec![7.0f32, 10.0, 15.0, 22.0]);
+}
+
+#[test]
+fn test_generic_integer() {
+    // Count matrices stay exact
+    let m1 = linear_algebra::Matrix::from_vec(2, 3, vec![1u32, 0, 2, 0, 3, 1]);
+    let m2 = linear_algebra::Matrix::matrix_transpose(&m1);
+
+    let m3 = linear_algebra::Matrix::matrix_mul(&m1, &m2);
+
+    assert_eq!(m3.data, vec![5u32, 2, 2, 10]);
+    assert_eq!((3u32 * &m1)[(1, 1)], 9);
+
+    let mut t1 = linear_algebra::Tensor::<i64>::zeros(&[2, 1, 2]);
+    t1[[1, 0, 1]] = 4;
+
+    assert_eq!((-&t1).as_slice(), vec![0i64, 0, 0, -4]);
+
+    let v1 = linear_algebra::Vector {
+        size: 3,
+        data: vec![1i32, 2, 3],
+    };
+
+    assert_eq!(linear_algebra::Vector::vector_dot(&v1, &v1), 14);
+}
+
+#[test]
+fn test_scalar_trait() {
+    use linear_algebra::Scalar;
+
+    assert_eq!(f64::zero(), 0.0);
+    assert_eq!(f64::one(), 1.0);
+    assert_eq!(Scalar::conj(-2.5f64), -2.5);
+    assert_eq!(u8::one(), 1);
+}
+
+// Reference i-j-k product the blocked kernels are checked against
+fn naive_matrix_mul(a: &linear_algebra::Matrix, b: &linear_algebra::Matrix) -> Vec<f64> {
+    let mut result = vec![0.0; a.rows * b.cols];
+
+    for i in 0..a.rows {
+        for j in 0..b.cols {
+            let mut sum = 0.0;
+
+            for k in 0..a.cols {
+                sum += a[(i, k)] * b[(k, j)];
+            }
+
+            result[i * b.cols + j] = sum;
+        }
+    }
+
+    result
+}
+
+fn pseudo_random_matrix(rows: usize, cols: usize, seed: u64) -> linear_algebra::Matrix {
+    let mut state = seed;
+    let data = (0..rows * cols)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+        })
+        .collect();
+
+    linear_algebra::Matrix::from_vec(rows, cols, data)
+}
+
+#[test]
+fn test_matrix_mul_blocked() {
+    // Sizes that straddle the kernel's tile boundaries
+    let a = pseudo_random_matrix(37, 300, 1);
+    let b = pseudo_random_matrix(300, 270, 2);
+
+    let product = linear_algebra::Matrix::matrix_mul(&a, &b);
+
+    // Every entry is summed in the same order, so the match is exact
+    assert_eq!((product.rows, product.cols), (37, 270));
+    assert_eq!(product.data, naive_matrix_mul(&a, &b));
+}
+
+#[test]
+fn test_matrix_mul_parallel() {
+    let a = pseudo_random_matrix(45, 130, 3);
+    let b = pseudo_random_matrix(130, 61, 4);
+
+    let expected = linear_algebra::Matrix::matrix_mul(&a, &b);
+
+    // More threads than rows, and 0 for one per core
+    for threads in [1, 2, 7, 64, 0] {
+        let product = linear_algebra::Matrix::matrix_mul_parallel(&a, &b, threads);
+
+        assert_eq!(product, expected);
     }
+
+    let empty = linear_algebra::Matrix::matrix_mul_parallel(
+        &linear_algebra::Matrix::new(0, 3),
+        &linear_algebra::Matrix::new(3, 2),
+        4,
+    );
+
+    assert_eq!((empty.rows, empty.cols), (0, 2));
+}
+
+#[test]
+#[should_panic]
+fn test_matrix_mul_parallel_panic() {
+    let a = linear_algebra::Matrix::new(2, 3);
+    let b = linear_algebra::Matrix::new(2, 3);
+
+    linear_algebra::Matrix::matrix_mul_parallel(&a, &b, 2);
+}
+
+#[test]
+fn test_try_matrix_mul_parallel() {
+    let a = linear_algebra::Matrix::new(2, 3);
+    let b = linear_algebra::Matrix::new(2, 3);
+
+    assert_eq!(
+        linear_algebra::Matrix::try_matrix_mul_parallel(&a, &b, 2),
+        Err(linear_algebra::LinAlgError::DimensionMismatch {
+            left: vec![2, 3],
+            right: vec![2, 3],
+        })
+    );
+}
+
+fn counting_matrix(rows: usize, cols: usize) -> linear_algebra::Matrix {
+    linear_algebra::Matrix::from_vec(rows, cols, (0..rows * cols).map(|x| x as f64).collect())
+}
+
+#[test]
+fn test_matrix_row_col() {
+    let mut m = counting_matrix(3, 4);
+
+    assert_eq!(m.row(1).data, vec![4.0, 5.0, 6.0, 7.0]);
+    assert_eq!(m.col(2).data, vec![2.0, 6.0, 10.0]);
+
+    let mut v = linear_algebra::Vector::new(4);
+    v.vector_set(vec![-1.0, -2.0, -3.0, -4.0]);
+    m.set_row(0, &v);
+
+    let mut w = linear_algebra::Vector::new(3);
+    w.vector_set(vec![9.0, 9.0, 9.0]);
+    m.set_col(3, &w);
+
+    assert_eq!(
+        m.data,
+        vec![-1.0, -2.0, -3.0, 9.0, 4.0, 5.0, 6.0, 9.0, 8.0, 9.0, 10.0, 9.0]
+    );
+
+    assert_eq!(
+        m.try_row(3),
+        Err(linear_algebra::LinAlgError::IndexOutOfBounds {
+            index: vec![3, 0],
+            shape: vec![3, 4],
+        })
+    );
+    assert!(m.try_col(4).is_err());
+    assert!(m.try_set_row(0, &w).is_err());
+    assert!(m.try_set_col(0, &v).is_err());
+}
+
+#[test]
+#[should_panic]
+fn test_matrix_row_panic() {
+    let m = counting_matrix(2, 2);
+
+    m.row(2);
+}
+
+#[test]
+fn test_matrix_slice() {
+    let mut m = counting_matrix(4, 5);
+
+    let view = m.slice(1..3, 2..);
+
+    assert_eq!((view.rows(), view.cols()), (2, 3));
+    assert_eq!(view.to_matrix().data, vec![7.0, 8.0, 9.0, 12.0, 13.0, 14.0]);
+    assert_eq!(
+        m.slice(.., 4..=4).to_matrix().data,
+        vec![4.0, 9.0, 14.0, 19.0]
+    );
+    assert_eq!(m.slice(2..2, ..).rows(), 0);
+
+    m.slice_mut(..2, ..2).fill(-1.0);
+    m.slice_mut(3.., 3..)
+        .copy_from(&counting_matrix(1, 2).view());
+
+    assert_eq!(m.row(0).data, vec![-1.0, -1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(m.row(1).data, vec![-1.0, -1.0, 7.0, 8.0, 9.0]);
+    assert_eq!(m.row(3).data, vec![15.0, 16.0, 17.0, 0.0, 1.0]);
+
+    assert_eq!(
+        m.try_slice(0..5, ..).unwrap_err(),
+        linear_algebra::LinAlgError::IndexOutOfBounds {
+            index: vec![0, 5],
+            shape: vec![4],
+        }
+    );
+    assert!(m.try_slice_mut(.., 3..6).is_err());
+
+    // Bounds at usize::MAX are errors, not overflows
+    assert_eq!(
+        m.try_slice(0..=usize::MAX, ..).unwrap_err(),
+        linear_algebra::LinAlgError::IndexOutOfBounds {
+            index: vec![usize::MAX],
+            shape: vec![4],
+        }
+    );
+    assert!(m
+        .try_slice(.., (Bound::Excluded(usize::MAX), Bound::Unbounded))
+        .is_err());
+}
+
+#[test]
+#[should_panic]
+fn test_matrix_slice_panic() {
+    let m = counting_matrix(2, 2);
+
+    m.slice(.., 1..3);
+}
+
+#[test]
+fn test_matrix_stack() {
+    let a = counting_matrix(2, 2);
+    let b = counting_matrix(2, 1);
+    let c = counting_matrix(1, 2);
+
+    assert_eq!(
+        linear_algebra::Matrix::hstack(&[&a, &b]).data,
+        vec![0.0, 1.0, 0.0, 2.0, 3.0, 1.0]
+    );
+    assert_eq!(
+        linear_algebra::Matrix::vstack(&[&a, &c]).data,
+        vec![0.0, 1.0, 2.0, 3.0, 0.0, 1.0]
+    );
+
+    assert_eq!(
+        linear_algebra::Matrix::try_hstack(&[&a, &c]),
+        Err(linear_algebra::LinAlgError::DimensionMismatch {
+            left: vec![2, 2],
+            right: vec![1, 2],
+        })
+    );
+    assert!(linear_algebra::Matrix::try_vstack(&[&a, &b]).is_err());
+
+    let empty = linear_algebra::Matrix::<f64>::hstack(&[]);
+    assert_eq!((empty.rows, empty.cols), (0, 0));
+}
+
+#[test]
+#[should_panic]
+fn test_matrix_vstack_panic() {
+    linear_algebra::Matrix::vstack(&[&counting_matrix(1, 2), &counting_matrix(1, 3)]);
+}
+
+#[test]
+fn test_matrix_block_diag() {
+    let a = counting_matrix(1, 2);
+    let b = counting_matrix(2, 1);
+
+    let m = linear_algebra::Matrix::block_diag(&[&a, &b]);
+
+    assert_eq!((m.rows, m.cols), (3, 3));
+    assert_eq!(m.data, vec![0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn test_matrix_swap_and_select() {
+    let mut m = counting_matrix(3, 3);
+
+    m.swap_rows(0, 2);
+    assert_eq!(m.data, vec![6.0, 7.0, 8.0, 3.0, 4.0, 5.0, 0.0, 1.0, 2.0]);
+
+    m.swap_cols(0, 1);
+    assert_eq!(m.data, vec![7.0, 6.0, 8.0, 4.0, 3.0, 5.0, 1.0, 0.0, 2.0]);
+
+    m.swap_rows(1, 1);
+    assert!(m.try_swap_rows(0, 3).is_err());
+    assert!(m.try_swap_cols(3, 0).is_err());
+
+    let m = counting_matrix(3, 4);
+
+    assert_eq!(
+        m.select_rows(&[2, 0, 2]).data,
+        vec![8.0, 9.0, 10.0, 11.0, 0.0, 1.0, 2.0, 3.0, 8.0, 9.0, 10.0, 11.0]
+    );
+    assert_eq!(
+        m.select_cols(&[3, 1]).data,
+        vec![3.0, 1.0, 7.0, 5.0, 11.0, 9.0]
+    );
+    assert_eq!(m.select_rows(&[]).rows, 0);
+
+    assert!(m.try_select_rows(&[0, 3]).is_err());
+    assert_eq!(
+        m.try_select_cols(&[4]),
+        Err(linear_algebra::LinAlgError::IndexOutOfBounds {
+            index: vec![0, 4],
+            shape: vec![3, 4],
+        })
+    );
+}
+
+#[test]
+fn test_tensor_nd_access() {
+    let mut t1 = linear_algebra::Tensor::tensor_new(&[2, 3, 4, 5]);
+
+    assert_eq!(t1.ndim(), 4);
+    assert_eq!(t1.size(), 120);
+    assert_eq!(t1.strides(), vec![60, 20, 5, 1]);
+    assert_eq!(t1.matrix_count(), 6);
+
+    t1[[1, 2, 3, 4]] = 7.0;
+    *t1.get_mut(&[0, 1, 0, 2]) = -1.0;
+
+    assert_eq!(t1.get(&[1, 2, 3, 4]), 7.0);
+    assert_eq!(t1.as_slice()[119], 7.0);
+    assert_eq!(t1.as_slice()[22], -1.0);
+
+    // Matrix 5 is [1, 2, .., ..]
+    assert_eq!(t1.tensor_view(5).get(3, 4), 7.0);
+    assert_eq!(t1.tensor_view(1).get(0, 2), -1.0);
+
+    assert_eq!(
+        t1.try_get(&[1, 3, 0, 0]),
+        Err(linear_algebra::LinAlgError::IndexOutOfBounds {
+            index: vec![1, 3, 0, 0],
+            shape: vec![2, 3, 4, 5],
+        })
+    );
+    assert!(t1.try_get(&[1, 2, 3]).is_err());
+
+    // 1-D signals and scalars
+    let signal = linear_algebra::Tensor::from_vec(&[4], vec![1.0, 2.0, 3.0, 4.0]);
+
+    assert_eq!(signal[[2]], 3.0);
+    assert_eq!(signal.matrix_count(), 0);
+
+    let scalar = linear_algebra::Tensor::from_vec(&[], vec![5.0]);
+
+    assert_eq!(scalar.ndim(), 0);
+    assert_eq!(scalar[[]], 5.0);
+}
+
+#[test]
+#[should_panic]
+fn test_tensor_index_panic() {
+    let t1 = linear_algebra::Tensor::tensor_new(&[2, 2]);
+
+    let _x = t1[[0, 0, 0]];
+}
+
+#[test]
+fn test_tensor_reshape_permute() {
+    let t1 = linear_algebra::Tensor::from_vec(&[2, 3], vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    let t2 = t1.reshape(&[3, 2]);
+
+    assert_eq!(t2.shape, vec![3, 2]);
+    assert_eq!(t2[[2, 0]], 5.0);
+
+    // Permuting only swaps strides
+    let t3 = t1.clone().permute(&[1, 0]);
+
+    assert_eq!(t3.shape, vec![3, 2]);
+    assert_eq!(t3.strides(), vec![1, 3]);
+    assert_eq!(t3.as_slice(), t1.as_slice());
+    assert!(!t3.is_contiguous());
+    assert_eq!(t3[[2, 0]], 3.0);
+    assert_eq!(
+        t3.iter().collect::<Vec<_>>(),
+        vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]
+    );
+
+    // Reshaping a permuted tensor uses its logical order
+    assert_eq!(
+        t3.reshape(&[6]).as_slice(),
+        vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]
+    );
+    assert_eq!(t3.to_contiguous().strides(), vec![2, 1]);
+    assert_eq!(
+        t3.to_matrix(),
+        linear_algebra::Matrix::matrix_transpose(&t1.to_matrix())
+    );
+
+    // Equality ignores the layout
+    assert_eq!(
+        t3,
+        linear_algebra::Tensor::from_vec(&[3, 2], vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0])
+    );
+    assert_eq!(&t3 + &t3, linear_algebra::Tensor::tensor_scale(&t3, 2.0));
+
+    // Views of a permuted [2, 2, 3] tensor follow the strides
+    let t4 = linear_algebra::Tensor::from_vec(&[2, 2, 3], (0..12).map(|x| x as f64).collect());
+    let t5 = t4.permute(&[0, 2, 1]);
+
+    assert_eq!(t5.shape, vec![2, 3, 2]);
+    assert_eq!(
+        t5.tensor_view(1).to_matrix().data,
+        vec![6.0, 9.0, 7.0, 10.0, 8.0, 11.0]
+    );
+
+    assert_eq!(
+        t1.try_reshape(&[4, 2]),
+        Err(linear_algebra::LinAlgError::DimensionMismatch {
+            left: vec![2, 3],
+            right: vec![4, 2],
+        })
+    );
+    assert!(t1.clone().try_permute(&[0, 0]).is_err());
+    assert!(t1.clone().try_permute(&[0]).is_err());
+    assert!(t1.clone().try_permute(&[0, 2]).is_err());
+}
+
+#[test]
+fn test_tensor_conversions() {
+    let mut v1 = linear_algebra::Vector::new(3);
+    v1.vector_set(vec![1.0, 2.0, 3.0]);
+
+    let t1 = linear_algebra::Tensor::from(v1.clone());
+
+    assert_eq!(t1.shape, vec![3]);
+    assert_eq!(t1.to_vector(), v1);
+    assert!(t1.try_to_matrix().is_err());
+
+    let m1 = linear_algebra::Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+    let t2 = linear_algebra::Tensor::from(m1.clone());
+
+    assert_eq!(t2.shape, vec![2, 2]);
+    assert_eq!(t2.to_matrix(), m1);
+    assert!(t2.try_to_vector().is_err());
+
+    let m2 = linear_algebra::Matrix::from_vec(2, 2, vec![5.0, 6.0, 7.0, 8.0]);
+    let t3 = linear_algebra::Tensor::from_matrices(&[m1, m2.clone()]);
+
+    assert_eq!(t3.shape, vec![2, 2, 2]);
+    assert_eq!(t3.tensor_view(1).to_matrix(), m2);
+    assert_eq!(t3[[1, 1, 0]], 7.0);
+
+    assert_eq!(
+        linear_algebra::Tensor::try_from_vec(&[2, 2], vec![1.0; 3]),
+        Err(linear_algebra::LinAlgError::DimensionMismatch {
+            left: vec![2, 2],
+            right: vec![3],
+        })
+    );
+}
+
+#[test]
+fn test_broadcast_shapes() {
+    assert_eq!(
+        linear_algebra::broadcast_shapes(&[8, 1, 6, 1], &[7, 1, 5]),
+        Ok(vec![8, 7, 6, 5])
+    );
+    assert_eq!(
+        linear_algebra::broadcast_shapes(&[], &[2, 3]),
+        Ok(vec![2, 3])
+    );
+    assert_eq!(
+        linear_algebra::broadcast_shapes(&[2, 3, 4], &[2, 4]),
+        Err(linear_algebra::LinAlgError::BroadcastMismatch {
+            left: vec![2, 3, 4],
+            right: vec![2, 4],
+            axis: 1,
+        })
+    );
+    assert_eq!(
+        linear_algebra::LinAlgError::BroadcastMismatch {
+            left: vec![2, 3],
+            right: vec![4],
+            axis: 1,
+        }
+        .to_string(),
+        "Shapes [2, 3] and [4] cannot be broadcast together at axis 1!"
+    );
+}
+
+#[test]
+fn test_matrix_broadcast() {
+    let m1 = linear_algebra::Matrix::from_vec(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    // A bias row, as a vector or a 1 x 3 matrix
+    let mut bias = linear_algebra::Vector::new(3);
+    bias.vector_set(vec![10.0, 20.0, 30.0]);
+
+    let expected = vec![11.0, 22.0, 33.0, 14.0, 25.0, 36.0];
+
+    assert_eq!(
+        linear_algebra::Matrix::broadcast_add(&m1, &bias).data,
+        expected
+    );
+    assert_eq!(
+        linear_algebra::Matrix::broadcast_add(
+            &m1,
+            &linear_algebra::Matrix::from_vec(1, 3, vec![10.0, 20.0, 30.0])
+        )
+        .data,
+        expected
+    );
+
+    // A column scales each row
+    let column = linear_algebra::Matrix::from_vec(2, 1, vec![2.0, -1.0]);
+
+    assert_eq!(
+        linear_algebra::Matrix::broadcast_mul(&m1, &column).data,
+        vec![2.0, 4.0, 6.0, -4.0, -5.0, -6.0]
+    );
+    assert_eq!(
+        linear_algebra::Matrix::broadcast_div(&m1, &2.0).data,
+        vec![0.5, 1.0, 1.5, 2.0, 2.5, 3.0]
+    );
+    assert_eq!(
+        linear_algebra::Matrix::broadcast_sub(&m1, &1.0).data,
+        vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]
+    );
+    assert_eq!(
+        linear_algebra::Matrix::broadcast_pow(&m1, &2.0).data,
+        vec![1.0, 4.0, 9.0, 16.0, 25.0, 36.0]
+    );
+
+    // A row against a column grows to the outer shape
+    let row = linear_algebra::Matrix::from_vec(1, 3, vec![1.0, 2.0, 3.0]);
+    let outer = linear_algebra::Matrix::broadcast_mul(&row, &column);
+
+    assert_eq!((outer.rows, outer.cols), (2, 3));
+    assert_eq!(outer.data, vec![2.0, 4.0, 6.0, -1.0, -2.0, -3.0]);
+
+    let mut short = linear_algebra::Vector::new(2);
+    short.vector_set(vec![1.0, 2.0]);
+
+    assert_eq!(
+        linear_algebra::Matrix::try_broadcast_add(&m1, &short),
+        Err(linear_algebra::LinAlgError::BroadcastMismatch {
+            left: vec![2, 3],
+            right: vec![2],
+            axis: 1,
+        })
+    );
+    assert!(linear_algebra::Matrix::try_broadcast_add(
+        &m1,
+        &linear_algebra::Tensor::tensor_new(&[2, 2, 3])
+    )
+    .is_err());
+}
+
+#[test]
+#[should_panic]
+fn test_matrix_broadcast_panic() {
+    let m1 = linear_algebra::Matrix::new(2, 3);
+    let m2 = linear_algebra::Matrix::new(3, 2);
+
+    linear_algebra::Matrix::broadcast_add(&m1, &m2);
+}
+
+#[test]
+fn test_vector_tensor_broadcast() {
+    let mut v1 = linear_algebra::Vector::new(4);
+    v1.vector_set(vec![1.0, -2.0, f64::NAN, 4.0]);
+
+    let min = linear_algebra::Vector::broadcast_min(&v1, &0.0);
+    let max = linear_algebra::Vector::broadcast_max(&v1, &0.0);
+
+    assert_eq!(min.data[..2], [0.0, -2.0]);
+    assert!(min.data[2].is_nan());
+    assert_eq!(max.data[3], 4.0);
+    assert!(max.data[2].is_nan());
+
+    let mut v2 = linear_algebra::Vector::<i32>::zeros(3);
+    v2.data = vec![3, 1, 2];
+
+    assert_eq!(
+        linear_algebra::Vector::broadcast_max(&v2, &2).data,
+        vec![3, 2, 2]
+    );
+    assert!(
+        linear_algebra::Vector::try_broadcast_add(&v1, &linear_algebra::Matrix::new(1, 4)).is_err()
+    );
+
+    // A per-channel bias over a [batch, channels, width] tensor
+    let t1 = linear_algebra::Tensor::from_vec(&[2, 2, 2], (0..8).map(|x| x as f64).collect());
+    let bias = linear_algebra::Tensor::from_vec(&[2, 1], vec![100.0, 200.0]);
+    let t2 = linear_algebra::Tensor::broadcast_add(&t1, &bias);
+
+    assert_eq!(t2.shape, vec![2, 2, 2]);
+    assert_eq!(
+        t2.as_slice(),
+        vec![100.0, 101.0, 202.0, 203.0, 104.0, 105.0, 206.0, 207.0]
+    );
+
+    // Broadcasting works on permuted layouts too
+    let t3 = t1.clone().permute(&[2, 1, 0]);
+    let t4 = linear_algebra::Tensor::broadcast_sub(&t3, &t3.to_contiguous());
+
+    assert_eq!(t4.as_slice(), vec![0.0; 8]);
+
+    let mut t5 = bias.broadcast_to(&[3, 2, 4]);
+
+    assert!(t5.is_contiguous());
+    assert_eq!(t5.as_slice().len(), 24);
+    assert_eq!(t5[[2, 1, 3]], 200.0);
+
+    // The repeats are copies, writing one leaves the others alone
+    t5[[0, 1, 0]] = 99.0;
+
+    assert_eq!(t5[[1, 1, 0]], 200.0);
+    assert_eq!(t5.sum(None, false).as_slice(), vec![12.0 * 300.0 - 101.0]);
+    assert!(bias.try_broadcast_to(&[3, 4]).is_err());
+    assert!(bias.try_broadcast_to(&[1]).is_err());
+}
+
+#[test]
+fn test_matrix_reductions() {
+    let m1 = linear_algebra::Matrix::from_vec(2, 3, vec![1.0, 5.0, 3.0, 4.0, 2.0, 6.0]);
+
+    // Column means and row sums
+    assert_eq!(
+        m1.mean(Some(0), false).to_vector().data,
+        vec![2.5, 3.5, 4.5]
+    );
+    assert_eq!(m1.sum(Some(1), false).as_slice(), vec![9.0, 12.0]);
+    assert_eq!(m1.sum(None, false).as_slice(), vec![21.0]);
+    assert_eq!(m1.prod(Some(0), false).as_slice(), vec![4.0, 10.0, 18.0]);
+
+    // Kept dimensions broadcast back against the matrix
+    let means = m1.mean(Some(0), true);
+
+    assert_eq!(means.shape, vec![1, 3]);
+    assert_eq!(m1.sum(None, true).shape, vec![1, 1]);
+
+    let centered = linear_algebra::Matrix::broadcast_sub(&m1, &means);
+
+    assert_eq!(centered.data, vec![-1.5, 1.5, -1.5, 1.5, -1.5, 1.5]);
+
+    assert_eq!(m1.min(Some(1), false).as_slice(), vec![1.0, 2.0]);
+    assert_eq!(m1.max(None, false).as_slice(), vec![6.0]);
+    assert_eq!(m1.argmin(Some(0), false).as_slice(), vec![0, 1, 0]);
+    assert_eq!(m1.argmax(Some(1), false).as_slice(), vec![1, 2]);
+    assert_eq!(m1.argmax(None, false).as_slice(), vec![5]);
+
+    assert_eq!(
+        m1.variance(Some(0), false, 0).as_slice(),
+        vec![2.25, 2.25, 2.25]
+    );
+    assert_eq!(
+        m1.variance(Some(0), false, 1).as_slice(),
+        vec![4.5, 4.5, 4.5]
+    );
+    let norms = m1.norm(Some(1), false).as_slice().to_vec();
+
+    assert!((norms[0] - 35.0f64.sqrt()).abs() < 1e-14);
+    assert!((norms[1] - 56.0f64.sqrt()).abs() < 1e-14);
+
+    assert_eq!(
+        m1.try_sum(Some(2), false),
+        Err(linear_algebra::LinAlgError::IndexOutOfBounds {
+            index: vec![2],
+            shape: vec![2, 3],
+        })
+    );
+
+    // ddof must leave a positive divisor, here with lanes of 2 elements
+    for ddof in [2, 5] {
+        assert_eq!(
+            m1.try_variance(Some(0), false, ddof),
+            Err(linear_algebra::LinAlgError::InvalidParameter {
+                name: "ddof",
+                reason: "must be less than the number of elements reduced",
+            })
+        );
+    }
+
+    assert_eq!(m1.variance(None, false, 5).as_slice(), vec![17.5]);
+    assert!(m1.try_variance(None, false, 6).is_err());
+    assert!(m1.try_variance(Some(2), false, 5).is_err());
+}
+
+#[test]
+fn test_tensor_reductions() {
+    let t1 = linear_algebra::Tensor::from_vec(&[2, 3, 2], (0..12).map(|x| x as f64).collect());
+
+    let s = t1.sum(Some(1), false);
+
+    assert_eq!(s.shape, vec![2, 2]);
+    assert_eq!(s.as_slice(), vec![6.0, 9.0, 24.0, 27.0]);
+    assert_eq!(t1.sum(Some(1), true).shape, vec![2, 1, 2]);
+    assert_eq!(t1.sum(None, true).shape, vec![1, 1, 1]);
+    assert_eq!(t1.mean(None, false).as_slice(), vec![5.5]);
+
+    // Reductions follow the logical layout of permuted tensors
+    let t2 = t1.clone().permute(&[2, 0, 1]);
+
+    assert_eq!(
+        t2.sum(Some(2), false).as_slice(),
+        vec![6.0, 24.0, 9.0, 27.0]
+    );
+    assert_eq!(t2.argmax(None, false).as_slice(), vec![11]);
+
+    // NaN wins min, max and their indices
+    let t3 = linear_algebra::Tensor::from_vec(&[4], vec![1.0, f64::NAN, -1.0, 3.0]);
+
+    assert!(t3.min(None, false).as_slice()[0].is_nan());
+    assert_eq!(t3.argmax(None, false).as_slice(), vec![1]);
+
+    // Integer reductions
+    let t4 = linear_algebra::Tensor::<i64>::from_vec(&[2, 2], vec![3, -1, 4, 1]);
+
+    assert_eq!(t4.sum(Some(0), false).as_slice(), vec![7, 0]);
+    assert_eq!(t4.prod(None, false).as_slice(), vec![-12]);
+    assert_eq!(t4.argmin(Some(1), false).as_slice(), vec![1, 1]);
+
+    // Empty lanes
+    let empty = linear_algebra::Tensor::tensor_new(&[2, 0]);
+
+    assert_eq!(empty.sum(Some(1), false).as_slice(), vec![0.0, 0.0]);
+    assert_eq!(
+        empty.try_max(Some(1), false),
+        Err(linear_algebra::LinAlgError::EmptyReduction { shape: vec![2, 0] })
+    );
+    assert_eq!(empty.max(Some(0), false).as_slice(), Vec::<f64>::new());
+}
+
+#[test]
+fn test_stable_reductions() {
+    // Naive summation of 1e6 copies of 0.1 drifts by ~1e-6
+    let t1 = linear_algebra::Tensor::<f64>::from_vec(&[1_000_000], vec![0.1; 1_000_000]);
+
+    assert!((t1.sum(None, false).as_slice()[0] - 100_000.0).abs() < 1e-8);
+
+    // A large offset does not destroy the variance
+    let t2 =
+        linear_algebra::Tensor::from_vec(&[4], vec![1e9 + 4.0, 1e9 + 7.0, 1e9 + 13.0, 1e9 + 16.0]);
+
+    assert_eq!(t2.variance(None, false, 1).as_slice(), vec![30.0]);
+
+    // Norms of huge and tiny entries neither overflow nor underflow
+    let t3 = linear_algebra::Tensor::<f64>::from_vec(&[2], vec![3e200, 4e200]);
+    let t4 = linear_algebra::Tensor::<f64>::from_vec(&[2], vec![3e-200, 4e-200]);
+
+    assert!((t3.norm(None, false).as_slice()[0] / 5e200 - 1.0).abs() < 1e-14);
+    assert!((t4.norm(None, false).as_slice()[0] / 5e-200 - 1.0).abs() < 1e-14);
+
+    // Infinite entries give an infinite norm, NaN a NaN one
+    let t5 = linear_algebra::Tensor::from_vec(&[2, 2], vec![f64::INFINITY, 1.0, f64::NAN, 1.0]);
+
+    assert_eq!(t5.norm(Some(1), false).as_slice()[0], f64::INFINITY);
+    assert!(t5.norm(Some(1), false).as_slice()[1].is_nan());
+    assert!(t5.norm(None, false).as_slice()[0].is_nan());
+}
+
+#[test]
+#[should_panic]
+fn test_reduction_panic() {
+    let t1 = linear_algebra::Tensor::tensor_new(&[2, 2]);
+
+    t1.mean(Some(3), false);
+}
+
+#[test]
+fn test_vector_p_norms() {
+    let mut v1 = linear_algebra::Vector::new(4);
+    v1.vector_set(vec![3.0, -4.0, 0.0, 1.0]);
+
+    let norm = |n| linear_algebra::Vector::vector_p_norm(&v1, n);
+
+    assert_eq!(norm(linear_algebra::VectorNorm::One), 8.0);
+    assert_eq!(norm(linear_algebra::VectorNorm::Two), 26.0f64.sqrt());
+    assert_eq!(norm(linear_algebra::VectorNorm::Inf), 4.0);
+    assert_eq!(norm(linear_algebra::VectorNorm::P(1.0)), 8.0);
+    assert_eq!(norm(linear_algebra::VectorNorm::P(f64::INFINITY)), 4.0);
+    assert!((norm(linear_algebra::VectorNorm::P(3.0)) - 92.0f64.cbrt()).abs() < 1e-14);
+
+    // Large p approaches the maximum without overflowing
+    assert!((norm(linear_algebra::VectorNorm::P(500.0)) - 4.0).abs() < 1e-12);
+
+    // Entries whose squares overflow or underflow
+    let mut v2 = linear_algebra::Vector::new(2);
+    v2.vector_set(vec![3e200, -4e200]);
+
+    assert!((linear_algebra::Vector::vector_norm(&v2) / 5e200 - 1.0).abs() < 1e-15);
+    let cubic = linear_algebra::Vector::vector_p_norm(&v2, linear_algebra::VectorNorm::P(3.0));
+
+    assert!((cubic / (91.0f64.cbrt() * 1e200) - 1.0).abs() < 1e-14);
+
+    v2.vector_set(vec![3e-170, 4e-170]);
+
+    assert!((linear_algebra::Vector::vector_norm(&v2) / 5e-170 - 1.0).abs() < 1e-15);
+
+    let zero = linear_algebra::Vector::new(3);
+
+    assert_eq!(linear_algebra::Vector::vector_norm(&zero), 0.0);
+    assert_eq!(
+        linear_algebra::Vector::vector_p_norm(&zero, linear_algebra::VectorNorm::P(3.0)),
+        0.0
+    );
+
+    // f32 overflows at a much smaller magnitude
+    let mut v3 = linear_algebra::Vector::<f32>::zeros(2);
+    v3.data = vec![3e30, 4e30];
+
+    assert_eq!(linear_algebra::Vector::vector_norm(&v3), 5e30);
+
+    // An infinite entry gives an infinite norm, a NaN entry a NaN one
+    let mut v4 = linear_algebra::Vector::new(2);
+    v4.vector_set(vec![f64::INFINITY, 1.0]);
+
+    for n in [
+        linear_algebra::VectorNorm::One,
+        linear_algebra::VectorNorm::Two,
+        linear_algebra::VectorNorm::Inf,
+        linear_algebra::VectorNorm::P(3.0),
+    ] {
+        assert_eq!(linear_algebra::Vector::vector_p_norm(&v4, n), f64::INFINITY);
+    }
+
+    v4.vector_set(vec![f64::NAN, f64::INFINITY]);
+
+    for n in [
+        linear_algebra::VectorNorm::One,
+        linear_algebra::VectorNorm::Two,
+        linear_algebra::VectorNorm::Inf,
+        linear_algebra::VectorNorm::P(3.0),
+    ] {
+        assert!(linear_algebra::Vector::vector_p_norm(&v4, n).is_nan());
+    }
+
+    // p must be positive
+    for p in [0.0, -1.0, f64::NAN] {
+        assert_eq!(
+            linear_algebra::Vector::try_vector_p_norm(&v1, linear_algebra::VectorNorm::P(p)),
+            Err(linear_algebra::LinAlgError::InvalidParameter {
+                name: "p",
+                reason: "must be positive",
+            })
+        );
+    }
+
+    assert_eq!(
+        linear_algebra::Vector::try_vector_p_norm(&v1, linear_algebra::VectorNorm::P(0.5)),
+        Ok(norm(linear_algebra::VectorNorm::P(0.5)))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_vector_p_norm_panic() {
+    let v = linear_algebra::Vector::<f64>::zeros(2);
+
+    let _n = linear_algebra::Vector::vector_p_norm(&v, linear_algebra::VectorNorm::P(0.0));
+}
+
+#[test]
+fn test_matrix_norms() {
+    let m1 = linear_algebra::Matrix::from_vec(2, 2, vec![1.0, -2.0, 3.0, 4.0]);
+
+    let norm = |n| linear_algebra::Matrix::matrix_norm(&m1, n);
+
+    assert_eq!(norm(linear_algebra::MatrixNorm::Frobenius), 30.0f64.sqrt());
+    assert_eq!(norm(linear_algebra::MatrixNorm::One), 6.0);
+    assert_eq!(norm(linear_algebra::MatrixNorm::Inf), 7.0);
+    assert_eq!(norm(linear_algebra::MatrixNorm::Max), 4.0);
+
+    // Singular values of [[1, -2], [3, 4]] are sqrt(15 +- 5 sqrt(5))
+    let s1 = (15.0 + 5.0 * 5.0f64.sqrt()).sqrt();
+    let s2 = (15.0 - 5.0 * 5.0f64.sqrt()).sqrt();
+
+    assert!((norm(linear_algebra::MatrixNorm::Spectral) - s1).abs() < 1e-12);
+    assert!((norm(linear_algebra::MatrixNorm::Nuclear) - (s1 + s2)).abs() < 1e-12);
+
+    // Rectangular and f32 matrices
+    let m2 = linear_algebra::Matrix::<f32>::from_vec(1, 3, vec![2.0, -3.0, 6.0]);
+
+    assert_eq!(
+        linear_algebra::Matrix::matrix_norm(&m2, linear_algebra::MatrixNorm::Spectral),
+        7.0
+    );
+    assert_eq!(
+        linear_algebra::Matrix::matrix_norm(&m2, linear_algebra::MatrixNorm::One),
+        6.0
+    );
+    assert_eq!(
+        linear_algebra::Matrix::matrix_norm(&m2, linear_algebra::MatrixNorm::Inf),
+        11.0
+    );
+
+    let big = linear_algebra::Matrix::<f64>::from_vec(1, 2, vec![3e300, 4e300]);
+
+    assert!(
+        (linear_algebra::Matrix::matrix_norm(&big, linear_algebra::MatrixNorm::Frobenius) / 5e300
+            - 1.0)
+            .abs()
+            < 1e-15
+    );
+    assert_eq!(
+        linear_algebra::Matrix::matrix_norm(
+            &linear_algebra::Matrix::new(0, 0),
+            linear_algebra::MatrixNorm::Nuclear
+        ),
+        0.0
+    );
+
+    let nan = linear_algebra::Matrix::from_vec(2, 2, vec![1.0, f64::NAN, 2.0, 3.0]);
+
+    for n in [
+        linear_algebra::MatrixNorm::Frobenius,
+        linear_algebra::MatrixNorm::One,
+        linear_algebra::MatrixNorm::Inf,
+        linear_algebra::MatrixNorm::Max,
+    ] {
+        assert!(linear_algebra::Matrix::matrix_norm(&nan, n).is_nan());
+    }
+}
+
+#[test]
+fn test_distance() {
+    let mut v1 = linear_algebra::Vector::new(2);
+    v1.vector_set(vec![1.0, 1.0]);
+    let mut v2 = linear_algebra::Vector::new(2);
+    v2.vector_set(vec![4.0, 5.0]);
+
+    assert_eq!(
+        linear_algebra::distance(&v1, &v2, linear_algebra::VectorNorm::Two),
+        5.0
+    );
+    assert_eq!(
+        linear_algebra::distance(&v1, &v2, linear_algebra::VectorNorm::One),
+        7.0
+    );
+
+    let m1 = linear_algebra::Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+    let m2 = linear_algebra::Matrix::from_vec(2, 2, vec![1.0, 0.0, 3.0, 7.0]);
+
+    assert_eq!(
+        linear_algebra::distance(&m1, &m2, linear_algebra::MatrixNorm::Max),
+        3.0
+    );
+
+    assert_eq!(
+        linear_algebra::try_distance(
+            &v1,
+            &linear_algebra::Vector::new(3),
+            linear_algebra::VectorNorm::Inf
+        ),
+        Err(linear_algebra::LinAlgError::DimensionMismatch {
+            left: vec![2],
+            right: vec![3],
+        })
+    );
 }
@@ -0,0 +1,242 @@
+// tests/matrix_functions_tests.rs
+
+mod common;
+
+use common::{assert_close, identity};
+use LAMS_rs::linear_algebra::*;
+use LAMS_rs::matrix_functions::*;
+
+// Well conditioned, with eigenvalues in the right half plane
+fn test_matrix() -> linear_algebra::Matrix {
+    linear_algebra::Matrix::from_vec(3, 3, vec![4.0, 1.0, -0.5, 0.5, 3.0, 1.0, -1.0, 0.5, 5.0])
+}
+
+#[test]
+fn test_expm() {
+    // exp(0) = I and exp of a diagonal matrix
+    assert_eq!(
+        matrix_functions::expm(&linear_algebra::Matrix::new(3, 3))
+            .unwrap()
+            .data,
+        identity(3).data
+    );
+
+    let d = linear_algebra::Matrix::from_vec(2, 2, vec![1.0, 0.0, 0.0, -2.0]);
+
+    assert_close(
+        &matrix_functions::expm(&d).unwrap().data,
+        &[1f64.exp(), 0.0, 0.0, (-2f64).exp()],
+        1e-15,
+    );
+
+    // Nilpotent: exp(N) = I + N
+    let n = linear_algebra::Matrix::from_vec(2, 2, vec![0.0, 1.0, 0.0, 0.0]);
+
+    assert_close(
+        &matrix_functions::expm(&n).unwrap().data,
+        &[1.0, 1.0, 0.0, 1.0],
+        1e-15,
+    );
+
+    // A rotation generator, with a norm large enough to need squaring
+    let t = 20.0f64;
+    let r = linear_algebra::Matrix::from_vec(2, 2, vec![0.0, -t, t, 0.0]);
+
+    assert_close(
+        &matrix_functions::expm(&r).unwrap().data,
+        &[t.cos(), -t.sin(), t.sin(), t.cos()],
+        1e-12,
+    );
+
+    // exp(A) exp(-A) = I at every Pade degree
+    for scale in [0.001, 0.1, 0.5, 1.0, 3.0, 10.0] {
+        let a = &test_matrix() * (scale / 5.0);
+        let product =
+            &matrix_functions::expm(&a).unwrap() * &matrix_functions::expm(&(&a * -1.0)).unwrap();
+
+        assert_close(&product.data, &identity(3).data, 1e-11);
+    }
+
+    assert_eq!(
+        matrix_functions::expm(&linear_algebra::Matrix::new(2, 3)).unwrap_err(),
+        linear_algebra::LinAlgError::NotSquare { rows: 2, cols: 3 }
+    );
+
+    // Non-finite input is rejected up front, and a result too large for f64
+    // is an error rather than a matrix of NaN
+    let infinite = linear_algebra::Matrix::from_vec(2, 2, vec![f64::INFINITY, 0.0, 0.0, 1.0]);
+
+    assert_eq!(
+        matrix_functions::expm(&infinite).unwrap_err(),
+        linear_algebra::LinAlgError::InvalidParameter {
+            name: "matrix",
+            reason: "has a non-finite entry",
+        }
+    );
+    assert!(matrix_functions::expm(&(&d * f64::NAN)).is_err());
+    assert_eq!(
+        matrix_functions::expm(&(&d * 1e300)).unwrap_err(),
+        linear_algebra::LinAlgError::Overflow
+    );
+    assert_eq!(
+        matrix_functions::expm(&linear_algebra::Matrix::from_vec(1, 1, vec![1e308])).unwrap_err(),
+        linear_algebra::LinAlgError::Overflow
+    );
+
+    // Large results that still fit are fine
+    let large = matrix_functions::expm(&(&d * 300.0)).unwrap();
+
+    assert!((large.data[0] / 300f64.exp() - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_sqrtm() {
+    let a = test_matrix();
+    let x = matrix_functions::sqrtm(&a).unwrap();
+
+    assert_close(&(&x * &x).data, &a.data, 1e-12);
+
+    let d = linear_algebra::Matrix::from_vec(2, 2, vec![4.0, 0.0, 0.0, 9.0]);
+
+    assert_close(
+        &matrix_functions::sqrtm(&d).unwrap().data,
+        &[2.0, 0.0, 0.0, 3.0],
+        1e-14,
+    );
+
+    // Complex eigenvalues 1 +- i have a real principal square root
+    let c = linear_algebra::Matrix::from_vec(2, 2, vec![1.0, -1.0, 1.0, 1.0]);
+    let y = matrix_functions::sqrtm(&c).unwrap();
+
+    assert_close(&(&y * &y).data, &c.data, 1e-13);
+
+    // Entries whose squares and determinants overflow
+    for scale in [1e160, 1e300] {
+        let big = linear_algebra::Matrix::from_vec(2, 2, vec![4.0, 1.0, 0.0, 9.0]) * scale;
+        let root = matrix_functions::sqrtm(&big).unwrap();
+
+        assert_close(
+            &(root * (1.0 / scale.sqrt())).data,
+            &[2.0, 0.2, 0.0, 3.0],
+            1e-14,
+        );
+    }
+
+    let negative = linear_algebra::Matrix::from_vec(2, 2, vec![-1.0, 0.0, 0.0, 4.0]);
+
+    assert_eq!(
+        matrix_functions::sqrtm(&negative).unwrap_err(),
+        linear_algebra::LinAlgError::EigenvalueOnNegativeAxis { eigenvalue: -1.0 }
+    );
+
+    // Empty matrices are their own square root, like their own exp and log
+    let empty = linear_algebra::Matrix::new(0, 0);
+
+    assert_eq!(matrix_functions::sqrtm(&empty), Ok(empty.clone()));
+    assert_eq!(matrix_functions::expm(&empty), Ok(empty.clone()));
+    assert_eq!(matrix_functions::logm(&empty), Ok(empty));
+}
+
+#[test]
+fn test_logm() {
+    assert_close(
+        &matrix_functions::logm(&identity(3)).unwrap().data,
+        &linear_algebra::Matrix::new(3, 3).data,
+        1e-15,
+    );
+
+    let a = test_matrix();
+    let log = matrix_functions::logm(&a).unwrap();
+
+    assert_close(&matrix_functions::expm(&log).unwrap().data, &a.data, 1e-11);
+
+    // log(exp(B)) = B while B's eigenvalues have |imag| < pi
+    let b = &test_matrix() * 0.3;
+
+    assert_close(
+        &matrix_functions::logm(&matrix_functions::expm(&b).unwrap())
+            .unwrap()
+            .data,
+        &b.data,
+        1e-11,
+    );
+
+    let singular = linear_algebra::Matrix::from_vec(2, 2, vec![1.0, 0.0, 0.0, 0.0]);
+
+    assert_eq!(
+        matrix_functions::logm(&singular).unwrap_err(),
+        linear_algebra::LinAlgError::EigenvalueOnNegativeAxis { eigenvalue: 0.0 }
+    );
+    assert_eq!(
+        linear_algebra::LinAlgError::EigenvalueOnNegativeAxis { eigenvalue: -2.0 }.to_string(),
+        "Eigenvalue -2 lies on the closed negative real axis!"
+    );
+}
+
+#[test]
+fn test_matrix_power() {
+    let a = test_matrix();
+
+    assert_eq!(
+        matrix_functions::matrix_power(&a, 0).unwrap().data,
+        identity(3).data
+    );
+    assert_close(
+        &matrix_functions::matrix_power(&a, 5).unwrap().data,
+        &(&(&(&(&a * &a) * &a) * &a) * &a).data,
+        1e-9,
+    );
+
+    let inverse_squared = matrix_functions::matrix_power(&a, -2).unwrap();
+
+    assert_close(
+        &(&inverse_squared * &(&a * &a)).data,
+        &identity(3).data,
+        1e-13,
+    );
+
+    let singular = linear_algebra::Matrix::from_vec(2, 2, vec![1.0, 2.0, 2.0, 4.0]);
+
+    assert_eq!(
+        matrix_functions::matrix_power(&singular, -1).unwrap_err(),
+        linear_algebra::LinAlgError::SingularMatrix
+    );
+    assert!(matrix_functions::matrix_power(&singular, 3).is_ok());
+}
+
+#[test]
+fn test_powm() {
+    let a = test_matrix();
+
+    assert_close(
+        &matrix_functions::powm(&a, 0.5).unwrap().data,
+        &matrix_functions::sqrtm(&a).unwrap().data,
+        1e-14,
+    );
+    assert_eq!(
+        matrix_functions::powm(&a, 2.0).unwrap().data,
+        (&a * &a).data
+    );
+
+    // (A^1.5)^2 = A^3 and A^-0.5 A^0.5 = I
+    let p = matrix_functions::powm(&a, 1.5).unwrap();
+
+    assert_close(
+        &(&p * &p).data,
+        &matrix_functions::matrix_power(&a, 3).unwrap().data,
+        1e-9,
+    );
+
+    let inverse_root = matrix_functions::powm(&a, -0.5).unwrap();
+    let root = matrix_functions::powm(&a, 0.5).unwrap();
+
+    assert_close(&(&inverse_root * &root).data, &identity(3).data, 1e-12);
+
+    let negative = linear_algebra::Matrix::from_vec(1, 1, vec![-4.0]);
+
+    assert!(matrix_functions::powm(&negative, 0.25).is_err());
+    assert_eq!(
+        matrix_functions::powm(&negative, 3.0).unwrap().data,
+        vec![-64.0]
+    );
+}
@@ -0,0 +1,232 @@
+// tests/sparse_tests.rs
+
+use LAMS_rs::linear_algebra::*;
+use LAMS_rs::sparse::*;
+
+// 4 x 5 with a duplicate entry at (0, 1)
+fn sample_coo() -> sparse::CooMatrix {
+    sparse::CooMatrix::from_triplets(
+        4,
+        5,
+        &[
+            (3, 4, 5.0),
+            (0, 1, 1.0),
+            (2, 0, -2.0),
+            (0, 1, 2.0),
+            (1, 3, 4.0),
+            (2, 2, 6.0),
+        ],
+    )
+}
+
+fn sample_dense() -> linear_algebra::Matrix {
+    linear_algebra::Matrix::from_vec(
+        4,
+        5,
+        vec![
+            0.0, 3.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 4.0, 0.0, -2.0, 0.0, 6.0, 0.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 5.0,
+        ],
+    )
+}
+
+#[test]
+fn test_coo_conversions() {
+    let coo = sample_coo();
+
+    assert_eq!(coo.nnz(), 6);
+    assert_eq!(coo.to_dense(), sample_dense());
+
+    let csr = coo.to_csr();
+
+    // The duplicate is summed and the columns of each row are sorted
+    assert_eq!(csr.nnz(), 5);
+    assert_eq!(csr.indptr, vec![0, 1, 2, 4, 5]);
+    assert_eq!(csr.indices, vec![1, 3, 0, 2, 4]);
+    assert_eq!(csr.values, vec![3.0, 4.0, -2.0, 6.0, 5.0]);
+
+    let csc = coo.to_csc();
+
+    assert_eq!(csc.indptr, vec![0, 1, 2, 3, 4, 5]);
+    assert_eq!(csc.indices, vec![2, 0, 2, 1, 3]);
+    assert_eq!(csc.to_dense(), sample_dense());
+
+    assert_eq!(csr.to_csc(), csc);
+    assert_eq!(csc.to_csr(), csr);
+    assert_eq!(csr.to_coo().to_dense(), sample_dense());
+    assert_eq!(csc.to_coo().to_csr(), csr);
+}
+
+#[test]
+fn test_from_dense() {
+    let dense = sample_dense();
+
+    let csr = sparse::CsrMatrix::from_dense(&dense);
+    let csc = sparse::CscMatrix::from_dense(&dense);
+
+    assert_eq!(csr.nnz(), 5);
+    assert_eq!(csr.to_dense(), dense);
+    assert_eq!(csc.to_dense(), dense);
+    assert_eq!(sparse::CooMatrix::from_dense(&dense).nnz(), 5);
+
+    assert_eq!(csr.get(1, 3), 4.0);
+    assert_eq!(csr.get(1, 2), 0.0);
+    assert_eq!(csc.get(2, 0), -2.0);
+    assert_eq!(csc.get(3, 0), 0.0);
+}
+
+#[test]
+#[should_panic]
+fn test_coo_push_panic() {
+    let mut coo = sparse::CooMatrix::new(2, 2);
+
+    coo.push(2, 0, 1.0);
+}
+
+#[test]
+fn test_try_get() {
+    let csr = sample_coo().to_csr();
+
+    assert_eq!(
+        csr.try_get(4, 0),
+        Err(linear_algebra::LinAlgError::IndexOutOfBounds {
+            index: vec![4, 0],
+            shape: vec![4, 5],
+        })
+    );
+    assert!(sample_coo().to_csc().try_get(0, 5).is_err());
+}
+
+#[test]
+fn test_transpose() {
+    let dense = sample_dense();
+    let expected = dense.view().transpose().to_matrix();
+
+    assert_eq!(sample_coo().transpose().to_dense(), expected);
+    assert_eq!(sample_coo().to_csr().transpose().to_dense(), expected);
+    assert_eq!(sample_coo().to_csc().transpose().to_dense(), expected);
+}
+
+#[test]
+fn test_sparse_vector_mul() {
+    let mut v = linear_algebra::Vector::new(5);
+    v.vector_set(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+
+    let expected = &sample_dense() * &v;
+
+    let csr = sample_coo().to_csr();
+    let csc = sample_coo().to_csc();
+
+    assert_eq!(&csr * &v, expected);
+    assert_eq!(&csc * &v, expected);
+    assert!(sparse::CsrMatrix::try_csr_vector_mul(&csr, &linear_algebra::Vector::new(4)).is_err());
+}
+
+#[test]
+fn test_sparse_dense_mul() {
+    let b = linear_algebra::Matrix::from_vec(
+        5,
+        2,
+        vec![1.0, 0.5, -1.0, 2.0, 0.0, 1.0, 3.0, -2.0, 1.5, 1.0],
+    );
+
+    let expected = &sample_dense() * &b;
+
+    assert_eq!(&sample_coo().to_csr() * &b, expected);
+    assert_eq!(&sample_coo().to_csc() * &b, expected);
+    assert!(
+        sparse::CsrMatrix::try_csr_matrix_mul(&sample_coo().to_csr(), &sample_dense()).is_err()
+    );
+}
+
+#[test]
+fn test_sparse_add() {
+    let a = sample_coo().to_csr();
+    let mut other = sparse::CooMatrix::new(4, 5);
+    other.push(0, 1, -3.0);
+    other.push(0, 0, 1.0);
+    other.push(3, 4, 1.0);
+
+    let b = other.to_csr();
+    let sum = &a + &b;
+
+    assert_eq!(sum.to_dense(), &sample_dense() + &other.to_dense());
+
+    // The cancelled entry at (0, 1) is not stored
+    assert_eq!(sum.nnz(), 5);
+
+    let csc_sum = &sample_coo().to_csc() + &other.to_csc();
+    assert_eq!(csc_sum.to_dense(), sum.to_dense());
+
+    assert!(sparse::CsrMatrix::try_csr_add(&a, &a.transpose()).is_err());
+}
+
+#[test]
+#[should_panic]
+fn test_sparse_add_panic() {
+    let a = sample_coo().to_csr();
+
+    sparse::CsrMatrix::csr_add(&a, &a.transpose());
+}
+
+#[test]
+fn test_sparse_mul() {
+    let a = sample_coo().to_csr();
+    let b = a.transpose();
+
+    let product = &a * &b;
+    let expected = &sample_dense() * &sample_dense().view().transpose().to_matrix();
+
+    assert_eq!((product.rows, product.cols), (4, 4));
+    assert_eq!(product.to_dense(), expected);
+
+    let csc_product = &sample_coo().to_csc() * &sample_coo().to_csc().transpose();
+    assert_eq!(csc_product.to_dense(), expected);
+
+    assert!(sparse::CsrMatrix::try_csr_mul(&a, &a).is_err());
+    assert!(sparse::CscMatrix::try_csc_mul(&a.to_csc(), &a.to_csc()).is_err());
+}
+
+#[test]
+fn test_sparse_laplacian() {
+    // 1-D finite difference Laplacian, tridiagonal [-1, 2, -1]
+    let n = 1000;
+    let mut coo = sparse::CooMatrix::new(n, n);
+
+    for i in 0..n {
+        coo.push(i, i, 2.0);
+
+        if i > 0 {
+            coo.push(i, i - 1, -1.0);
+        }
+
+        if i + 1 < n {
+            coo.push(i, i + 1, -1.0);
+        }
+    }
+
+    let laplacian = coo.to_csr();
+
+    assert_eq!(laplacian.nnz(), 3 * n - 2);
+
+    // The second difference of a linear function vanishes in the interior
+    let mut v = linear_algebra::Vector::new(n);
+    v.vector_set((0..n).map(|i| i as f64).collect());
+
+    let result = &laplacian * &v;
+
+    assert_eq!(result[0], -1.0);
+    assert!(result.data[1..n - 1].iter().all(|&x| x == 0.0));
+    assert_eq!(result[n - 1], n as f64);
+
+    // L^2 is pentadiagonal
+    assert_eq!((&laplacian * &laplacian).nnz(), 5 * n - 6);
+}
+
+#[test]
+fn test_sparse_integers() {
+    let coo = sparse::CooMatrix::from_triplets(2, 2, &[(0, 0, 2i64), (1, 0, 3), (1, 1, -1)]);
+    let csr = coo.to_csr();
+
+    assert_eq!((&csr * &csr).to_dense().data, vec![4i64, 0, 3, 1]);
+}
@@ -0,0 +1,127 @@
+// tests/static_matrix_tests.rs
+
+use LAMS_rs::linear_algebra::*;
+use LAMS_rs::static_matrix::*;
+
+#[test]
+fn test_static_vector() {
+    let a = static_matrix::SVector::new([1.0, 2.0, 3.0]);
+    let b = static_matrix::SVector::new([3.0, 4.0, 5.0]);
+
+    assert_eq!(a.dot(&b), 26.0);
+    assert_eq!(static_matrix::SVector::new([3.0, 4.0]).norm(), 5.0);
+    assert_eq!((a + b).data, [4.0, 6.0, 8.0]);
+    assert_eq!((b - a).data, [2.0, 2.0, 2.0]);
+    assert_eq!((-a).data, [-1.0, -2.0, -3.0]);
+    assert_eq!(a * 2.0, 2.0 * a);
+
+    let mut c = a;
+    c += b;
+    c -= a;
+    c *= 0.5;
+    c[0] = 0.0;
+
+    assert_eq!(c.data, [0.0, 2.0, 2.5]);
+    assert_eq!(c[2], 2.5);
+    assert_eq!(static_matrix::SVector::<4, i32>::zeros().data, [0, 0, 0, 0]);
+
+    // Stored inline: exactly the size of the elements
+    assert_eq!(
+        std::mem::size_of::<static_matrix::SVector<3>>(),
+        3 * std::mem::size_of::<f64>()
+    );
+}
+
+#[test]
+fn test_static_matrix() {
+    let a = static_matrix::SMatrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let b = static_matrix::SMatrix::new([[7.0, 8.0], [9.0, 10.0], [11.0, 12.0]]);
+
+    // (2 x 3)(3 x 2) = 2 x 2, checked by the type system
+    let c: static_matrix::SMatrix<2, 2> = a * b;
+
+    assert_eq!(c.data, [[58.0, 64.0], [139.0, 154.0]]);
+    assert_eq!(a.transpose().data, [[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]]);
+    assert_eq!(a.row(1).data, [4.0, 5.0, 6.0]);
+    assert_eq!(a.column(2).data, [3.0, 6.0]);
+    assert_eq!(c.trace(), 212.0);
+
+    let v = static_matrix::SVector::new([1.0, 0.0, -1.0]);
+
+    assert_eq!((a * v).data, [-2.0, -2.0]);
+
+    let mut i = static_matrix::SMatrix::<2, 2>::identity();
+
+    assert_eq!(i * c, c);
+
+    i *= c;
+    i -= c;
+
+    assert_eq!(i, static_matrix::SMatrix::zeros());
+    assert_eq!((a + a).data, (2.0 * a).data);
+    assert_eq!((-a)[(1, 2)], -6.0);
+
+    let mut d = a;
+    d[(0, 0)] = 10.0;
+
+    assert_eq!(d.data[0], [10.0, 2.0, 3.0]);
+
+    // Integer elements work too
+    let m = static_matrix::SMatrix::new([[1, 2], [3, 4]]);
+
+    assert_eq!((m * m).data, [[7, 10], [15, 22]]);
+    assert_eq!(
+        std::mem::size_of::<static_matrix::SMatrix<3, 4, f32>>(),
+        12 * std::mem::size_of::<f32>()
+    );
+}
+
+#[test]
+fn test_static_conversions() {
+    let a = static_matrix::SMatrix::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let m = a.to_matrix();
+
+    assert_eq!((m.rows, m.cols), (2, 3));
+    assert_eq!(m.data, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(static_matrix::SMatrix::<2, 3>::from_matrix(&m), a);
+    assert_eq!(linear_algebra::Matrix::from(a), m);
+
+    // Agrees with the dynamic product
+    let b = a.transpose();
+
+    assert_eq!(
+        (a * b).to_matrix(),
+        linear_algebra::Matrix::matrix_mul(&m, &b.to_matrix())
+    );
+
+    let v = static_matrix::SVector::new([1.0, 2.0]);
+    let dynamic = v.to_vector();
+
+    assert_eq!(dynamic.size, 2);
+    assert_eq!(dynamic.data, vec![1.0, 2.0]);
+    assert_eq!(linear_algebra::Vector::from(v), dynamic);
+    assert_eq!(static_matrix::SVector::<2>::from_vector(&dynamic), v);
+
+    assert_eq!(
+        static_matrix::SMatrix::<3, 2>::try_from_matrix(&m),
+        Err(linear_algebra::LinAlgError::DimensionMismatch {
+            left: vec![2, 3],
+            right: vec![3, 2],
+        })
+    );
+    assert_eq!(
+        static_matrix::SVector::<3>::try_from_vector(&dynamic),
+        Err(linear_algebra::LinAlgError::DimensionMismatch {
+            left: vec![2],
+            right: vec![3],
+        })
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_static_conversion_panic() {
+    let m = linear_algebra::Matrix::new(2, 2);
+
+    let _s = static_matrix::SMatrix::<2, 3>::from_matrix(&m);
+}
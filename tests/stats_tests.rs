@@ -11,7 +11,7 @@ pub fn test_bernoulli_new() {
 #[test]
 #[should_panic]
 pub fn test_bernoulli_new_panic() {
-    let b = stats::Bernoulli::new(1.5);
+    let _b = stats::Bernoulli::new(1.5);
 }
 
 #[test]
@@ -71,7 +71,7 @@ pub fn test_binomial_new() {
 #[test]
 #[should_panic]
 pub fn test_binomial_new_panic() {
-    let b = stats::Binomial::new(10, 1.5);
+    let _b = stats::Binomial::new(10, 1.5);
 }
 
 #[test]
@@ -166,7 +166,7 @@ pub fn test_discreteuniform_new() {
 #[test]
 #[should_panic]
 pub fn test_discreteuniform_new_panic() {
-    let d = stats::DiscreteUniform::new(10, 0);
+    let _d = stats::DiscreteUniform::new(10, 0);
 }
 
 #[test]
@@ -232,7 +232,7 @@ pub fn test_geometric_new() {
 #[test]
 #[should_panic]
 pub fn test_geometric_new_panic() {
-    let g = stats::Geometric::new(1.5);
+    let _g = stats::Geometric::new(1.5);
 }
 
 #[test]
@@ -0,0 +1,162 @@
+// tests/svd_tests.rs
+
+mod common;
+
+use common::{assert_close, assert_orthonormal_columns, transpose};
+use LAMS_rs::linear_algebra::*;
+use LAMS_rs::svd::*;
+
+fn sample() -> linear_algebra::Matrix {
+    linear_algebra::Matrix::from_vec(
+        4,
+        3,
+        vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 10.0, -1.0, 0.5, 2.0],
+    )
+}
+
+#[test]
+fn test_svd_thin() {
+    let a = sample();
+
+    let svd = svd::SVD::new(&a).unwrap();
+
+    assert_eq!((svd.u.rows, svd.u.cols), (4, 3));
+    assert_eq!((svd.v.rows, svd.v.cols), (3, 3));
+    assert_orthonormal_columns(&svd.u);
+    assert_orthonormal_columns(&svd.v);
+    assert_close(&svd.reconstruct().data, &a.data, 1e-12);
+
+    for i in 1..3 {
+        assert!(svd.singular_values[i - 1] >= svd.singular_values[i]);
+    }
+}
+
+#[test]
+fn test_svd_full() {
+    let a = sample();
+
+    let svd = svd::SVD::full(&a).unwrap();
+
+    assert_eq!((svd.u.rows, svd.u.cols), (4, 4));
+    assert_orthonormal_columns(&svd.u);
+    assert_close(&svd.reconstruct().data, &a.data, 1e-12);
+
+    // Wide matrices go through the transpose
+    let wide = transpose(&a);
+    let svd_wide = svd::SVD::full(&wide).unwrap();
+
+    assert_eq!((svd_wide.u.rows, svd_wide.u.cols), (3, 3));
+    assert_eq!((svd_wide.v.rows, svd_wide.v.cols), (4, 4));
+    assert_orthonormal_columns(&svd_wide.v);
+    assert_close(&svd_wide.reconstruct().data, &wide.data, 1e-12);
+    assert_close(
+        &svd_wide.singular_values.data,
+        &svd.singular_values.data,
+        1e-12,
+    );
+}
+
+#[test]
+fn test_svd_known_values() {
+    let a = linear_algebra::Matrix::from_vec(2, 2, vec![3.0, 0.0, 4.0, 5.0]);
+
+    let svd = svd::SVD::new(&a).unwrap();
+
+    // A^T A has eigenvalues 45 and 5
+    assert_close(
+        &svd.singular_values.data,
+        &[45.0f64.sqrt(), 5.0f64.sqrt()],
+        1e-12,
+    );
+    assert!((svd.norm2() - 45.0f64.sqrt()).abs() < 1e-12);
+    assert!((svd.condition_number() - 3.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_svd_rank_deficient() {
+    // Third column is the sum of the first two
+    let a = linear_algebra::Matrix::from_vec(
+        4,
+        3,
+        vec![1.0, 0.0, 1.0, 0.0, 1.0, 1.0, 1.0, 1.0, 2.0, 2.0, -1.0, 1.0],
+    );
+
+    let svd = svd::SVD::full(&a).unwrap();
+
+    assert_eq!(svd.rank(None), 2);
+    assert_eq!(svd::rank(&a, None).unwrap(), 2);
+    assert!(svd.condition_number() > 1e12);
+    assert_orthonormal_columns(&svd.u);
+    assert_close(&svd.truncate(2).data, &a.data, 1e-12);
+
+    // The best rank one approximation is off by exactly sigma_2 in the 2-norm
+    let error = &a - &svd.truncate(1);
+    let error_norm = svd::norm2(&error).unwrap();
+    assert!((error_norm - svd.singular_values[1]).abs() < 1e-12);
+
+    // Columns e_i - e_(i+1) span the vectors summing to zero, so the missing
+    // direction of U is (1, 1, 1, 1, 1) / sqrt(5), not close to any axis
+    let mut differences = linear_algebra::Matrix::new(5, 4);
+
+    for j in 0..4 {
+        differences[(j, j)] = 1.0;
+        differences[(j + 1, j)] = -1.0;
+    }
+
+    let svd = svd::SVD::full(&differences).unwrap();
+
+    assert_orthonormal_columns(&svd.u);
+    assert_close(&svd.reconstruct().data, &differences.data, 1e-12);
+}
+
+#[test]
+fn test_pinv() {
+    let a = sample();
+
+    let pinv = svd::pinv(&a, None).unwrap();
+
+    assert_eq!((pinv.rows, pinv.cols), (3, 4));
+
+    // Moore-Penrose conditions
+    let apa = &(&a * &pinv) * &a;
+    let pap = &(&pinv * &a) * &pinv;
+
+    assert_close(&apa.data, &a.data, 1e-12);
+    assert_close(&pap.data, &pinv.data, 1e-12);
+
+    // Full column rank, so pinv(A) A = I
+    let identity = &pinv * &a;
+    assert_close(
+        &identity.data,
+        &[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0],
+        1e-12,
+    );
+}
+
+#[test]
+fn test_svd_least_squares() {
+    // Rank deficient, so the minimum norm solution is unique
+    let a = linear_algebra::Matrix::from_vec(2, 2, vec![1.0, 1.0, 1.0, 1.0]);
+    let mut b = linear_algebra::Vector::new(2);
+    b.vector_set(vec![2.0, 2.0]);
+
+    let svd = svd::SVD::new(&a).unwrap();
+    let x = svd.solve_least_squares(&b, None).unwrap();
+
+    assert_close(&x.data, &[1.0, 1.0], 1e-12);
+    assert!(svd
+        .solve_least_squares(&linear_algebra::Vector::new(3), None)
+        .is_err());
+}
+
+#[test]
+fn test_svd_zero_matrix() {
+    let a = linear_algebra::Matrix::new(3, 2);
+
+    let svd = svd::SVD::full(&a).unwrap();
+
+    assert_close(&svd.singular_values.data, &[0.0, 0.0], 0.0);
+    assert_eq!(svd.rank(None), 0);
+    assert_orthonormal_columns(&svd.u);
+    assert_close(&svd.pinv(None).data, &[0.0; 6], 0.0);
+}